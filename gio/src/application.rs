@@ -9,6 +9,8 @@ use glib::{
     translate::*,
 };
 
+#[cfg(feature = "clap")]
+use crate::prelude::ApplicationExt;
 use crate::{Application, ApplicationCommandLine, File, ffi};
 
 pub trait ApplicationExtManual: IsA<Application> {
@@ -21,9 +23,28 @@ pub trait ApplicationExtManual: IsA<Application> {
     fn run_with_args<S: AsRef<str>>(&self, args: &[S]) -> ExitCode {
         let argv: Vec<&str> = args.iter().map(|a| a.as_ref()).collect();
         let argc = argv.len() as i32;
+
+        // Keep the arguments for `connect_handle_local_options_with_clap()`, as the signal
+        // doesn't provide them.
+        #[cfg(feature = "clap")]
+        unsafe {
+            self.set_qdata(
+                clap_args_quark(),
+                argv.iter()
+                    .map(std::ffi::OsString::from)
+                    .collect::<Vec<_>>(),
+            );
+        }
+
         let exit_code = unsafe {
             ffi::g_application_run(self.as_ref().to_glib_none().0, argc, argv.to_glib_none().0)
         };
+
+        #[cfg(feature = "clap")]
+        unsafe {
+            let _ = self.steal_qdata::<Vec<std::ffi::OsString>>(clap_args_quark());
+        }
+
         ExitCode::try_from(exit_code).unwrap()
     }
 
@@ -97,6 +118,50 @@ pub trait ApplicationExtManual: IsA<Application> {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Handles command lines by parsing their arguments with `command`.
+    ///
+    /// This sets [`HANDLES_COMMAND_LINE`](crate::ApplicationFlags::HANDLES_COMMAND_LINE) and
+    /// [`SEND_ENVIRONMENT`](crate::ApplicationFlags::SEND_ENVIRONMENT) on the application, so it
+    /// has to be called before the application is registered. The arguments of remote
+    /// invocations are then forwarded to the primary instance together with their working
+    /// directory and environment, which are available from the [`ApplicationCommandLine`] passed
+    /// to `f`. Relative paths in the matches should be resolved with
+    /// [`create_file_for_arg()`](crate::prelude::ApplicationCommandLineExt::create_file_for_arg)
+    /// or against [`cwd()`](crate::prelude::ApplicationCommandLineExt::cwd).
+    ///
+    /// If parsing fails or help or version information was requested, the output is printed on
+    /// the invoking command line and `f` is not called.
+    ///
+    /// GLib only passes unknown options through to the command line if no options were added
+    /// with [`add_main_option()`](crate::prelude::ApplicationExt::add_main_option) or
+    /// [`add_main_option_entries()`](Self::add_main_option_entries), so don't combine both.
+    #[cfg(feature = "clap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+    #[doc(alias = "command-line")]
+    fn connect_command_line_with_clap<
+        F: Fn(&Self, &ApplicationCommandLine, clap::ArgMatches) -> ExitCode + 'static,
+    >(
+        &self,
+        command: clap::Command,
+        f: F,
+    ) -> SignalHandlerId {
+        use crate::prelude::ApplicationCommandLineExtManual;
+
+        self.set_flags(
+            self.flags()
+                | crate::ApplicationFlags::HANDLES_COMMAND_LINE
+                | crate::ApplicationFlags::SEND_ENVIRONMENT,
+        );
+
+        self.connect_command_line(move |app, command_line| {
+            match command_line.try_get_matches(command.clone()) {
+                Ok(matches) => f(app, command_line, matches),
+                Err(err) => command_line.print_clap_error(&err),
+            }
+        })
+    }
+
     #[doc(alias = "handle-local-options")]
     fn connect_handle_local_options<
         F: Fn(&Self, &glib::VariantDict) -> ControlFlow<ExitCode> + 'static,
@@ -136,6 +201,86 @@ pub trait ApplicationExtManual: IsA<Application> {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Parses the arguments the application is run with with `command` in the local instance.
+    ///
+    /// `f` is called with the matches before the application is registered and can stop the
+    /// application with an exit code, or return [`ControlFlow::Continue`] to continue with
+    /// the default processing, e.g. forwarding the command line to the primary instance. This
+    /// allows handling options like `--version` locally.
+    ///
+    /// These are the arguments passed to [`run_with_args()`](Self::run_with_args), or
+    /// [`std::env::args_os()`] if the application is run from elsewhere. If parsing fails or
+    /// help or version information was requested, the output is printed locally and the
+    /// application exits.
+    ///
+    /// GLib only passes unknown options through if the application handles command lines, so
+    /// this sets [`HANDLES_COMMAND_LINE`](crate::ApplicationFlags::HANDLES_COMMAND_LINE) on the
+    /// application and it should be combined with
+    /// [`connect_command_line_with_clap()`](Self::connect_command_line_with_clap).
+    #[cfg(feature = "clap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+    #[doc(alias = "handle-local-options")]
+    fn connect_handle_local_options_with_clap<
+        F: Fn(&Self, &clap::ArgMatches) -> ControlFlow<ExitCode> + 'static,
+    >(
+        &self,
+        command: clap::Command,
+        f: F,
+    ) -> SignalHandlerId {
+        self.set_flags(self.flags() | crate::ApplicationFlags::HANDLES_COMMAND_LINE);
+
+        self.connect_handle_local_options(move |app, _| {
+            let args = unsafe {
+                app.qdata::<Vec<std::ffi::OsString>>(clap_args_quark())
+                    .map(|args| args.as_ref().clone())
+            }
+            .unwrap_or_else(|| std::env::args_os().collect());
+
+            match command.clone().try_get_matches_from(args) {
+                Ok(matches) => f(app, &matches),
+                Err(err) => {
+                    let _ = err.print();
+                    ControlFlow::Break(
+                        ExitCode::try_from(err.exit_code()).unwrap_or(ExitCode::FAILURE),
+                    )
+                }
+            }
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds options to be parsed by the application.
+    ///
+    /// The callbacks of the entries are called while the local instance parses its command
+    /// line, and the callbacks of array options right after parsing. Unlike options added with
+    /// [`add_main_option()`](crate::prelude::ApplicationExt::add_main_option), the parsed values
+    /// are not available in the options dictionary passed to `handle-local-options`.
+    #[doc(alias = "g_application_add_main_option_entries")]
+    fn add_main_option_entries(&self, entries: impl IntoIterator<Item = glib::OptionEntry>) {
+        let entries = glib::OptionEntries::new(entries);
+        unsafe {
+            ffi::g_application_add_main_option_entries(
+                self.as_ref().to_glib_none().0,
+                entries.as_ptr(),
+            );
+        }
+
+        // The group dispatches the callbacks of the main group to the entries and keeps them
+        // alive as long as the application, as GLib keeps pointers to their strings.
+        self.add_option_group(entries.into_group());
+    }
+
+    #[doc(alias = "g_application_add_option_group")]
+    fn add_option_group(&self, group: glib::OptionGroup) {
+        unsafe {
+            ffi::g_application_add_option_group(
+                self.as_ref().to_glib_none().0,
+                group.into_glib_ptr(),
+            );
+        }
+    }
+
     #[doc(alias = "g_application_hold")]
     fn hold(&self) -> ApplicationHoldGuard {
         unsafe {
@@ -155,6 +300,12 @@ pub trait ApplicationExtManual: IsA<Application> {
 
 impl<O: IsA<Application>> ApplicationExtManual for O {}
 
+#[cfg(feature = "clap")]
+fn clap_args_quark() -> glib::Quark {
+    static QUARK: std::sync::OnceLock<glib::Quark> = std::sync::OnceLock::new();
+    *QUARK.get_or_init(|| glib::Quark::from_str("gtk-rs-application-clap-args"))
+}
+
 #[derive(Debug)]
 #[must_use = "if unused the Application will immediately be released"]
 pub struct ApplicationHoldGuard(glib::WeakRef<Application>);
@@ -184,3 +335,73 @@ impl Drop for ApplicationBusyGuard {
         }
     }
 }
+
+#[cfg(all(test, feature = "clap"))]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+    use crate::{ApplicationFlags, prelude::ApplicationCommandLineExt};
+
+    fn command() -> clap::Command {
+        clap::Command::new("test").arg(
+            clap::Arg::new("count")
+                .long("count")
+                .value_parser(clap::value_parser!(u32)),
+        )
+    }
+
+    #[test]
+    fn command_line_with_clap() {
+        let app = Application::new(None, ApplicationFlags::NON_UNIQUE);
+        let count = Rc::new(Cell::new(None));
+        app.connect_command_line_with_clap(command(), {
+            let count = count.clone();
+            move |_, command_line, matches| {
+                assert!(!command_line.is_remote());
+                count.set(matches.get_one::<u32>("count").copied());
+                ExitCode::SUCCESS
+            }
+        });
+        assert!(app.flags().contains(ApplicationFlags::HANDLES_COMMAND_LINE));
+
+        assert_eq!(
+            app.run_with_args(&["test", "--count", "3"]),
+            ExitCode::SUCCESS
+        );
+        assert_eq!(count.get(), Some(3));
+    }
+
+    #[test]
+    fn handle_local_options_with_clap() {
+        let app = Application::new(None, ApplicationFlags::NON_UNIQUE);
+        let count = Rc::new(Cell::new(None));
+        app.connect_handle_local_options_with_clap(command(), {
+            let count = count.clone();
+            move |_, matches| {
+                count.set(matches.get_one::<u32>("count").copied());
+                ControlFlow::Break(ExitCode::SUCCESS)
+            }
+        });
+
+        assert_eq!(
+            app.run_with_args(&["test", "--count", "4"]),
+            ExitCode::SUCCESS
+        );
+        assert_eq!(count.get(), Some(4));
+    }
+
+    #[test]
+    fn command_line_with_clap_error() {
+        for (args, exit_code) in [
+            (&["test", "--count", "x"][..], ExitCode::new(2)),
+            (&["test", "--help"][..], ExitCode::SUCCESS),
+        ] {
+            let app = Application::new(None, ApplicationFlags::NON_UNIQUE);
+            app.connect_command_line_with_clap(command(), |_, _, _| {
+                panic!("handler called for invalid arguments")
+            });
+            assert_eq!(app.run_with_args(args), exit_code);
+        }
+    }
+}
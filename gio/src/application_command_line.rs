@@ -24,6 +24,49 @@ pub trait ApplicationCommandLineExtManual: IsA<ApplicationCommandLine> {
             ffi::g_application_command_line_set_exit_status(self.as_ref().to_glib_none().0, status);
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the arguments of the command line with `command`.
+    ///
+    /// For remote invocations these are the arguments the remote instance was started with.
+    /// Values of arguments with `clap::Arg::env` are read from the environment of the current
+    /// process, use [`getenv()`](crate::prelude::ApplicationCommandLineExt::getenv) for the
+    /// environment of the invocation instead.
+    #[cfg(feature = "clap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+    fn try_get_matches(&self, command: clap::Command) -> Result<clap::ArgMatches, clap::Error> {
+        use crate::prelude::ApplicationCommandLineExt;
+
+        command.try_get_matches_from(self.as_ref().arguments())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Prints `err` on the command line and returns the exit code it should exit with.
+    ///
+    /// Help and version output is printed to stdout, errors to stderr of the invocation.
+    #[cfg(feature = "clap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "clap")))]
+    fn print_clap_error(&self, err: &clap::Error) -> ExitCode {
+        let message = glib::GString::from(err.render().to_string());
+        // `g_application_command_line_print_literal()` requires GLib 2.80.
+        unsafe {
+            if err.use_stderr() {
+                ffi::g_application_command_line_printerr(
+                    self.as_ref().to_glib_none().0,
+                    c"%s".as_ptr(),
+                    message.as_ptr(),
+                );
+            } else {
+                ffi::g_application_command_line_print(
+                    self.as_ref().to_glib_none().0,
+                    c"%s".as_ptr(),
+                    message.as_ptr(),
+                );
+            }
+        }
+
+        ExitCode::try_from(err.exit_code()).unwrap_or(ExitCode::FAILURE)
+    }
 }
 
 impl<O: IsA<ApplicationCommandLine>> ApplicationCommandLineExtManual for O {}
@@ -159,16 +159,6 @@ pub trait ApplicationExt: IsA<Application> + 'static {
         }
     }
 
-    //#[doc(alias = "g_application_add_main_option_entries")]
-    //fn add_main_option_entries(&self, entries: /*Ignored*/&[glib::OptionEntry]) {
-    //    unsafe { TODO: call ffi:g_application_add_main_option_entries() }
-    //}
-
-    //#[doc(alias = "g_application_add_option_group")]
-    //fn add_option_group(&self, group: /*Ignored*/glib::OptionGroup) {
-    //    unsafe { TODO: call ffi:g_application_add_option_group() }
-    //}
-
     #[doc(alias = "g_application_bind_busy_property")]
     fn bind_busy_property(&self, object: &impl IsA<glib::Object>, property: &str) {
         unsafe {
@@ -228,12 +228,6 @@ impl DBusMessage {
         }
     }
 
-    //#[doc(alias = "g_dbus_message_new_method_error")]
-    //#[must_use]
-    //pub fn new_method_error(&self, error_name: &str, error_message_format: &str, : /*Unknown conversion*//*Unimplemented*/Basic: VarArgs) -> DBusMessage {
-    //    unsafe { TODO: call ffi:g_dbus_message_new_method_error() }
-    //}
-
     #[doc(alias = "g_dbus_message_new_method_error_literal")]
     #[must_use]
     pub fn new_method_error_literal(&self, error_name: &str, error_message: &str) -> DBusMessage {
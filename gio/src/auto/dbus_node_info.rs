@@ -31,11 +31,6 @@ impl DBusNodeInfo {
         }
     }
 
-    //#[doc(alias = "g_dbus_node_info_generate_xml")]
-    //pub fn generate_xml(&self, indent: u32, string_builder: /*Ignored*/&mut glib::String) {
-    //    unsafe { TODO: call ffi:g_dbus_node_info_generate_xml() }
-    //}
-
     #[doc(alias = "g_dbus_node_info_lookup_interface")]
     pub fn lookup_interface(&self, name: &str) -> Option<DBusInterfaceInfo> {
         unsafe {
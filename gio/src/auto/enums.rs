@@ -465,16 +465,6 @@ impl DBusError {
         }
     }
 
-    //#[doc(alias = "g_dbus_error_register_error_domain")]
-    //pub fn register_error_domain(error_domain_quark_name: &str, entries: /*Ignored*/&[DBusErrorEntry]) -> usize {
-    //    unsafe { TODO: call ffi:g_dbus_error_register_error_domain() }
-    //}
-
-    //#[doc(alias = "g_dbus_error_set_dbus_error")]
-    //pub fn set_dbus_error(dbus_error_name: &str, dbus_error_message: &str, format: Option<&str>, : /*Unknown conversion*//*Unimplemented*/Basic: VarArgs) -> Option<glib::Error> {
-    //    unsafe { TODO: call ffi:g_dbus_error_set_dbus_error() }
-    //}
-
     //#[doc(alias = "g_dbus_error_set_dbus_error_valist")]
     //pub fn set_dbus_error_valist(dbus_error_name: &str, dbus_error_message: &str, format: Option<&str>, var_args: /*Unknown conversion*//*Unimplemented*/Unsupported) -> Option<glib::Error> {
     //    unsafe { TODO: call ffi:g_dbus_error_set_dbus_error_valist() }
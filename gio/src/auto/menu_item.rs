@@ -56,12 +56,6 @@ impl MenuItem {
         }
     }
 
-    //#[doc(alias = "g_menu_item_get_attribute")]
-    //#[doc(alias = "get_attribute")]
-    //pub fn is_attribute(&self, attribute: &str, format_string: &str, : /*Unknown conversion*//*Unimplemented*/Basic: VarArgs) -> bool {
-    //    unsafe { TODO: call ffi:g_menu_item_get_attribute() }
-    //}
-
     #[doc(alias = "g_menu_item_get_attribute_value")]
     #[doc(alias = "get_attribute_value")]
     pub fn attribute_value(
@@ -89,11 +83,6 @@ impl MenuItem {
         }
     }
 
-    //#[doc(alias = "g_menu_item_set_action_and_target")]
-    //pub fn set_action_and_target(&self, action: Option<&str>, format_string: Option<&str>, : /*Unknown conversion*//*Unimplemented*/Basic: VarArgs) {
-    //    unsafe { TODO: call ffi:g_menu_item_set_action_and_target() }
-    //}
-
     #[doc(alias = "g_menu_item_set_action_and_target_value")]
     pub fn set_action_and_target_value(
         &self,
@@ -109,11 +98,6 @@ impl MenuItem {
         }
     }
 
-    //#[doc(alias = "g_menu_item_set_attribute")]
-    //pub fn set_attribute(&self, attribute: &str, format_string: Option<&str>, : /*Unknown conversion*//*Unimplemented*/Basic: VarArgs) {
-    //    unsafe { TODO: call ffi:g_menu_item_set_attribute() }
-    //}
-
     #[doc(alias = "g_menu_item_set_attribute_value")]
     pub fn set_attribute_value(&self, attribute: &str, value: Option<&glib::Variant>) {
         unsafe {
@@ -500,11 +500,6 @@ pub trait SettingsExt: IsA<Settings> + 'static {
         ObjectExt::property(self.as_ref(), "settings-schema")
     }
 
-    //#[doc(alias = "change-event")]
-    //fn connect_change_event<Unsupported or ignored types>(&self, f: F) -> SignalHandlerId {
-    //    Unimplemented keys: *.CArray TypeId { ns_id: 2, id: 5 }
-    //}
-
     #[doc(alias = "changed")]
     fn connect_changed<F: Fn(&Self, &str) + 'static>(
         &self,
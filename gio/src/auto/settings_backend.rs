@@ -17,11 +17,6 @@ glib::wrapper! {
 impl SettingsBackend {
     pub const NONE: Option<&'static SettingsBackend> = None;
 
-    //#[doc(alias = "g_settings_backend_flatten_tree")]
-    //pub fn flatten_tree(tree: /*Ignored*/&glib::Tree) -> (glib::GString, Vec<glib::GString>, Vec<glib::Variant>) {
-    //    unsafe { TODO: call ffi:g_settings_backend_flatten_tree() }
-    //}
-
     #[doc(alias = "g_settings_backend_get_default")]
     #[doc(alias = "get_default")]
     #[allow(clippy::should_implement_trait)]
@@ -31,26 +26,6 @@ impl SettingsBackend {
 }
 
 pub trait SettingsBackendExt: IsA<SettingsBackend> + 'static {
-    //#[doc(alias = "g_settings_backend_changed")]
-    //fn changed(&self, key: &str, origin_tag: /*Unimplemented*/Option<Basic: Pointer>) {
-    //    unsafe { TODO: call ffi:g_settings_backend_changed() }
-    //}
-
-    //#[doc(alias = "g_settings_backend_changed_tree")]
-    //fn changed_tree(&self, tree: /*Ignored*/&glib::Tree, origin_tag: /*Unimplemented*/Option<Basic: Pointer>) {
-    //    unsafe { TODO: call ffi:g_settings_backend_changed_tree() }
-    //}
-
-    //#[doc(alias = "g_settings_backend_keys_changed")]
-    //fn keys_changed(&self, path: &str, items: &[&str], origin_tag: /*Unimplemented*/Option<Basic: Pointer>) {
-    //    unsafe { TODO: call ffi:g_settings_backend_keys_changed() }
-    //}
-
-    //#[doc(alias = "g_settings_backend_path_changed")]
-    //fn path_changed(&self, path: &str, origin_tag: /*Unimplemented*/Option<Basic: Pointer>) {
-    //    unsafe { TODO: call ffi:g_settings_backend_path_changed() }
-    //}
-
     #[doc(alias = "g_settings_backend_path_writable_changed")]
     fn path_writable_changed(&self, path: &str) {
         unsafe {
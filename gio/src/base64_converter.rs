@@ -0,0 +1,255 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::{Base64Decoder, Base64Encoder};
+
+use crate::{Converter, subclass::prelude::*};
+
+mod imp {
+    use std::cell::RefCell;
+
+    use super::*;
+    use crate::{ConverterFlags, ConverterResult, IOErrorEnum};
+
+    pub(super) enum Coder {
+        Encoder(Base64Encoder),
+        Decoder(Base64Decoder),
+    }
+
+    pub(super) struct State {
+        pub(super) coder: Coder,
+        // Output that didn't fit into the output buffer yet.
+        pending: Vec<u8>,
+        finished: bool,
+    }
+
+    impl State {
+        pub(super) fn new(coder: Coder) -> Self {
+            Self {
+                coder,
+                pending: Vec::new(),
+                finished: false,
+            }
+        }
+    }
+
+    impl Default for State {
+        // Converters created with `glib::Object::new()` encode without line breaks.
+        fn default() -> Self {
+            Self::new(Coder::Encoder(Base64Encoder::new(false)))
+        }
+    }
+
+    #[derive(Default)]
+    pub struct Base64Converter {
+        pub(super) state: RefCell<State>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for Base64Converter {
+        const NAME: &'static str = "GioRsBase64Converter";
+        const ALLOW_NAME_CONFLICT: bool = true;
+        type Type = super::Base64Converter;
+        type Interfaces = (Converter,);
+    }
+
+    impl ObjectImpl for Base64Converter {}
+
+    impl ConverterImpl for Base64Converter {
+        fn convert(
+            &self,
+            inbuf: &[u8],
+            outbuf: &mut [u8],
+            flags: ConverterFlags,
+        ) -> Result<(ConverterResult, usize, usize), glib::Error> {
+            let mut state = self.state.borrow_mut();
+            let state = &mut *state;
+
+            if outbuf.is_empty() {
+                return Err(glib::Error::new(
+                    IOErrorEnum::NoSpace,
+                    "Not enough space in output buffer",
+                ));
+            }
+            let input_at_end = flags.contains(ConverterFlags::INPUT_AT_END);
+            let flush = flags.contains(ConverterFlags::FLUSH);
+            if inbuf.is_empty() && state.pending.is_empty() && !input_at_end && !flush {
+                return Err(glib::Error::new(
+                    IOErrorEnum::PartialInput,
+                    "Need more input",
+                ));
+            }
+
+            if !state.finished {
+                match &mut state.coder {
+                    Coder::Encoder(encoder) => {
+                        // A partial group of the input can only be written out padded, which
+                        // would end the encoded data, so it is kept on flushes until the end
+                        // of the input.
+                        encoder.encode_step(inbuf, &mut state.pending);
+                        if input_at_end {
+                            encoder.finish(&mut state.pending);
+                        }
+                    }
+                    // A partial group of the input can't be decoded before more input arrives.
+                    Coder::Decoder(decoder) => decoder.decode_step(inbuf, &mut state.pending),
+                }
+                state.finished = input_at_end;
+            }
+
+            let written = outbuf.len().min(state.pending.len());
+            outbuf[..written].copy_from_slice(&state.pending[..written]);
+            state.pending.drain(..written);
+
+            let res = if !state.pending.is_empty() {
+                ConverterResult::Converted
+            } else if state.finished {
+                ConverterResult::Finished
+            } else if flush {
+                ConverterResult::Flushed
+            } else {
+                ConverterResult::Converted
+            };
+
+            Ok((res, inbuf.len(), written))
+        }
+
+        fn reset(&self) {
+            let mut state = self.state.borrow_mut();
+            match &mut state.coder {
+                Coder::Encoder(encoder) => encoder.reset(),
+                Coder::Decoder(decoder) => decoder.reset(),
+            }
+            state.pending.clear();
+            state.finished = false;
+        }
+    }
+}
+
+glib::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A [`Converter`] that base64-encodes or -decodes data, e.g. for use with
+    /// [`ConverterInputStream`](crate::ConverterInputStream) and
+    /// [`ConverterOutputStream`](crate::ConverterOutputStream).
+    ///
+    /// On flushes, the encoder writes out all complete 3-byte groups of the input so far. The
+    /// remaining bytes are kept until more input arrives or the input ends.
+    pub struct Base64Converter(ObjectSubclass<imp::Base64Converter>) @implements Converter;
+}
+
+impl Base64Converter {
+    // rustdoc-stripper-ignore-next
+    /// Creates a converter that encodes data. If `break_lines` is `true`, a newline is inserted
+    /// after every 76 characters of output.
+    pub fn encoder(break_lines: bool) -> Self {
+        Self::with_coder(imp::Coder::Encoder(Base64Encoder::new(break_lines)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a converter that decodes data.
+    pub fn decoder() -> Self {
+        Self::with_coder(imp::Coder::Decoder(Base64Decoder::new()))
+    }
+
+    fn with_coder(coder: imp::Coder) -> Self {
+        let obj: Self = glib::Object::new();
+        obj.imp().state.replace(imp::State::new(coder));
+        obj
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+    use crate::{
+        ConverterInputStream, ConverterOutputStream, MemoryInputStream, MemoryOutputStream,
+        prelude::*,
+    };
+
+    const DATA: &[u8] =
+        b"The quick brown fox jumps over the lazy dog, over and over and over again.";
+
+    #[test]
+    fn encode_output_stream() {
+        let base = MemoryOutputStream::new_resizable();
+        let stream = ConverterOutputStream::new(&base, &Base64Converter::encoder(false));
+        for chunk in DATA.chunks(7) {
+            stream.write_all(chunk, crate::Cancellable::NONE).unwrap();
+        }
+        stream.close(crate::Cancellable::NONE).unwrap();
+
+        assert_eq!(
+            &*base.steal_as_bytes(),
+            glib::base64_encode(DATA).as_bytes()
+        );
+    }
+
+    #[test]
+    fn decode_input_stream() {
+        let encoded = glib::base64_encode(DATA);
+        let base = MemoryInputStream::from_bytes(&glib::Bytes::from(encoded.as_bytes()));
+        let stream = ConverterInputStream::new(&base, &Base64Converter::decoder());
+
+        let mut decoded = Vec::new();
+        stream.into_read().read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, DATA);
+    }
+
+    #[test]
+    fn flush() {
+        let base = MemoryOutputStream::new_resizable();
+        let stream = ConverterOutputStream::new(&base, &Base64Converter::encoder(false));
+        stream.write_all(b"abcd", crate::Cancellable::NONE).unwrap();
+        assert_eq!(base.data_size(), 4);
+        stream.flush(crate::Cancellable::NONE).unwrap();
+        assert_eq!(base.data_size(), 4);
+        stream.write_all(b"ef", crate::Cancellable::NONE).unwrap();
+        stream.flush(crate::Cancellable::NONE).unwrap();
+        assert_eq!(base.data_size(), 8);
+        stream.write_all(b"g", crate::Cancellable::NONE).unwrap();
+        stream.close(crate::Cancellable::NONE).unwrap();
+        let encoded = base.steal_as_bytes();
+        assert_eq!(&*encoded, glib::base64_encode(b"abcdefg").as_bytes());
+    }
+
+    #[test]
+    fn object_new() {
+        let converter = glib::Object::new::<Base64Converter>();
+        let mut out = [0; 8];
+        let (res, read, written) = converter
+            .convert(b"abcd", &mut out[..], crate::ConverterFlags::INPUT_AT_END)
+            .unwrap();
+        assert_eq!(
+            (res, read, written),
+            (crate::ConverterResult::Finished, 4, 8)
+        );
+        assert_eq!(&out, b"YWJjZA==");
+    }
+
+    #[test]
+    fn small_output_buffer() {
+        let converter = Base64Converter::encoder(false);
+        let mut out = [0; 3];
+        let (res, read, written) = converter
+            .convert(b"abcd", &mut out[..], crate::ConverterFlags::INPUT_AT_END)
+            .unwrap();
+        assert_eq!(
+            (res, read, written),
+            (crate::ConverterResult::Converted, 4, 3)
+        );
+        assert_eq!(&out, b"YWJ");
+
+        let mut encoded = out.to_vec();
+        loop {
+            let (res, _, written) = converter
+                .convert(b"", &mut out[..], crate::ConverterFlags::INPUT_AT_END)
+                .unwrap();
+            encoded.extend_from_slice(&out[..written]);
+            if res == crate::ConverterResult::Finished {
+                break;
+            }
+        }
+        assert_eq!(encoded, b"YWJjZA==");
+    }
+}
@@ -0,0 +1,49 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ffi::CStr;
+
+use glib::translate::*;
+
+use crate::ffi;
+
+// rustdoc-stripper-ignore-next
+/// Creates a `NULL`-terminated array of annotations that is freed together with the info it is
+/// assigned to.
+pub(crate) fn annotations_to_glib_full(
+    annotations: &[(String, String)],
+) -> *mut *mut ffi::GDBusAnnotationInfo {
+    unsafe {
+        let array = glib::ffi::g_malloc0(
+            std::mem::size_of::<*mut ffi::GDBusAnnotationInfo>() * (annotations.len() + 1),
+        ) as *mut *mut ffi::GDBusAnnotationInfo;
+
+        for (i, (key, value)) in annotations.iter().enumerate() {
+            let annotation = glib::ffi::g_malloc0(std::mem::size_of::<ffi::GDBusAnnotationInfo>())
+                as *mut ffi::GDBusAnnotationInfo;
+            (*annotation).ref_count = 1;
+            (*annotation).key = key.to_glib_full();
+            (*annotation).value = value.to_glib_full();
+            (*annotation).annotations = glib::ffi::g_malloc0(std::mem::size_of::<
+                *mut ffi::GDBusAnnotationInfo,
+            >()) as *mut *mut ffi::GDBusAnnotationInfo;
+            std::ptr::write(array.add(i), annotation);
+        }
+
+        array
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Looks up the value of the annotation `name` in a `NULL`-terminated array of annotations.
+pub(crate) unsafe fn lookup_annotation<'a>(
+    annotations: *mut *mut ffi::GDBusAnnotationInfo,
+    name: &str,
+) -> Option<&'a str> {
+    unsafe {
+        let value = ffi::g_dbus_annotation_info_lookup(annotations, name.to_glib_none().0);
+        if value.is_null() {
+            return None;
+        }
+        Some(CStr::from_ptr(value).to_str().unwrap())
+    }
+}
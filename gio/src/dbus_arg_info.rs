@@ -0,0 +1,88 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ffi::CStr;
+
+use glib::translate::*;
+
+use crate::{DBusArgInfo, dbus_annotation_info, ffi};
+
+impl DBusArgInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for an argument with the D-Bus type `signature`.
+    pub fn builder(signature: &str) -> DBusArgInfoBuilder {
+        DBusArgInfoBuilder::new(signature)
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let name = (*c_obj).name;
+            if name.is_null() {
+                return None;
+            }
+            let c_str = CStr::from_ptr(name);
+            Some(c_str.to_str().unwrap())
+        }
+    }
+
+    pub fn signature(&self) -> &str {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let signature = (*c_obj).signature;
+            assert!(!signature.is_null());
+            let c_str = CStr::from_ptr(signature);
+            c_str.to_str().unwrap()
+        }
+    }
+
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, name: &str) -> Option<&str> {
+        unsafe { dbus_annotation_info::lookup_annotation((*self.as_ptr()).annotations, name) }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`DBusArgInfo`] objects.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusArgInfoBuilder {
+    name: Option<String>,
+    signature: String,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusArgInfoBuilder {
+    pub fn new(signature: &str) -> Self {
+        Self {
+            name: None,
+            signature: signature.to_owned(),
+            annotations: Vec::new(),
+        }
+    }
+
+    pub fn name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_owned());
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Build the [`DBusArgInfo`].
+    pub fn build(self) -> DBusArgInfo {
+        unsafe {
+            let info = glib::ffi::g_malloc0(std::mem::size_of::<ffi::GDBusArgInfo>())
+                as *mut ffi::GDBusArgInfo;
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).signature = self.signature.to_glib_full();
+            (*info).annotations = dbus_annotation_info::annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
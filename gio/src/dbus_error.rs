@@ -0,0 +1,125 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{collections::BTreeSet, ffi::CString, sync::Mutex};
+
+use glib::{prelude::*, translate::*};
+
+use crate::{DBusError, ffi};
+
+impl DBusError {
+    // rustdoc-stripper-ignore-next
+    /// Registers the D-Bus error names of the error domain `T`.
+    ///
+    /// Afterwards, errors of the domain are sent as D-Bus errors with these names, e.g. by
+    /// [`DBusMethodInvocation::return_error()`](crate::DBusMethodInvocation::return_error), and
+    /// received D-Bus errors with these names are converted back to errors of the domain so they
+    /// can be matched with [`glib::Error::kind()`].
+    ///
+    /// The names are given by [`ErrorDomain::dbus_error_names()`], which is generated by
+    /// `#[derive(glib::ErrorDomain)]` when a `dbus_prefix` is set. Registering a domain more
+    /// than once has no effect.
+    #[doc(alias = "g_dbus_error_register_error_domain")]
+    pub fn register_error_domain<T: ErrorDomain>() {
+        static REGISTERED: Mutex<BTreeSet<glib::Quark>> = Mutex::new(BTreeSet::new());
+
+        let names = T::dbus_error_names();
+        if names.is_empty() {
+            return;
+        }
+
+        let domain = T::domain();
+        if !REGISTERED.lock().unwrap().insert(domain) {
+            return;
+        }
+
+        let names = names
+            .iter()
+            .map(|&(code, name)| (code, CString::new(name).unwrap()))
+            .collect::<Vec<_>>();
+        let entries = names
+            .iter()
+            .map(|(code, name)| ffi::GDBusErrorEntry {
+                error_code: *code,
+                dbus_error_name: name.as_ptr(),
+            })
+            .collect::<Vec<_>>();
+
+        unsafe {
+            // The quark name is interned by GLib, so it's valid for the whole program.
+            let mut quark_volatile = 0;
+            ffi::g_dbus_error_register_error_domain(
+                domain.as_str().as_ptr(),
+                &mut quark_volatile,
+                entries.as_ptr(),
+                entries.len() as u32,
+            );
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an error for the D-Bus error `dbus_error_name` like
+    /// [`new_for_dbus_error()`](Self::new_for_dbus_error), with `prefix` and `": "` prepended
+    /// to its message.
+    #[doc(alias = "g_dbus_error_set_dbus_error")]
+    pub fn set_dbus_error(
+        dbus_error_name: &str,
+        dbus_error_message: &str,
+        prefix: Option<&str>,
+    ) -> glib::Error {
+        unsafe {
+            let mut error = std::ptr::null_mut();
+            match prefix {
+                Some(prefix) => ffi::g_dbus_error_set_dbus_error(
+                    &mut error,
+                    dbus_error_name.to_glib_none().0,
+                    dbus_error_message.to_glib_none().0,
+                    c"%s".as_ptr(),
+                    ToGlibPtr::<*const libc::c_char>::to_glib_none(prefix).0,
+                ),
+                None => ffi::g_dbus_error_set_dbus_error(
+                    &mut error,
+                    dbus_error_name.to_glib_none().0,
+                    dbus_error_message.to_glib_none().0,
+                    std::ptr::null(),
+                ),
+            }
+            from_glib_full(error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Eq, PartialEq, Clone, Copy, glib::ErrorDomain)]
+    #[error_domain(name = "gio-rs-test-dbus-error", dbus_prefix = "org.gtk_rs.Test.Error")]
+    enum TestError {
+        Failed,
+        #[error_domain(dbus_name = "org.gtk_rs.Test.OtherError")]
+        Other,
+    }
+
+    #[test]
+    fn register_error_domain() {
+        DBusError::register_error_domain::<TestError>();
+        DBusError::register_error_domain::<TestError>();
+
+        let error = glib::Error::new(TestError::Other, "oh no");
+        assert_eq!(
+            DBusError::encode_gerror(&error),
+            "org.gtk_rs.Test.OtherError"
+        );
+
+        let mut error = DBusError::new_for_dbus_error("org.gtk_rs.Test.Error.Failed", "remote");
+        assert_eq!(error.kind::<TestError>(), Some(TestError::Failed));
+        assert!(DBusError::strip_remote_error(&mut error));
+        assert_eq!(error.message(), "remote");
+
+        let mut error =
+            DBusError::set_dbus_error("org.gtk_rs.Test.Error.Failed", "remote", Some("a"));
+        assert_eq!(error.kind::<TestError>(), Some(TestError::Failed));
+        assert!(DBusError::strip_remote_error(&mut error));
+        assert_eq!(error.message(), "a: remote");
+    }
+}
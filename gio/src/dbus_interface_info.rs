@@ -2,9 +2,19 @@
 
 use std::ffi::CStr;
 
-use crate::DBusInterfaceInfo;
+use glib::translate::*;
+
+use crate::{
+    DBusInterfaceInfo, DBusMethodInfo, DBusPropertyInfo, DBusSignalInfo, dbus_annotation_info, ffi,
+};
 
 impl DBusInterfaceInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for an interface called `name`.
+    pub fn builder(name: &str) -> DBusInterfaceInfoBuilder {
+        DBusInterfaceInfoBuilder::new(name)
+    }
+
     pub fn name(&self) -> &str {
         unsafe {
             let c_obj = self.as_ptr();
@@ -14,4 +24,124 @@ impl DBusInterfaceInfo {
             c_str.to_str().unwrap()
         }
     }
+
+    pub fn methods(&self) -> &[DBusMethodInfo] {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let c_methods = (*c_obj).methods;
+            if c_methods.is_null() {
+                return &[];
+            }
+            glib::collections::PtrSlice::from_glib_borrow(c_methods)
+        }
+    }
+
+    pub fn signals(&self) -> &[DBusSignalInfo] {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let c_signals = (*c_obj).signals;
+            if c_signals.is_null() {
+                return &[];
+            }
+            glib::collections::PtrSlice::from_glib_borrow(c_signals)
+        }
+    }
+
+    pub fn properties(&self) -> &[DBusPropertyInfo] {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let c_properties = (*c_obj).properties;
+            if c_properties.is_null() {
+                return &[];
+            }
+            glib::collections::PtrSlice::from_glib_borrow(c_properties)
+        }
+    }
+
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, name: &str) -> Option<&str> {
+        unsafe { dbus_annotation_info::lookup_annotation((*self.as_ptr()).annotations, name) }
+    }
+
+    #[doc(alias = "g_dbus_interface_info_generate_xml")]
+    pub fn generate_xml(&self, indent: u32, string_builder: &mut glib::GStringBuilder) {
+        unsafe {
+            ffi::g_dbus_interface_info_generate_xml(
+                self.to_glib_none().0,
+                indent,
+                string_builder.to_glib_none_mut().0,
+            );
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the introspection XML of the interface.
+    pub fn to_xml(&self) -> glib::GString {
+        let mut string_builder = glib::GStringBuilder::default();
+        self.generate_xml(0, &mut string_builder);
+        string_builder.into_string()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`DBusInterfaceInfo`] objects.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusInterfaceInfoBuilder {
+    name: String,
+    methods: Vec<DBusMethodInfo>,
+    signals: Vec<DBusSignalInfo>,
+    properties: Vec<DBusPropertyInfo>,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusInterfaceInfoBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            methods: Vec::new(),
+            signals: Vec::new(),
+            properties: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    pub fn method(mut self, method: DBusMethodInfo) -> Self {
+        self.methods.push(method);
+        self
+    }
+
+    pub fn signal(mut self, signal: DBusSignalInfo) -> Self {
+        self.signals.push(signal);
+        self
+    }
+
+    pub fn property(mut self, property: DBusPropertyInfo) -> Self {
+        self.properties.push(property);
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Build the [`DBusInterfaceInfo`].
+    pub fn build(self) -> DBusInterfaceInfo {
+        unsafe {
+            let info = glib::ffi::g_malloc0(std::mem::size_of::<ffi::GDBusInterfaceInfo>())
+                as *mut ffi::GDBusInterfaceInfo;
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).methods = ToGlibContainerFromSlice::to_glib_full_from_slice(&self.methods);
+            (*info).signals = ToGlibContainerFromSlice::to_glib_full_from_slice(&self.signals);
+            (*info).properties =
+                ToGlibContainerFromSlice::to_glib_full_from_slice(&self.properties);
+            (*info).annotations = dbus_annotation_info::annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
 }
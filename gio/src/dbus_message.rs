@@ -2,7 +2,23 @@
 
 use std::fmt;
 
-use crate::DBusMessage;
+use crate::{DBusError, DBusMessage};
+
+impl DBusMessage {
+    // rustdoc-stripper-ignore-next
+    /// Creates a message that is an error reply to `self` for `error`.
+    ///
+    /// The D-Bus error name is derived from the domain and code of `error`, using the names
+    /// registered with [`DBusError::register_error_domain()`].
+    #[doc(alias = "g_dbus_message_new_method_error")]
+    #[must_use]
+    pub fn new_method_error(&self, error: &glib::Error) -> DBusMessage {
+        let mut error = error.clone();
+        let error_name = DBusError::encode_gerror(&error);
+        DBusError::strip_remote_error(&mut error);
+        self.new_method_error_literal(&error_name, error.message())
+    }
+}
 
 impl fmt::Display for DBusMessage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
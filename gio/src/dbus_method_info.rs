@@ -0,0 +1,106 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ffi::CStr;
+
+use glib::translate::*;
+
+use crate::{DBusArgInfo, DBusMethodInfo, dbus_annotation_info, ffi};
+
+impl DBusMethodInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a method called `name`.
+    pub fn builder(name: &str) -> DBusMethodInfoBuilder {
+        DBusMethodInfoBuilder::new(name)
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let name = (*c_obj).name;
+            assert!(!name.is_null());
+            let c_str = CStr::from_ptr(name);
+            c_str.to_str().unwrap()
+        }
+    }
+
+    pub fn in_args(&self) -> &[DBusArgInfo] {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let c_in_args = (*c_obj).in_args;
+            if c_in_args.is_null() {
+                return &[];
+            }
+            glib::collections::PtrSlice::from_glib_borrow(c_in_args)
+        }
+    }
+
+    pub fn out_args(&self) -> &[DBusArgInfo] {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let c_out_args = (*c_obj).out_args;
+            if c_out_args.is_null() {
+                return &[];
+            }
+            glib::collections::PtrSlice::from_glib_borrow(c_out_args)
+        }
+    }
+
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, name: &str) -> Option<&str> {
+        unsafe { dbus_annotation_info::lookup_annotation((*self.as_ptr()).annotations, name) }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`DBusMethodInfo`] objects.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusMethodInfoBuilder {
+    name: String,
+    in_args: Vec<DBusArgInfo>,
+    out_args: Vec<DBusArgInfo>,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusMethodInfoBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            in_args: Vec::new(),
+            out_args: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    pub fn in_arg(mut self, arg: DBusArgInfo) -> Self {
+        self.in_args.push(arg);
+        self
+    }
+
+    pub fn out_arg(mut self, arg: DBusArgInfo) -> Self {
+        self.out_args.push(arg);
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Build the [`DBusMethodInfo`].
+    pub fn build(self) -> DBusMethodInfo {
+        unsafe {
+            let info = glib::ffi::g_malloc0(std::mem::size_of::<ffi::GDBusMethodInfo>())
+                as *mut ffi::GDBusMethodInfo;
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).in_args = ToGlibContainerFromSlice::to_glib_full_from_slice(&self.in_args);
+            (*info).out_args = ToGlibContainerFromSlice::to_glib_full_from_slice(&self.out_args);
+            (*info).annotations = dbus_annotation_info::annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
@@ -2,11 +2,19 @@
 
 use glib::{VariantTy, prelude::*, translate::*};
 
-use crate::{DBusMethodInvocation, ffi};
+use crate::{DBusError, DBusMethodInvocation, ffi};
 
 impl DBusMethodInvocation {
+    // rustdoc-stripper-ignore-next
+    /// Returns `error` as the result of this invocation.
+    ///
+    /// The D-Bus error names of the error domain are registered with
+    /// [`DBusError::register_error_domain()`] first, so errors of domains that define them are
+    /// sent with their D-Bus error name.
     #[doc(alias = "g_dbus_method_invocation_return_error_literal")]
     pub fn return_error<T: ErrorDomain>(&self, error: T, message: &str) {
+        DBusError::register_error_domain::<T>();
+
         unsafe {
             ffi::g_dbus_method_invocation_return_error_literal(
                 self.to_glib_full(),
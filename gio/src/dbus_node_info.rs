@@ -2,9 +2,17 @@
 
 use std::ffi::CStr;
 
-use crate::{DBusInterfaceInfo, DBusNodeInfo};
+use glib::translate::*;
+
+use crate::{DBusInterfaceInfo, DBusNodeInfo, dbus_annotation_info, ffi};
 
 impl DBusNodeInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a node.
+    pub fn builder() -> DBusNodeInfoBuilder {
+        DBusNodeInfoBuilder::new()
+    }
+
     pub fn path(&self) -> Option<&str> {
         unsafe {
             let c_obj = self.as_ptr();
@@ -39,4 +47,138 @@ impl DBusNodeInfo {
             glib::collections::PtrSlice::from_glib_borrow(c_ni)
         }
     }
+
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, name: &str) -> Option<&str> {
+        unsafe { dbus_annotation_info::lookup_annotation((*self.as_ptr()).annotations, name) }
+    }
+
+    #[doc(alias = "g_dbus_node_info_generate_xml")]
+    pub fn generate_xml(&self, indent: u32, string_builder: &mut glib::GStringBuilder) {
+        unsafe {
+            ffi::g_dbus_node_info_generate_xml(
+                self.to_glib_none().0,
+                indent,
+                string_builder.to_glib_none_mut().0,
+            );
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the introspection XML of the node, e.g. to reply to
+    /// `org.freedesktop.DBus.Introspectable.Introspect` calls.
+    pub fn to_xml(&self) -> glib::GString {
+        let mut string_builder = glib::GStringBuilder::default();
+        self.generate_xml(0, &mut string_builder);
+        string_builder.into_string()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`DBusNodeInfo`] objects.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug, Default)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusNodeInfoBuilder {
+    path: Option<String>,
+    interfaces: Vec<DBusInterfaceInfo>,
+    nodes: Vec<DBusNodeInfo>,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusNodeInfoBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn path(mut self, path: &str) -> Self {
+        self.path = Some(path.to_owned());
+        self
+    }
+
+    pub fn interface(mut self, interface: DBusInterfaceInfo) -> Self {
+        self.interfaces.push(interface);
+        self
+    }
+
+    pub fn node(mut self, node: DBusNodeInfo) -> Self {
+        self.nodes.push(node);
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Build the [`DBusNodeInfo`].
+    pub fn build(self) -> DBusNodeInfo {
+        unsafe {
+            let info = glib::ffi::g_malloc0(std::mem::size_of::<ffi::GDBusNodeInfo>())
+                as *mut ffi::GDBusNodeInfo;
+            (*info).ref_count = 1;
+            (*info).path = self.path.to_glib_full();
+            (*info).interfaces =
+                ToGlibContainerFromSlice::to_glib_full_from_slice(&self.interfaces);
+            (*info).nodes = ToGlibContainerFromSlice::to_glib_full_from_slice(&self.nodes);
+            (*info).annotations = dbus_annotation_info::annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DBusArgInfo, DBusMethodInfo, DBusPropertyInfo, DBusPropertyInfoFlags, DBusSignalInfo,
+    };
+
+    #[test]
+    fn builder_roundtrip() {
+        let node = DBusNodeInfo::builder()
+            .interface(
+                DBusInterfaceInfo::builder("com.github.gtk_rs.Example")
+                    .method(
+                        DBusMethodInfo::builder("Hello")
+                            .in_arg(DBusArgInfo::builder("s").name("name").build())
+                            .out_arg(DBusArgInfo::builder("s").name("greet").build())
+                            .build(),
+                    )
+                    .signal(
+                        DBusSignalInfo::builder("Greeted")
+                            .arg(DBusArgInfo::builder("s").name("name").build())
+                            .build(),
+                    )
+                    .property(
+                        DBusPropertyInfo::builder("Greeting", "s")
+                            .flags(DBusPropertyInfoFlags::READABLE)
+                            .annotation("org.freedesktop.DBus.Property.EmitsChangedSignal", "const")
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build();
+
+        let xml = node.to_xml();
+        let parsed = DBusNodeInfo::for_xml(&xml).unwrap();
+        assert_eq!(parsed.to_xml(), xml);
+
+        let interface = parsed
+            .lookup_interface("com.github.gtk_rs.Example")
+            .unwrap();
+        let method = interface.lookup_method("Hello").unwrap();
+        assert_eq!(method.in_args().len(), 1);
+        assert_eq!(method.in_args()[0].name(), Some("name"));
+        assert_eq!(method.out_args()[0].signature(), "s");
+        assert_eq!(interface.lookup_signal("Greeted").unwrap().args().len(), 1);
+        let property = interface.lookup_property("Greeting").unwrap();
+        assert_eq!(property.flags(), DBusPropertyInfoFlags::READABLE);
+        assert_eq!(
+            property.annotation("org.freedesktop.DBus.Property.EmitsChangedSignal"),
+            Some("const")
+        );
+    }
 }
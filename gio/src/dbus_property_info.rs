@@ -0,0 +1,93 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ffi::CStr;
+
+use glib::translate::*;
+
+use crate::{DBusPropertyInfo, DBusPropertyInfoFlags, dbus_annotation_info, ffi};
+
+impl DBusPropertyInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a property called `name` with the D-Bus type `signature`.
+    pub fn builder(name: &str, signature: &str) -> DBusPropertyInfoBuilder {
+        DBusPropertyInfoBuilder::new(name, signature)
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let name = (*c_obj).name;
+            assert!(!name.is_null());
+            let c_str = CStr::from_ptr(name);
+            c_str.to_str().unwrap()
+        }
+    }
+
+    pub fn signature(&self) -> &str {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let signature = (*c_obj).signature;
+            assert!(!signature.is_null());
+            let c_str = CStr::from_ptr(signature);
+            c_str.to_str().unwrap()
+        }
+    }
+
+    pub fn flags(&self) -> DBusPropertyInfoFlags {
+        unsafe { from_glib((*self.as_ptr()).flags) }
+    }
+
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, name: &str) -> Option<&str> {
+        unsafe { dbus_annotation_info::lookup_annotation((*self.as_ptr()).annotations, name) }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`DBusPropertyInfo`] objects.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusPropertyInfoBuilder {
+    name: String,
+    signature: String,
+    flags: DBusPropertyInfoFlags,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusPropertyInfoBuilder {
+    pub fn new(name: &str, signature: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            signature: signature.to_owned(),
+            flags: DBusPropertyInfoFlags::NONE,
+            annotations: Vec::new(),
+        }
+    }
+
+    pub fn flags(mut self, flags: DBusPropertyInfoFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Build the [`DBusPropertyInfo`].
+    pub fn build(self) -> DBusPropertyInfo {
+        unsafe {
+            let info = glib::ffi::g_malloc0(std::mem::size_of::<ffi::GDBusPropertyInfo>())
+                as *mut ffi::GDBusPropertyInfo;
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).signature = self.signature.to_glib_full();
+            (*info).flags = self.flags.into_glib();
+            (*info).annotations = dbus_annotation_info::annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
@@ -0,0 +1,87 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::ffi::CStr;
+
+use glib::translate::*;
+
+use crate::{DBusArgInfo, DBusSignalInfo, dbus_annotation_info, ffi};
+
+impl DBusSignalInfo {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a signal called `name`.
+    pub fn builder(name: &str) -> DBusSignalInfoBuilder {
+        DBusSignalInfoBuilder::new(name)
+    }
+
+    pub fn name(&self) -> &str {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let name = (*c_obj).name;
+            assert!(!name.is_null());
+            let c_str = CStr::from_ptr(name);
+            c_str.to_str().unwrap()
+        }
+    }
+
+    pub fn args(&self) -> &[DBusArgInfo] {
+        unsafe {
+            let c_obj = self.as_ptr();
+            let c_args = (*c_obj).args;
+            if c_args.is_null() {
+                return &[];
+            }
+            glib::collections::PtrSlice::from_glib_borrow(c_args)
+        }
+    }
+
+    #[doc(alias = "g_dbus_annotation_info_lookup")]
+    pub fn annotation(&self, name: &str) -> Option<&str> {
+        unsafe { dbus_annotation_info::lookup_annotation((*self.as_ptr()).annotations, name) }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`DBusSignalInfo`] objects.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct DBusSignalInfoBuilder {
+    name: String,
+    args: Vec<DBusArgInfo>,
+    annotations: Vec<(String, String)>,
+}
+
+impl DBusSignalInfoBuilder {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            args: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: DBusArgInfo) -> Self {
+        self.args.push(arg);
+        self
+    }
+
+    pub fn annotation(mut self, key: &str, value: &str) -> Self {
+        self.annotations.push((key.to_owned(), value.to_owned()));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Build the [`DBusSignalInfo`].
+    pub fn build(self) -> DBusSignalInfo {
+        unsafe {
+            let info = glib::ffi::g_malloc0(std::mem::size_of::<ffi::GDBusSignalInfo>())
+                as *mut ffi::GDBusSignalInfo;
+            (*info).ref_count = 1;
+            (*info).name = self.name.to_glib_full();
+            (*info).args = ToGlibContainerFromSlice::to_glib_full_from_slice(&self.args);
+            (*info).annotations = dbus_annotation_info::annotations_to_glib_full(&self.annotations);
+            from_glib_full(info)
+        }
+    }
+}
@@ -0,0 +1,76 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::SignalHandlerId;
+
+use crate::{DebugController, prelude::*};
+
+pub trait DebugControllerExtManual: IsA<DebugController> + Sized {
+    // rustdoc-stripper-ignore-next
+    /// Keeps the debug flag of `router` in sync with the
+    /// [`debug-enabled`](DebugControllerExt::is_debug_enabled) property, e.g. so that debug
+    /// output can be turned on over D-Bus with a
+    /// [`DebugControllerDBus`](crate::DebugControllerDBus).
+    ///
+    /// The returned handler can be disconnected to stop updating the router.
+    fn bind_log_router(&self, router: &glib::LogRouter) -> SignalHandlerId {
+        router.set_debug_enabled(self.is_debug_enabled());
+
+        let router = router.clone();
+        self.connect_debug_enabled_notify(move |controller| {
+            router.set_debug_enabled(controller.is_debug_enabled());
+        })
+    }
+}
+
+impl<O: IsA<DebugController>> DebugControllerExtManual for O {}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::net::UnixStream;
+
+    use super::*;
+    use crate::{Cancellable, DBusConnection, DBusConnectionFlags, DebugControllerDBus, Socket};
+
+    fn connection(stream: UnixStream, flags: DBusConnectionFlags) -> DBusConnection {
+        let socket = Socket::from_fd(stream.into()).unwrap();
+        let guid = flags
+            .contains(DBusConnectionFlags::AUTHENTICATION_SERVER)
+            .then(crate::dbus_generate_guid);
+        DBusConnection::new_sync(
+            &socket.connection_factory_create_connection(),
+            guid.as_deref(),
+            flags,
+            None,
+            Cancellable::NONE,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn bind_log_router() {
+        let (server, client) = UnixStream::pair().unwrap();
+        let server = std::thread::spawn(move || {
+            connection(server, DBusConnectionFlags::AUTHENTICATION_SERVER)
+        });
+        let client = connection(client, DBusConnectionFlags::AUTHENTICATION_CLIENT);
+        let _server = server.join().unwrap();
+
+        let controller = DebugControllerDBus::new(&client, Cancellable::NONE)
+            .unwrap()
+            .unwrap();
+        controller.set_debug_enabled(true);
+
+        let router = glib::LogRouter::new();
+        let handler = controller.bind_log_router(&router);
+        assert!(router.is_debug_enabled());
+
+        controller.set_debug_enabled(false);
+        assert!(!router.is_debug_enabled());
+        controller.set_debug_enabled(true);
+        assert!(router.is_debug_enabled());
+
+        controller.disconnect(handler);
+        controller.set_debug_enabled(false);
+        assert!(router.is_debug_enabled());
+    }
+}
@@ -19,6 +19,8 @@ pub use action_entry::{ActionEntry, ActionEntryBuilder};
 pub use application::{ApplicationBusyGuard, ApplicationHoldGuard};
 mod application_command_line;
 mod async_initable;
+mod base64_converter;
+pub use crate::base64_converter::Base64Converter;
 mod cancellable;
 pub use cancellable::CancelledHandlerId;
 mod cancellable_future;
@@ -30,16 +32,25 @@ mod data_input_stream;
 mod datagram_based;
 mod dbus;
 pub use self::dbus::*;
+mod dbus_annotation_info;
+mod dbus_arg_info;
 mod dbus_connection;
 pub use self::dbus_connection::{
     ActionGroupExportId, DBusSignalRef, FilterId, MenuModelExportId, RegistrationBuilder,
     RegistrationId, SignalSubscription, SignalSubscriptionId, SubscribedSignalStream, WatcherId,
     WeakSignalSubscription,
 };
+mod dbus_error;
 mod dbus_interface_info;
 mod dbus_message;
+mod dbus_method_info;
 mod dbus_method_invocation;
 mod dbus_node_info;
+mod dbus_property_info;
+mod dbus_signal_info;
+#[cfg(feature = "v2_72")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v2_72")))]
+mod debug_controller;
 #[cfg(feature = "v2_72")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v2_72")))]
 mod debug_controller_dbus;
@@ -79,11 +90,18 @@ pub use crate::pollable_output_stream::OutputStreamAsyncWrite;
 mod resource;
 pub use crate::resource::resources_register_include_impl;
 mod settings;
-pub use crate::settings::BindingBuilder;
+pub use crate::settings::{BindingBuilder, SettingsChangesStream};
+mod settings_backend;
+pub use crate::settings_backend::SettingsBackendOriginTag;
 mod simple_proxy_resolver;
 mod socket;
 pub use socket::{InputMessage, InputVector, OutputMessage, OutputVector, SocketControlMessages};
 mod dbus_object_manager_client;
+mod menu;
+pub use crate::menu::MenuBuilder;
+mod menu_item;
+pub use crate::menu_item::MenuItemBuilder;
+mod notification;
 mod socket_control_message;
 mod socket_listener;
 mod subprocess;
@@ -96,6 +114,12 @@ mod test_util;
 pub mod builders {
     pub use super::async_initable::AsyncInitableBuilder;
     pub use super::auto::builders::*;
+    pub use super::dbus_arg_info::DBusArgInfoBuilder;
+    pub use super::dbus_interface_info::DBusInterfaceInfoBuilder;
+    pub use super::dbus_method_info::DBusMethodInfoBuilder;
+    pub use super::dbus_node_info::DBusNodeInfoBuilder;
+    pub use super::dbus_property_info::DBusPropertyInfoBuilder;
+    pub use super::dbus_signal_info::DBusSignalInfoBuilder;
     pub use super::initable::InitableBuilder;
 }
 
@@ -0,0 +1,125 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::prelude::*;
+
+use crate::{Menu, MenuItem, MenuItemBuilder};
+
+impl Menu {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a menu.
+    ///
+    /// ```
+    /// use gio::prelude::*;
+    ///
+    /// let menu = gio::Menu::builder()
+    ///     .action("New", "app.new")
+    ///     .item(
+    ///         gio::MenuItem::builder()
+    ///             .label("Open Recent")
+    ///             .action_and_target("app.open", "notes.txt"),
+    ///     )
+    ///     .section(gio::Menu::builder().action("Quit", "app.quit"))
+    ///     .submenu(
+    ///         "Help",
+    ///         gio::Menu::builder().action_and_target("About", "app.about", 1u32),
+    ///     )
+    ///     .build();
+    /// assert_eq!(menu.n_items(), 4);
+    /// ```
+    pub fn builder() -> MenuBuilder {
+        MenuBuilder::new()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`Menu`] objects.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct MenuBuilder {
+    menu: Menu,
+}
+
+impl MenuBuilder {
+    fn new() -> Self {
+        Self { menu: Menu::new() }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends an item with a detailed action like `app.quit` or `app.open::foo`.
+    pub fn action(self, label: &str, detailed_action: &str) -> Self {
+        self.menu.append(Some(label), Some(detailed_action));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends an item that activates `action` with `target` as its parameter.
+    pub fn action_and_target(self, label: &str, action: &str, target: impl ToVariant) -> Self {
+        self.item(
+            MenuItem::builder()
+                .label(label)
+                .action_and_target(action, target),
+        )
+    }
+
+    pub fn item(self, item: MenuItemBuilder) -> Self {
+        self.menu.append_item(&item.build());
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends a section without a label.
+    pub fn section(self, section: MenuBuilder) -> Self {
+        self.menu.append_section(None, &section.build());
+        self
+    }
+
+    pub fn labeled_section(self, label: &str, section: MenuBuilder) -> Self {
+        self.menu.append_section(Some(label), &section.build());
+        self
+    }
+
+    pub fn submenu(self, label: &str, submenu: MenuBuilder) -> Self {
+        self.menu.append_submenu(Some(label), &submenu.build());
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Build the [`Menu`].
+    pub fn build(self) -> Menu {
+        self.menu
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MenuModel, prelude::*};
+
+    #[test]
+    fn nested() {
+        let menu = Menu::builder()
+            .action("One", "app.one")
+            .labeled_section(
+                "Section",
+                Menu::builder().action_and_target("Two", "app.two", 2u8),
+            )
+            .submenu("Submenu", Menu::builder().action("Three", "app.three"))
+            .build();
+        assert_eq!(menu.n_items(), 3);
+
+        let section = menu.item_link(1, "section").unwrap();
+        assert_eq!(
+            section.item_attribute_value(0, "target", None),
+            Some(2u8.to_variant())
+        );
+        let submenu: MenuModel = menu.item_link(2, "submenu").unwrap();
+        assert_eq!(
+            submenu
+                .item_attribute_value(0, "label", None)
+                .and_then(|label| label.get::<String>()),
+            Some("Three".to_owned())
+        );
+    }
+}
@@ -0,0 +1,135 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::prelude::*;
+
+use crate::{Icon, MenuItem, MenuModel};
+
+impl MenuItem {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a menu item.
+    ///
+    /// ```
+    /// let item = gio::MenuItem::builder()
+    ///     .label("Open Recent")
+    ///     .action_and_target("app.open", "notes.txt")
+    ///     .attribute("accel", "<Control>r")
+    ///     .build();
+    /// assert_eq!(item.attribute::<String>("target").as_deref(), Some("notes.txt"));
+    /// ```
+    pub fn builder() -> MenuItemBuilder {
+        MenuItemBuilder::new()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of `attribute` if it is set and has the type of `T`.
+    #[doc(alias = "g_menu_item_get_attribute")]
+    #[doc(alias = "get_attribute")]
+    pub fn attribute<T: FromVariant>(&self, attribute: &str) -> Option<T> {
+        self.attribute_value(attribute, Some(&T::static_variant_type()))
+            .and_then(|value| value.get())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the action of the item to `action` with `target` as its parameter.
+    ///
+    /// Unlike with detailed action strings like `app.open::foo`, the target can be of any type.
+    #[doc(alias = "g_menu_item_set_action_and_target")]
+    pub fn set_action_and_target(&self, action: &str, target: impl ToVariant) {
+        self.set_action_and_target_value(Some(action), Some(&target.to_variant()));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets `attribute` to `value`.
+    #[doc(alias = "g_menu_item_set_attribute")]
+    pub fn set_attribute(&self, attribute: &str, value: impl ToVariant) {
+        self.set_attribute_value(attribute, Some(&value.to_variant()));
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A [builder-pattern] type to construct [`MenuItem`] objects.
+///
+/// [builder-pattern]: https://doc.rust-lang.org/1.0.0/style/ownership/builders.html
+#[derive(Debug)]
+#[must_use = "The builder must be built to be used"]
+pub struct MenuItemBuilder {
+    item: MenuItem,
+}
+
+impl MenuItemBuilder {
+    fn new() -> Self {
+        Self {
+            item: MenuItem::new(None, None),
+        }
+    }
+
+    pub fn label(self, label: &str) -> Self {
+        self.item.set_label(Some(label));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets a detailed action like `app.quit` or `app.open::foo`.
+    pub fn action(self, detailed_action: &str) -> Self {
+        self.item.set_detailed_action(detailed_action);
+        self
+    }
+
+    pub fn action_and_target(self, action: &str, target: impl ToVariant) -> Self {
+        self.item.set_action_and_target(action, target);
+        self
+    }
+
+    pub fn icon(self, icon: &impl IsA<Icon>) -> Self {
+        self.item.set_icon(icon);
+        self
+    }
+
+    pub fn attribute(self, attribute: &str, value: impl ToVariant) -> Self {
+        self.item.set_attribute(attribute, value);
+        self
+    }
+
+    pub fn section(self, section: &impl IsA<MenuModel>) -> Self {
+        self.item.set_section(Some(section));
+        self
+    }
+
+    pub fn submenu(self, submenu: &impl IsA<MenuModel>) -> Self {
+        self.item.set_submenu(Some(submenu));
+        self
+    }
+
+    pub fn link(self, link: &str, model: &impl IsA<MenuModel>) -> Self {
+        self.item.set_link(link, Some(model));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Build the [`MenuItem`].
+    pub fn build(self) -> MenuItem {
+        self.item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn action_and_target() {
+        let item = MenuItem::builder()
+            .label("Go")
+            .action_and_target("win.go", (1i32, "two"))
+            .build();
+        assert_eq!(
+            item.attribute::<String>("action").as_deref(),
+            Some("win.go")
+        );
+        assert_eq!(
+            item.attribute::<(i32, String)>("target"),
+            Some((1, "two".to_owned()))
+        );
+        assert_eq!(item.attribute::<i32>("target"), None);
+    }
+}
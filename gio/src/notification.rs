@@ -0,0 +1,22 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use glib::prelude::*;
+
+use crate::Notification;
+
+impl Notification {
+    // rustdoc-stripper-ignore-next
+    /// Adds a button that activates `action` with `target` as its parameter.
+    #[doc(alias = "g_notification_add_button_with_target")]
+    pub fn add_button_with_target(&self, label: &str, action: &str, target: impl ToVariant) {
+        self.add_button_with_target_value(label, action, Some(&target.to_variant()));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the action that is activated with `target` as its parameter when the notification is
+    /// clicked.
+    #[doc(alias = "g_notification_set_default_action_and_target")]
+    pub fn set_default_action_and_target(&self, action: &str, target: impl ToVariant) {
+        self.set_default_action_and_target_value(action, Some(&target.to_variant()));
+    }
+}
@@ -10,6 +10,9 @@ pub use glib::prelude::*;
 pub use crate::app_info::AppInfoExtManual;
 #[cfg(feature = "v2_72")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v2_72")))]
+pub use crate::debug_controller::DebugControllerExtManual;
+#[cfg(feature = "v2_72")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v2_72")))]
 pub use crate::debug_controller_dbus::DebugControllerDBusExtManual;
 pub use crate::{
     action_map::ActionMapExtManual, application::ApplicationExtManual,
@@ -22,6 +25,7 @@ pub use crate::{
     io_stream::IOStreamExtManual, list_model::ListModelExtManual,
     output_stream::OutputStreamExtManual, pollable_input_stream::PollableInputStreamExtManual,
     pollable_output_stream::PollableOutputStreamExtManual, settings::SettingsExtManual,
+    settings_backend::SettingsBackendExtManual,
     simple_proxy_resolver::SimpleProxyResolverExtManual, socket::SocketExtManual,
     socket_control_message::SocketControlMessageExtManual,
     socket_listener::SocketListenerExtManual, tls_connection::TlsConnectionExtManual,
@@ -1,6 +1,22 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use glib::{BoolError, StrV, Variant, prelude::*, translate::*};
+use std::{
+    boxed::Box as Box_,
+    cell::RefCell,
+    mem::transmute,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use futures_channel::mpsc;
+use futures_core::Stream;
+use glib::{
+    BoolError, Quark, StrV, Variant,
+    prelude::*,
+    signal::{SignalHandlerId, connect_raw},
+    translate::*,
+};
 
 use crate::{Settings, SettingsBindFlags, ffi, prelude::*};
 
@@ -235,10 +251,159 @@ pub trait SettingsExtManual: IsA<Settings> {
             set_mapping: None,
         }
     }
+
+    // rustdoc-stripper-ignore-next
+    /// Connects to the `change-event` signal, which is emitted once for all keys that changed
+    /// together, e.g. with a single write of the backend.
+    ///
+    /// `f` receives the changed keys. Returning [`glib::Propagation::Stop`] prevents the
+    /// `changed` signal from being emitted for them.
+    #[doc(alias = "change-event")]
+    fn connect_change_event<F: Fn(&Self, &[Quark]) -> glib::Propagation + 'static>(
+        &self,
+        f: F,
+    ) -> SignalHandlerId {
+        unsafe extern "C" fn change_event_trampoline<
+            P: IsA<Settings>,
+            F: Fn(&P, &[Quark]) -> glib::Propagation + 'static,
+        >(
+            this: *mut ffi::GSettings,
+            keys: glib::ffi::gpointer,
+            n_keys: std::ffi::c_int,
+            f: glib::ffi::gpointer,
+        ) -> glib::ffi::gboolean {
+            unsafe {
+                let f: &F = &*(f as *const F);
+                let settings = Settings::from_glib_borrow(this);
+                // `NULL` means that all keys may have changed.
+                let all_keys;
+                let keys = if keys.is_null() {
+                    all_keys = settings
+                        .settings_schema()
+                        .map(|schema| {
+                            schema
+                                .list_keys()
+                                .iter()
+                                .map(Quark::from_str)
+                                .collect::<Vec<_>>()
+                        })
+                        .unwrap_or_default();
+                    &all_keys[..]
+                } else {
+                    // `Quark` is a transparent wrapper around `GQuark`.
+                    std::slice::from_raw_parts(keys as *const Quark, n_keys as usize)
+                };
+                f(settings.unsafe_cast_ref(), keys).into_glib()
+            }
+        }
+        unsafe {
+            let f: Box_<F> = Box_::new(f);
+            connect_raw(
+                self.as_ptr() as *mut _,
+                c"change-event".as_ptr() as *const _,
+                Some(transmute::<*const (), unsafe extern "C" fn()>(
+                    change_event_trampoline::<Self, F> as *const (),
+                )),
+                Box_::into_raw(f),
+            )
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a stream of the keys that changed together.
+    ///
+    /// Each item contains the keys of one `change-event`. In delayed-apply mode (see
+    /// [`delay()`](crate::prelude::SettingsExt::delay)), the keys changed while there are
+    /// unapplied changes are collected instead and yielded together once the changes are
+    /// applied with [`apply()`](crate::prelude::SettingsExt::apply) or reverted with
+    /// [`revert()`](crate::prelude::SettingsExt::revert).
+    ///
+    /// The stream holds a strong reference to the settings and disconnects its signal handlers
+    /// when dropped.
+    fn changes_stream(&self) -> SettingsChangesStream {
+        struct Pending {
+            keys: RefCell<Vec<Quark>>,
+            sender: mpsc::UnboundedSender<Vec<Quark>>,
+        }
+
+        impl Pending {
+            fn push(&self, keys: &[Quark]) {
+                let mut pending = self.keys.borrow_mut();
+                for key in keys {
+                    if !pending.contains(key) {
+                        pending.push(*key);
+                    }
+                }
+            }
+
+            fn flush(&self) {
+                let keys = std::mem::take(&mut *self.keys.borrow_mut());
+                if !keys.is_empty() {
+                    let _ = self.sender.unbounded_send(keys);
+                }
+            }
+        }
+
+        let (sender, receiver) = mpsc::unbounded();
+        let pending = Rc::new(Pending {
+            keys: RefCell::new(Vec::new()),
+            sender,
+        });
+
+        let change_event = self.connect_change_event({
+            let pending = pending.clone();
+            move |settings, keys| {
+                pending.push(keys);
+                if !settings.has_unapplied() {
+                    pending.flush();
+                }
+                glib::Propagation::Proceed
+            }
+        });
+        let has_unapplied = self.connect_has_unapplied_notify(move |settings| {
+            if !settings.has_unapplied() {
+                pending.flush();
+            }
+        });
+
+        SettingsChangesStream {
+            settings: self.as_ref().clone(),
+            handlers: vec![change_event, has_unapplied],
+            receiver,
+        }
+    }
 }
 
 impl<O: IsA<Settings>> SettingsExtManual for O {}
 
+// rustdoc-stripper-ignore-next
+/// A stream of the keys of [`Settings`] that changed together.
+///
+/// See [`SettingsExtManual::changes_stream()`].
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct SettingsChangesStream {
+    settings: Settings,
+    handlers: Vec<SignalHandlerId>,
+    receiver: mpsc::UnboundedReceiver<Vec<Quark>>,
+}
+
+impl Stream for SettingsChangesStream {
+    type Item = Vec<Quark>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
+
+impl Drop for SettingsChangesStream {
+    fn drop(&mut self) {
+        for handler in self.handlers.drain(..) {
+            self.settings.disconnect(handler);
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::{env::set_var, process::Command, str::from_utf8, sync::Once};
@@ -297,6 +462,75 @@ mod test {
         assert!(!settings.get::<bool>("test-bool"));
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn change_event() {
+        set_env();
+        let settings = Settings::new("com.github.gtk-rs.test");
+        let changed = Rc::new(RefCell::new(Vec::new()));
+        settings.connect_changed(None, {
+            let changed = changed.clone();
+            move |_, key| changed.borrow_mut().push(key.to_owned())
+        });
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let stop = Rc::new(RefCell::new(false));
+        settings.connect_change_event({
+            let events = events.clone();
+            let stop = stop.clone();
+            move |_, keys| {
+                events.borrow_mut().push(keys.to_vec());
+                if *stop.borrow() {
+                    glib::Propagation::Stop
+                } else {
+                    glib::Propagation::Proceed
+                }
+            }
+        });
+
+        settings.set("test-bool", true).unwrap();
+        assert_eq!(*events.borrow(), [[Quark::from_str("test-bool")]]);
+        assert_eq!(*changed.borrow(), ["test-bool"]);
+
+        *stop.borrow_mut() = true;
+        settings.set("test-bool", false).unwrap();
+        assert_eq!(
+            *events.borrow(),
+            [
+                [Quark::from_str("test-bool")],
+                [Quark::from_str("test-bool")]
+            ]
+        );
+        assert_eq!(*changed.borrow(), ["test-bool"]);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn changes_stream() {
+        use futures_util::{FutureExt, StreamExt};
+
+        set_env();
+        let settings = Settings::new("com.github.gtk-rs.test");
+        let mut changes = settings.changes_stream();
+
+        settings.set("test-string", "Changed").unwrap();
+        let keys = changes.next().now_or_never().unwrap().unwrap();
+        assert_eq!(keys, [Quark::from_str("test-string")]);
+
+        settings.delay();
+        settings
+            .set("test-bool", !settings.get::<bool>("test-bool"))
+            .unwrap();
+        settings.set("test-string", "Good").unwrap();
+        assert!(changes.next().now_or_never().is_none());
+
+        settings.apply();
+        let keys = changes.next().now_or_never().unwrap().unwrap();
+        assert_eq!(
+            keys,
+            [Quark::from_str("test-bool"), Quark::from_str("test-string")]
+        );
+    }
+
     #[test]
     #[should_panic]
     #[serial_test::serial]
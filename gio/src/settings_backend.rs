@@ -0,0 +1,234 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{collections::BTreeMap, ffi::CStr, ptr};
+
+use glib::{Variant, prelude::*, translate::*};
+
+use crate::{SettingsBackend, ffi};
+
+// rustdoc-stripper-ignore-next
+/// An opaque tag passed to the writes of a [`SettingsBackend`] that has to be passed on to the
+/// change notifications caused by them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct SettingsBackendOriginTag(glib::ffi::gpointer);
+
+impl SettingsBackendOriginTag {
+    // rustdoc-stripper-ignore-next
+    /// The tag for changes that don't originate from a write, e.g. because the underlying
+    /// storage was modified externally.
+    pub const NONE: Self = Self(ptr::null_mut());
+
+    #[inline]
+    pub fn as_ptr(&self) -> glib::ffi::gpointer {
+        self.0
+    }
+
+    #[inline]
+    pub(crate) fn from_ptr(ptr: glib::ffi::gpointer) -> Self {
+        Self(ptr)
+    }
+}
+
+impl Default for SettingsBackendOriginTag {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+unsafe extern "C" fn compare_keys(
+    a: glib::ffi::gconstpointer,
+    b: glib::ffi::gconstpointer,
+    _user_data: glib::ffi::gpointer,
+) -> std::os::raw::c_int {
+    unsafe { libc::strcmp(a as *const _, b as *const _) }
+}
+
+unsafe extern "C" fn variant_unref0(value: glib::ffi::gpointer) {
+    unsafe {
+        if !value.is_null() {
+            glib::ffi::g_variant_unref(value as *mut _);
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Creates a `GTree` like the ones passed to `GSettingsBackendClass::write_tree`, mapping keys
+/// to values or to `NULL` for keys that are reset.
+pub(crate) fn tree_to_glib(tree: &BTreeMap<String, Option<Variant>>) -> *mut glib::ffi::GTree {
+    unsafe {
+        let gtree = glib::ffi::g_tree_new_full(
+            Some(compare_keys),
+            ptr::null_mut(),
+            Some(glib::ffi::g_free),
+            Some(variant_unref0),
+        );
+        for (key, value) in tree {
+            glib::ffi::g_tree_insert(
+                gtree,
+                ToGlibPtr::<*mut libc::c_char>::to_glib_full(key.as_str()) as glib::ffi::gpointer,
+                value.as_ref().map_or(ptr::null_mut(), |value| {
+                    ToGlibPtr::<*mut glib::ffi::GVariant>::to_glib_full(value)
+                        as glib::ffi::gpointer
+                }),
+            );
+        }
+        gtree
+    }
+}
+
+pub(crate) unsafe fn tree_from_glib(
+    gtree: *mut glib::ffi::GTree,
+) -> BTreeMap<String, Option<Variant>> {
+    unsafe extern "C" fn collect(
+        key: glib::ffi::gpointer,
+        value: glib::ffi::gpointer,
+        user_data: glib::ffi::gpointer,
+    ) -> glib::ffi::gboolean {
+        unsafe {
+            let tree = &mut *(user_data as *mut BTreeMap<String, Option<Variant>>);
+            let key = CStr::from_ptr(key as *const _)
+                .to_string_lossy()
+                .into_owned();
+            tree.insert(key, from_glib_none(value as *mut glib::ffi::GVariant));
+            glib::ffi::GFALSE
+        }
+    }
+
+    unsafe {
+        let mut tree = BTreeMap::new();
+        glib::ffi::g_tree_foreach(
+            gtree,
+            Some(collect),
+            &mut tree as *mut BTreeMap<String, Option<Variant>> as glib::ffi::gpointer,
+        );
+        tree
+    }
+}
+
+impl SettingsBackend {
+    // rustdoc-stripper-ignore-next
+    /// Splits `tree` into the longest common path of its keys, the keys relative to that path
+    /// and their values, where `None` means that the key is reset.
+    #[doc(alias = "g_settings_backend_flatten_tree")]
+    pub fn flatten_tree(
+        tree: &BTreeMap<String, Option<Variant>>,
+    ) -> (glib::GString, Vec<glib::GString>, Vec<Option<Variant>>) {
+        unsafe {
+            let gtree = tree_to_glib(tree);
+            let mut path = ptr::null_mut();
+            let mut keys = ptr::null_mut();
+            let mut values = ptr::null_mut();
+            ffi::g_settings_backend_flatten_tree(gtree, &mut path, &mut keys, &mut values);
+
+            // The keys and values are borrowed from the tree, only the arrays are owned.
+            let len = tree.len();
+            let ret_keys = (0..len)
+                .map(|i| from_glib_none(*keys.add(i)))
+                .collect::<Vec<_>>();
+            let ret_values = (0..len)
+                .map(|i| from_glib_none(*values.add(i)))
+                .collect::<Vec<_>>();
+            glib::ffi::g_free(keys as glib::ffi::gpointer);
+            glib::ffi::g_free(values as glib::ffi::gpointer);
+            glib::ffi::g_tree_unref(gtree);
+
+            (from_glib_full(path), ret_keys, ret_values)
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Change notifications to be emitted by implementations of [`SettingsBackend`].
+pub trait SettingsBackendExtManual: IsA<SettingsBackend> + 'static {
+    // rustdoc-stripper-ignore-next
+    /// Signals that the value of `key` changed. Writes must pass on their `origin_tag`.
+    #[doc(alias = "g_settings_backend_changed")]
+    fn changed(&self, key: &str, origin_tag: SettingsBackendOriginTag) {
+        unsafe {
+            ffi::g_settings_backend_changed(
+                self.as_ref().to_glib_none().0,
+                key.to_glib_none().0,
+                origin_tag.as_ptr(),
+            );
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Signals that the keys in `tree` changed, e.g. after a write of that tree.
+    #[doc(alias = "g_settings_backend_changed_tree")]
+    fn changed_tree(
+        &self,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: SettingsBackendOriginTag,
+    ) {
+        unsafe {
+            let gtree = tree_to_glib(tree);
+            ffi::g_settings_backend_changed_tree(
+                self.as_ref().to_glib_none().0,
+                gtree,
+                origin_tag.as_ptr(),
+            );
+            glib::ffi::g_tree_unref(gtree);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Signals that the values of `items`, relative to the directory `path`, changed.
+    #[doc(alias = "g_settings_backend_keys_changed")]
+    fn keys_changed(&self, path: &str, items: &[&str], origin_tag: SettingsBackendOriginTag) {
+        unsafe {
+            ffi::g_settings_backend_keys_changed(
+                self.as_ref().to_glib_none().0,
+                path.to_glib_none().0,
+                ToGlibContainerFromSlice::<*mut *const libc::c_char>::to_glib_none_from_slice(items)
+                    .0 as *const *const _,
+                origin_tag.as_ptr(),
+            );
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Signals that all keys below the directory `path` may have changed.
+    #[doc(alias = "g_settings_backend_path_changed")]
+    fn path_changed(&self, path: &str, origin_tag: SettingsBackendOriginTag) {
+        unsafe {
+            ffi::g_settings_backend_path_changed(
+                self.as_ref().to_glib_none().0,
+                path.to_glib_none().0,
+                origin_tag.as_ptr(),
+            );
+        }
+    }
+}
+
+impl<O: IsA<SettingsBackend>> SettingsBackendExtManual for O {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_tree() {
+        let mut tree = BTreeMap::new();
+        tree.insert("/org/example/a".to_owned(), Some(1i32.to_variant()));
+        tree.insert("/org/example/sub/b".to_owned(), None);
+
+        let (path, keys, values) = SettingsBackend::flatten_tree(&tree);
+        assert_eq!(path, "/org/example/");
+        assert_eq!(keys, ["a", "sub/b"]);
+        assert_eq!(values, [Some(1i32.to_variant()), None]);
+    }
+
+    #[test]
+    fn tree_roundtrip() {
+        let mut tree = BTreeMap::new();
+        tree.insert("/b".to_owned(), Some("b".to_variant()));
+        tree.insert("/a".to_owned(), None);
+
+        let gtree = tree_to_glib(&tree);
+        let roundtrip = unsafe { tree_from_glib(gtree) };
+        unsafe { glib::ffi::g_tree_unref(gtree) };
+        assert_eq!(roundtrip, tree);
+    }
+}
@@ -0,0 +1,138 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{ptr, slice};
+
+use glib::{Error, prelude::*, subclass::prelude::*, translate::*};
+
+use crate::{Converter, ConverterFlags, ConverterResult, ffi};
+
+pub trait ConverterImpl: ObjectImpl + ObjectSubclass<Type: IsA<Converter>> {
+    // rustdoc-stripper-ignore-next
+    /// Converts data from `inbuf` into `outbuf` and returns the result together with the
+    /// number of bytes read from `inbuf` and written to `outbuf`.
+    fn convert(
+        &self,
+        inbuf: &[u8],
+        outbuf: &mut [u8],
+        flags: ConverterFlags,
+    ) -> Result<(ConverterResult, usize, usize), Error>;
+
+    fn reset(&self);
+}
+
+pub trait ConverterImplExt: ConverterImpl {
+    fn parent_convert(
+        &self,
+        inbuf: &[u8],
+        outbuf: &mut [u8],
+        flags: ConverterFlags,
+    ) -> Result<(ConverterResult, usize, usize), Error> {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().parent_interface::<Converter>() as *const ffi::GConverterIface;
+
+            let func = (*parent_iface)
+                .convert
+                .expect("no parent \"convert\" implementation");
+
+            let mut bytes_read = 0;
+            let mut bytes_written = 0;
+            let mut err = ptr::null_mut();
+            let ret = func(
+                self.obj().unsafe_cast_ref::<Converter>().to_glib_none().0,
+                mut_override(inbuf.as_ptr()),
+                inbuf.len(),
+                outbuf.as_mut_ptr(),
+                outbuf.len(),
+                flags.into_glib(),
+                &mut bytes_read,
+                &mut bytes_written,
+                &mut err,
+            );
+
+            if err.is_null() {
+                Ok((from_glib(ret), bytes_read, bytes_written))
+            } else {
+                Err(from_glib_full(err))
+            }
+        }
+    }
+
+    fn parent_reset(&self) {
+        unsafe {
+            let type_data = Self::type_data();
+            let parent_iface =
+                type_data.as_ref().parent_interface::<Converter>() as *const ffi::GConverterIface;
+
+            let func = (*parent_iface)
+                .reset
+                .expect("no parent \"reset\" implementation");
+            func(self.obj().unsafe_cast_ref::<Converter>().to_glib_none().0)
+        }
+    }
+}
+
+impl<T: ConverterImpl> ConverterImplExt for T {}
+
+unsafe impl<T: ConverterImpl> IsImplementable<T> for Converter {
+    fn interface_init(iface: &mut glib::Interface<Self>) {
+        let iface = iface.as_mut();
+
+        iface.convert = Some(converter_convert::<T>);
+        iface.reset = Some(converter_reset::<T>);
+    }
+}
+
+unsafe extern "C" fn converter_convert<T: ConverterImpl>(
+    converter: *mut ffi::GConverter,
+    inbuf: *mut u8,
+    inbuf_size: usize,
+    outbuf: *mut u8,
+    outbuf_size: usize,
+    flags: ffi::GConverterFlags,
+    bytes_read: *mut usize,
+    bytes_written: *mut usize,
+    err: *mut *mut glib::ffi::GError,
+) -> ffi::GConverterResult {
+    unsafe {
+        let instance = &*(converter as *mut T::Instance);
+        let imp = instance.imp();
+
+        let inbuf = if inbuf_size == 0 {
+            &[][..]
+        } else {
+            slice::from_raw_parts(inbuf, inbuf_size)
+        };
+        let outbuf = if outbuf_size == 0 {
+            &mut [][..]
+        } else {
+            slice::from_raw_parts_mut(outbuf, outbuf_size)
+        };
+
+        match imp.convert(inbuf, outbuf, from_glib(flags)) {
+            Ok((res, read, written)) => {
+                assert!(read <= inbuf_size);
+                assert!(written <= outbuf_size);
+                *bytes_read = read;
+                *bytes_written = written;
+                res.into_glib()
+            }
+            Err(e) => {
+                if !err.is_null() {
+                    *err = e.into_glib_ptr();
+                }
+                ffi::G_CONVERTER_ERROR
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn converter_reset<T: ConverterImpl>(converter: *mut ffi::GConverter) {
+    unsafe {
+        let instance = &*(converter as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.reset()
+    }
+}
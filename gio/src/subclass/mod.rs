@@ -4,6 +4,7 @@ mod action_group;
 mod action_map;
 mod application;
 mod async_initable;
+mod converter;
 mod dbus_interface;
 mod dbus_proxy;
 mod file;
@@ -15,6 +16,7 @@ mod io_stream;
 mod list_model;
 mod output_stream;
 mod seekable;
+mod settings_backend;
 mod socket_control_message;
 mod vfs;
 
@@ -29,6 +31,7 @@ pub mod prelude {
         action_map::{ActionMapImpl, ActionMapImplExt},
         application::{ApplicationImpl, ApplicationImplExt},
         async_initable::{AsyncInitableImpl, AsyncInitableImplExt},
+        converter::{ConverterImpl, ConverterImplExt},
         dbus_interface::{DBusInterfaceImpl, DBusInterfaceImplExt},
         dbus_proxy::{DBusProxyImpl, DBusProxyImplExt},
         file::{FileImpl, FileImplExt},
@@ -40,6 +43,7 @@ pub mod prelude {
         list_model::{ListModelImpl, ListModelImplExt},
         output_stream::{OutputStreamImpl, OutputStreamImplExt},
         seekable::{SeekableImpl, SeekableImplExt},
+        settings_backend::{SettingsBackendImpl, SettingsBackendImplExt},
         socket_control_message::{SocketControlMessageImpl, SocketControlMessageImplExt},
         vfs::{VfsImpl, VfsImplExt},
     };
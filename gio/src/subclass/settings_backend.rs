@@ -0,0 +1,585 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::collections::BTreeMap;
+
+use glib::{Variant, VariantTy, prelude::*, subclass::prelude::*, translate::*};
+
+use crate::{
+    Permission, SettingsBackend, SettingsBackendOriginTag, ffi,
+    settings_backend::{tree_from_glib, tree_to_glib},
+};
+
+// Support custom implementation of virtual functions defined in `gio::ffi::GSettingsBackendClass`.
+//
+// Implementations must notify about changes with the methods of `SettingsBackendExtManual`,
+// passing on the origin tag of the write that caused them.
+pub trait SettingsBackendImpl: ObjectImpl + ObjectSubclass<Type: IsA<SettingsBackend>> {
+    fn read(&self, key: &str, expected_type: &VariantTy, default_value: bool) -> Option<Variant> {
+        self.parent_read(key, expected_type, default_value)
+    }
+
+    fn read_user_value(&self, key: &str, expected_type: &VariantTy) -> Option<Variant> {
+        self.parent_read_user_value(key, expected_type)
+    }
+
+    fn writable(&self, key: &str) -> bool {
+        self.parent_writable(key)
+    }
+
+    fn write(&self, key: &str, value: &Variant, origin_tag: SettingsBackendOriginTag) -> bool {
+        self.parent_write(key, value, origin_tag)
+    }
+
+    fn write_tree(
+        &self,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: SettingsBackendOriginTag,
+    ) -> bool {
+        self.parent_write_tree(tree, origin_tag)
+    }
+
+    fn reset(&self, key: &str, origin_tag: SettingsBackendOriginTag) {
+        self.parent_reset(key, origin_tag)
+    }
+
+    fn subscribe(&self, name: &str) {
+        self.parent_subscribe(name)
+    }
+
+    fn unsubscribe(&self, name: &str) {
+        self.parent_unsubscribe(name)
+    }
+
+    fn sync(&self) {
+        self.parent_sync()
+    }
+
+    fn permission(&self, path: &str) -> Permission {
+        self.parent_permission(path)
+    }
+}
+
+// Support parent implementation of virtual functions defined in `gio::ffi::GSettingsBackendClass`.
+pub trait SettingsBackendImplExt: SettingsBackendImpl {
+    fn parent_read(
+        &self,
+        key: &str,
+        expected_type: &VariantTy,
+        default_value: bool,
+    ) -> Option<Variant> {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            let f = (*parent_class)
+                .read
+                .expect("No parent class implementation for \"read\"");
+
+            from_glib_full(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                expected_type.to_glib_none().0,
+                default_value.into_glib(),
+            ))
+        }
+    }
+
+    fn parent_read_user_value(&self, key: &str, expected_type: &VariantTy) -> Option<Variant> {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            let f = (*parent_class)
+                .read_user_value
+                .expect("No parent class implementation for \"read_user_value\"");
+
+            from_glib_full(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                expected_type.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_writable(&self, key: &str) -> bool {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            let f = (*parent_class)
+                .get_writable
+                .expect("No parent class implementation for \"get_writable\"");
+
+            from_glib(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+            ))
+        }
+    }
+
+    fn parent_write(
+        &self,
+        key: &str,
+        value: &Variant,
+        origin_tag: SettingsBackendOriginTag,
+    ) -> bool {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            let f = (*parent_class)
+                .write
+                .expect("No parent class implementation for \"write\"");
+
+            from_glib(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                value.to_glib_none().0,
+                origin_tag.as_ptr(),
+            ))
+        }
+    }
+
+    fn parent_write_tree(
+        &self,
+        tree: &BTreeMap<String, Option<Variant>>,
+        origin_tag: SettingsBackendOriginTag,
+    ) -> bool {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            let f = (*parent_class)
+                .write_tree
+                .expect("No parent class implementation for \"write_tree\"");
+
+            let gtree = tree_to_glib(tree);
+            let ret = f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                gtree,
+                origin_tag.as_ptr(),
+            );
+            glib::ffi::g_tree_unref(gtree);
+            from_glib(ret)
+        }
+    }
+
+    fn parent_reset(&self, key: &str, origin_tag: SettingsBackendOriginTag) {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            let f = (*parent_class)
+                .reset
+                .expect("No parent class implementation for \"reset\"");
+
+            f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                key.to_glib_none().0,
+                origin_tag.as_ptr(),
+            )
+        }
+    }
+
+    fn parent_subscribe(&self, name: &str) {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            if let Some(f) = (*parent_class).subscribe {
+                f(
+                    self.obj()
+                        .unsafe_cast_ref::<SettingsBackend>()
+                        .to_glib_none()
+                        .0,
+                    name.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_unsubscribe(&self, name: &str) {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            if let Some(f) = (*parent_class).unsubscribe {
+                f(
+                    self.obj()
+                        .unsafe_cast_ref::<SettingsBackend>()
+                        .to_glib_none()
+                        .0,
+                    name.to_glib_none().0,
+                )
+            }
+        }
+    }
+
+    fn parent_sync(&self) {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            if let Some(f) = (*parent_class).sync {
+                f(self
+                    .obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0)
+            }
+        }
+    }
+
+    fn parent_permission(&self, path: &str) -> Permission {
+        unsafe {
+            let data = Self::type_data();
+            let parent_class = data.as_ref().parent_class() as *const ffi::GSettingsBackendClass;
+
+            let f = (*parent_class)
+                .get_permission
+                .expect("No parent class implementation for \"get_permission\"");
+
+            from_glib_full(f(
+                self.obj()
+                    .unsafe_cast_ref::<SettingsBackend>()
+                    .to_glib_none()
+                    .0,
+                path.to_glib_none().0,
+            ))
+        }
+    }
+}
+
+impl<T: SettingsBackendImpl> SettingsBackendImplExt for T {}
+
+// Implement virtual functions defined in `gio::ffi::GSettingsBackendClass`.
+unsafe impl<T: SettingsBackendImpl> IsSubclassable<T> for SettingsBackend {
+    fn class_init(class: &mut ::glib::Class<Self>) {
+        Self::parent_class_init::<T>(class);
+
+        let klass = class.as_mut();
+        klass.read = Some(read::<T>);
+        klass.read_user_value = Some(read_user_value::<T>);
+        klass.get_writable = Some(get_writable::<T>);
+        klass.write = Some(write::<T>);
+        klass.write_tree = Some(write_tree::<T>);
+        klass.reset = Some(reset::<T>);
+        klass.subscribe = Some(subscribe::<T>);
+        klass.unsubscribe = Some(unsubscribe::<T>);
+        klass.sync = Some(sync::<T>);
+        klass.get_permission = Some(get_permission::<T>);
+    }
+}
+
+unsafe extern "C" fn read<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+    expected_type: *const glib::ffi::GVariantType,
+    default_value: glib::ffi::gboolean,
+) -> *mut glib::ffi::GVariant {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.read(
+            &glib::GString::from_glib_borrow(key),
+            VariantTy::from_ptr(expected_type),
+            from_glib(default_value),
+        )
+        .into_glib_ptr()
+    }
+}
+
+unsafe extern "C" fn read_user_value<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+    expected_type: *const glib::ffi::GVariantType,
+) -> *mut glib::ffi::GVariant {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.read_user_value(
+            &glib::GString::from_glib_borrow(key),
+            VariantTy::from_ptr(expected_type),
+        )
+        .into_glib_ptr()
+    }
+}
+
+unsafe extern "C" fn get_writable<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+) -> glib::ffi::gboolean {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.writable(&glib::GString::from_glib_borrow(key))
+            .into_glib()
+    }
+}
+
+unsafe extern "C" fn write<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+    value: *mut glib::ffi::GVariant,
+    origin_tag: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.write(
+            &glib::GString::from_glib_borrow(key),
+            &from_glib_borrow(value),
+            SettingsBackendOriginTag::from_ptr(origin_tag),
+        )
+        .into_glib()
+    }
+}
+
+unsafe extern "C" fn write_tree<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    tree: *mut glib::ffi::GTree,
+    origin_tag: glib::ffi::gpointer,
+) -> glib::ffi::gboolean {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.write_tree(
+            &tree_from_glib(tree),
+            SettingsBackendOriginTag::from_ptr(origin_tag),
+        )
+        .into_glib()
+    }
+}
+
+unsafe extern "C" fn reset<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    key: *const libc::c_char,
+    origin_tag: glib::ffi::gpointer,
+) {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.reset(
+            &glib::GString::from_glib_borrow(key),
+            SettingsBackendOriginTag::from_ptr(origin_tag),
+        )
+    }
+}
+
+unsafe extern "C" fn subscribe<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    name: *const libc::c_char,
+) {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.subscribe(&glib::GString::from_glib_borrow(name))
+    }
+}
+
+unsafe extern "C" fn unsubscribe<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    name: *const libc::c_char,
+) {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.unsubscribe(&glib::GString::from_glib_borrow(name))
+    }
+}
+
+unsafe extern "C" fn sync<T: SettingsBackendImpl>(backend: *mut ffi::GSettingsBackend) {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.sync()
+    }
+}
+
+unsafe extern "C" fn get_permission<T: SettingsBackendImpl>(
+    backend: *mut ffi::GSettingsBackend,
+    path: *const libc::c_char,
+) -> *mut ffi::GPermission {
+    unsafe {
+        let instance = &*(backend as *mut T::Instance);
+        let imp = instance.imp();
+
+        imp.permission(&glib::GString::from_glib_borrow(path))
+            .into_glib_ptr()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, process::Command, sync::Mutex};
+
+    use super::*;
+    use crate::{Settings, SettingsSchemaSource, SimplePermission, prelude::*};
+
+    mod imp {
+        use super::*;
+
+        // GSettings may call into the backend from any thread.
+        #[derive(Default)]
+        pub struct MapBackend {
+            pub values: Mutex<BTreeMap<String, Variant>>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for MapBackend {
+            const NAME: &'static str = "MapSettingsBackend";
+            type Type = super::MapBackend;
+            type ParentType = SettingsBackend;
+        }
+
+        impl ObjectImpl for MapBackend {}
+
+        impl SettingsBackendImpl for MapBackend {
+            fn read(
+                &self,
+                key: &str,
+                expected_type: &VariantTy,
+                default_value: bool,
+            ) -> Option<Variant> {
+                if default_value {
+                    return None;
+                }
+                self.values
+                    .lock()
+                    .unwrap()
+                    .get(key)
+                    .filter(|value| value.is_type(expected_type))
+                    .cloned()
+            }
+
+            fn writable(&self, _key: &str) -> bool {
+                true
+            }
+
+            fn write(
+                &self,
+                key: &str,
+                value: &Variant,
+                origin_tag: SettingsBackendOriginTag,
+            ) -> bool {
+                self.values
+                    .lock()
+                    .unwrap()
+                    .insert(key.to_owned(), value.clone());
+                self.obj().changed(key, origin_tag);
+                true
+            }
+
+            fn write_tree(
+                &self,
+                tree: &BTreeMap<String, Option<Variant>>,
+                origin_tag: SettingsBackendOriginTag,
+            ) -> bool {
+                let mut values = self.values.lock().unwrap();
+                for (key, value) in tree {
+                    match value {
+                        Some(value) => values.insert(key.clone(), value.clone()),
+                        None => values.remove(key),
+                    };
+                }
+                drop(values);
+                self.obj().changed_tree(tree, origin_tag);
+                true
+            }
+
+            fn reset(&self, key: &str, origin_tag: SettingsBackendOriginTag) {
+                self.values.lock().unwrap().remove(key);
+                self.obj().changed(key, origin_tag);
+            }
+
+            fn permission(&self, _path: &str) -> Permission {
+                SimplePermission::new(true).upcast()
+            }
+        }
+    }
+
+    glib::wrapper! {
+        pub struct MapBackend(ObjectSubclass<imp::MapBackend>) @extends SettingsBackend;
+    }
+
+    fn settings(backend: &MapBackend) -> Settings {
+        let tmp_dir = glib::mkdtemp("gio-rs-test-schemas-XXXXXX").unwrap();
+        let output = Command::new("glib-compile-schemas")
+            .arg(format!("{}/tests", env!("CARGO_MANIFEST_DIR")))
+            .arg("--targetdir")
+            .arg(&tmp_dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "Can't test without GSchemas!");
+
+        let source = SettingsSchemaSource::from_directory(&tmp_dir, None, false).unwrap();
+        std::fs::remove_dir_all(&tmp_dir).unwrap();
+        let schema = source.lookup("com.github.gtk-rs.test", false).unwrap();
+        Settings::new_full(&schema, Some(backend), None)
+    }
+
+    #[test]
+    fn write_and_reset() {
+        let backend = glib::Object::new::<MapBackend>();
+        let settings = settings(&backend);
+
+        let changed = std::rc::Rc::new(RefCell::new(Vec::new()));
+        settings.connect_changed(None, {
+            let changed = changed.clone();
+            move |_, key| changed.borrow_mut().push(key.to_owned())
+        });
+
+        assert_eq!(settings.string("test-string"), "Good");
+        settings.set_string("test-string", "Better").unwrap();
+        assert_eq!(settings.string("test-string"), "Better");
+        assert_eq!(
+            backend
+                .imp()
+                .values
+                .lock()
+                .unwrap()
+                .get("/com/github/gtk-rs/test-string"),
+            Some(&"Better".to_variant())
+        );
+
+        settings.delay();
+        settings.set_boolean("test-bool", false).unwrap();
+        settings.reset("test-string");
+        settings.apply();
+        assert!(!settings.boolean("test-bool"));
+        assert_eq!(settings.string("test-string"), "Good");
+        assert_eq!(backend.imp().values.lock().unwrap().len(), 1);
+
+        assert_eq!(
+            *changed.borrow(),
+            ["test-string", "test-bool", "test-string"]
+        );
+    }
+}
@@ -4,6 +4,10 @@
 
 use std::{env, path::Path, process::Command};
 
+mod settings;
+
+pub use settings::{compile_schemas, generate_settings};
+
 // rustdoc-stripper-ignore-next
 /// Call to run `glib-compile-resources` to generate compiled gresources to embed
 /// in binary with [`gio::resources_register_include`]. `target` is relative to `OUT_DIR`.
@@ -0,0 +1,817 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    collections::HashSet,
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use gio::glib::{GString, MarkupEvent, MarkupEvents, MarkupParseFlags, Variant, VariantTy};
+
+// rustdoc-stripper-ignore-next
+/// Call to run `glib-compile-schemas` on the schemas in `schema_dir`. `target` is the
+/// directory relative to `OUT_DIR` the compiled schemas are written to, which is returned.
+///
+/// The compiled schemas can be loaded with [`gio::SettingsSchemaSource::from_directory`],
+/// e.g. to use the settings in tests without installing the schemas:
+///
+/// ```no_run
+/// // build.rs
+/// glib_build_tools::compile_schemas("data", "schemas");
+/// ```
+///
+/// ```ignore
+/// let source = gio::SettingsSchemaSource::from_directory(
+///     concat!(env!("OUT_DIR"), "/schemas"),
+///     None,
+///     false,
+/// )
+/// .unwrap();
+/// let backend = gio::memory_settings_backend_new();
+/// let settings = AppSettings::from_schema_source(&source, Some(&backend), None);
+/// ```
+pub fn compile_schemas<P: AsRef<Path>>(schema_dir: P, target: &str) -> PathBuf {
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let target_dir = Path::new(&out_dir).join(target);
+    fs::create_dir_all(&target_dir).unwrap();
+
+    let output = Command::new("glib-compile-schemas")
+        .arg("--strict")
+        .arg("--targetdir")
+        .arg(&target_dir)
+        .arg(schema_dir.as_ref())
+        .output()
+        .unwrap();
+
+    assert!(
+        output.status.success(),
+        "glib-compile-schemas failed with exit status {} and stderr:\n{}",
+        output.status,
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    println!("cargo:rerun-if-changed={}", schema_dir.as_ref().display());
+    for entry in fs::read_dir(schema_dir.as_ref()).unwrap() {
+        let path = entry.unwrap().path();
+        if path.to_str().is_some_and(|path| path.ends_with(".xml")) {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+
+    target_dir
+}
+
+// rustdoc-stripper-ignore-next
+/// Call to generate typed wrappers around [`gio::Settings`] for the schemas in `schema_files`.
+/// `target` is relative to `OUT_DIR` and meant to be included with [`include!`].
+///
+/// For each schema, a type named after the last component of its id followed by `Settings` is
+/// generated, e.g. `AppSettings` for `org.example.App`. It has a getter, a setter and a
+/// `connect_*_changed` method for each key, and constants for the defaults and ranges of the
+/// keys. Setters of keys with a range return an error if the value is out of range. Types of
+/// schemas with a fixed path are created with `new()` or `Default`, the others with `with_path()`.
+///
+/// Keys of basic types are mapped to the corresponding Rust types, string arrays to
+/// [`glib::StrV`](gio::glib::StrV) and enums declared with `<enum>` to generated Rust enums
+/// named after the last component of their id. Keys of all other types are accessed as
+/// [`glib::Variant`](gio::glib::Variant).
+///
+/// ```no_run
+/// // build.rs
+/// glib_build_tools::generate_settings(&["data/org.example.App.gschema.xml"], "settings.rs");
+/// ```
+///
+/// ```ignore
+/// include!(concat!(env!("OUT_DIR"), "/settings.rs"));
+///
+/// let settings = AppSettings::new();
+/// settings.set_volume(11).unwrap();
+/// settings.connect_volume_changed(|settings| println!("{}", settings.volume()));
+/// ```
+pub fn generate_settings<P: AsRef<Path>>(schema_files: &[P], target: &str) {
+    let mut schema_list = SchemaList::default();
+    for schema_file in schema_files {
+        let schema_file = schema_file.as_ref();
+        let text = fs::read_to_string(schema_file)
+            .unwrap_or_else(|err| panic!("Failed to read {}: {err}", schema_file.display()));
+        schema_list
+            .parse(&text)
+            .unwrap_or_else(|err| panic!("Failed to parse {}: {err}", schema_file.display()));
+        println!("cargo:rerun-if-changed={}", schema_file.display());
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join(target), schema_list.generate()).unwrap();
+}
+
+#[derive(Default)]
+struct SchemaList {
+    enums: Vec<EnumDef>,
+    schemas: Vec<Schema>,
+}
+
+struct EnumDef {
+    id: String,
+    is_flags: bool,
+    values: Vec<(String, i64)>,
+}
+
+struct Schema {
+    id: String,
+    path: Option<String>,
+    keys: Vec<Key>,
+}
+
+#[derive(Default)]
+struct Key {
+    name: String,
+    type_: Option<String>,
+    enum_id: Option<String>,
+    flags_id: Option<String>,
+    default: Option<String>,
+    summary: Option<String>,
+    description: Option<String>,
+    range: (Option<String>, Option<String>),
+}
+
+enum ValueType<'a> {
+    Basic(&'static str),
+    String,
+    StrV,
+    Enum(&'a EnumDef),
+    Flags,
+    Variant,
+}
+
+fn attribute(attributes: &[(GString, GString)], name: &str) -> Option<String> {
+    attributes
+        .iter()
+        .find(|(attribute, _)| attribute == name)
+        .map(|(_, value)| value.to_string())
+}
+
+impl SchemaList {
+    fn parse(&mut self, text: &str) -> Result<(), String> {
+        let mut stack = Vec::<GString>::new();
+        let mut text_buf = String::new();
+
+        for event in MarkupEvents::new(text, MarkupParseFlags::empty()) {
+            match event.map_err(|err| err.to_string())? {
+                MarkupEvent::StartElement { name, attributes } => {
+                    let parent = stack.last().map(GString::as_str);
+                    let attr = |name: &str| attribute(&attributes, name);
+                    match (parent, name.as_str()) {
+                        (Some("schemalist"), "schema") => {
+                            let id = attr("id").ok_or("<schema> without id")?;
+                            if attr("extends").is_some() {
+                                return Err(format!(
+                                    "Schema `{id}` uses `extends`, which is unsupported"
+                                ));
+                            }
+                            self.schemas.push(Schema {
+                                id,
+                                path: attr("path"),
+                                keys: Vec::new(),
+                            });
+                        }
+                        (Some("schema"), "child" | "override") => {
+                            return Err(format!(
+                                "<{name}> in schema `{}` is unsupported",
+                                self.schemas.last().unwrap().id
+                            ));
+                        }
+                        (Some("schema"), "key") => {
+                            let schema = self.schemas.last_mut().unwrap();
+                            schema.keys.push(Key {
+                                name: attr("name").ok_or("<key> without name")?,
+                                type_: attr("type"),
+                                enum_id: attr("enum"),
+                                flags_id: attr("flags"),
+                                ..Default::default()
+                            });
+                        }
+                        (Some("key"), "range") => {
+                            let key = self.last_key();
+                            key.range = (attr("min"), attr("max"));
+                        }
+                        (Some("schemalist"), "enum" | "flags") => self.enums.push(EnumDef {
+                            id: attr("id").ok_or("<enum> without id")?,
+                            is_flags: name.as_str() == "flags",
+                            values: Vec::new(),
+                        }),
+                        (Some("enum" | "flags"), "value") => {
+                            let nick = attr("nick").ok_or("<value> without nick")?;
+                            let value = attr("value")
+                                .and_then(|value| value.parse::<i64>().ok())
+                                .ok_or("<value> without valid value")?;
+                            let enum_def = self.enums.last_mut().unwrap();
+                            // Enum values are stored as `gint`, flags as `guint`.
+                            let fits = if enum_def.is_flags {
+                                u32::try_from(value).is_ok()
+                            } else {
+                                i32::try_from(value).is_ok()
+                            };
+                            if !fits {
+                                return Err(format!(
+                                    "Value {value} of `{nick}` is out of range for <{}>",
+                                    stack.last().unwrap()
+                                ));
+                            }
+                            enum_def.values.push((nick, value));
+                        }
+                        _ => (),
+                    }
+                    text_buf.clear();
+                    stack.push(name);
+                }
+                MarkupEvent::EndElement { name } => {
+                    stack.pop();
+                    let text = text_buf.trim().to_owned();
+                    match (stack.last().map(GString::as_str), name.as_str()) {
+                        (Some("key"), "default") => self.last_key().default = Some(text),
+                        (Some("key"), "summary") => self.last_key().summary = Some(text),
+                        (Some("key"), "description") => self.last_key().description = Some(text),
+                        _ => (),
+                    }
+                    text_buf.clear();
+                }
+                MarkupEvent::Text(text) => text_buf.push_str(&text),
+                MarkupEvent::Passthrough(_) => (),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn last_key(&mut self) -> &mut Key {
+        self.schemas.last_mut().unwrap().keys.last_mut().unwrap()
+    }
+
+    fn value_type(&self, key: &Key) -> ValueType<'_> {
+        if let Some(enum_id) = &key.enum_id {
+            return match self.enums.iter().find(|e| &e.id == enum_id && !e.is_flags) {
+                Some(enum_def) => ValueType::Enum(enum_def),
+                None => panic!("Enum `{enum_id}` of key `{}` not found", key.name),
+            };
+        }
+        if key.flags_id.is_some() {
+            return ValueType::Flags;
+        }
+
+        match key.type_.as_deref() {
+            Some("b") => ValueType::Basic("bool"),
+            Some("y") => ValueType::Basic("u8"),
+            Some("n") => ValueType::Basic("i16"),
+            Some("q") => ValueType::Basic("u16"),
+            Some("i") => ValueType::Basic("i32"),
+            Some("u") => ValueType::Basic("u32"),
+            Some("x") => ValueType::Basic("i64"),
+            Some("t") => ValueType::Basic("u64"),
+            Some("d") => ValueType::Basic("f64"),
+            Some("s") => ValueType::String,
+            Some("as") => ValueType::StrV,
+            _ => ValueType::Variant,
+        }
+    }
+
+    fn generate(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "// This file was generated by glib-build-tools").unwrap();
+        writeln!(out, "// DO NOT EDIT").unwrap();
+
+        for enum_def in self.enums.iter().filter(|e| !e.is_flags) {
+            generate_enum(&mut out, enum_def);
+        }
+        for schema in &self.schemas {
+            self.generate_schema(&mut out, schema);
+        }
+
+        out
+    }
+
+    fn generate_schema(&self, out: &mut String, schema: &Schema) {
+        let type_name = format!(
+            "{}Settings",
+            upper_camel_case(schema.id.rsplit('.').next().unwrap())
+        );
+        let id = &schema.id;
+
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "/// Typed access to the settings of the `{id}` schema."
+        )
+        .unwrap();
+        writeln!(out, "#[derive(Debug, Clone, PartialEq, Eq)]").unwrap();
+        writeln!(out, "pub struct {type_name}(gio::Settings);").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "#[allow(dead_code)]").unwrap();
+        writeln!(out, "impl {type_name} {{").unwrap();
+        writeln!(out, "    pub const SCHEMA_ID: &'static str = {id:?};").unwrap();
+
+        for key in &schema.keys {
+            self.generate_key_consts(out, key);
+        }
+
+        writeln!(out).unwrap();
+        if schema.path.is_some() {
+            writeln!(out, "    pub fn new() -> Self {{").unwrap();
+            writeln!(out, "        Self(gio::Settings::new(Self::SCHEMA_ID))").unwrap();
+        } else {
+            writeln!(out, "    pub fn with_path(path: &str) -> Self {{").unwrap();
+            writeln!(
+                out,
+                "        Self(gio::Settings::with_path(Self::SCHEMA_ID, path))"
+            )
+            .unwrap();
+        }
+        writeln!(out, "    }}").unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "    /// Creates the settings from the schema in `schema_source`, e.g. schemas compiled \
+             with `glib_build_tools::compile_schemas()`."
+        )
+        .unwrap();
+        writeln!(out, "    pub fn from_schema_source(").unwrap();
+        writeln!(out, "        schema_source: &gio::SettingsSchemaSource,").unwrap();
+        writeln!(out, "        backend: Option<&gio::SettingsBackend>,").unwrap();
+        writeln!(out, "        path: Option<&str>,").unwrap();
+        writeln!(out, "    ) -> Self {{").unwrap();
+        writeln!(out, "        let schema = schema_source").unwrap();
+        writeln!(out, "            .lookup(Self::SCHEMA_ID, true)").unwrap();
+        writeln!(
+            out,
+            "            .unwrap_or_else(|| panic!(\"Settings schema `{{}}` not found\", Self::SCHEMA_ID));"
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        Self(gio::Settings::new_full(&schema, backend, path))"
+        )
+        .unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out).unwrap();
+        writeln!(out, "    /// The underlying `gio::Settings`.").unwrap();
+        writeln!(out, "    pub fn settings(&self) -> &gio::Settings {{").unwrap();
+        writeln!(out, "        &self.0").unwrap();
+        writeln!(out, "    }}").unwrap();
+
+        for key in &schema.keys {
+            self.generate_key_methods(out, key);
+        }
+
+        writeln!(out, "}}").unwrap();
+        writeln!(out).unwrap();
+        if schema.path.is_some() {
+            writeln!(out, "impl Default for {type_name} {{").unwrap();
+            writeln!(out, "    fn default() -> Self {{").unwrap();
+            writeln!(out, "        Self::new()").unwrap();
+            writeln!(out, "    }}").unwrap();
+            writeln!(out, "}}").unwrap();
+            writeln!(out).unwrap();
+        }
+        writeln!(out, "impl AsRef<gio::Settings> for {type_name} {{").unwrap();
+        writeln!(out, "    fn as_ref(&self) -> &gio::Settings {{").unwrap();
+        writeln!(out, "        &self.0").unwrap();
+        writeln!(out, "    }}").unwrap();
+        writeln!(out, "}}").unwrap();
+    }
+
+    // Returns the Rust literal for the value `text` of `key`, if it can be expressed as one.
+    fn key_literal(&self, key: &Key, text: &str) -> Option<String> {
+        let value_type = self.value_type(key);
+        let signature = match &value_type {
+            ValueType::Basic(_) => key.type_.as_deref().unwrap(),
+            ValueType::String | ValueType::Enum(_) => "s",
+            ValueType::StrV => "as",
+            ValueType::Flags | ValueType::Variant => return None,
+        };
+
+        let value = Variant::parse(Some(VariantTy::new(signature).unwrap()), text)
+            .unwrap_or_else(|err| panic!("Invalid value `{text}` of key `{}`: {err}", key.name));
+        rust_literal(&value_type, &value)
+    }
+
+    // Returns the literals of the bounds of the range of `key` that have a `_MIN` or `_MAX`
+    // constant.
+    fn range_literals(&self, key: &Key) -> (Option<String>, Option<String>) {
+        if !matches!(self.value_type(key), ValueType::Basic(_)) {
+            return (None, None);
+        }
+
+        let literal =
+            |text: &Option<String>| text.as_deref().and_then(|text| self.key_literal(key, text));
+        (literal(&key.range.0), literal(&key.range.1))
+    }
+
+    fn generate_key_consts(&self, out: &mut String, key: &Key) {
+        let const_name = snake_case(&key.name).to_uppercase();
+        let rust_type = match self.value_type(key) {
+            ValueType::Basic(rust_type) => rust_type.to_string(),
+            ValueType::String => "&'static str".to_owned(),
+            ValueType::StrV => "&'static [&'static str]".to_owned(),
+            ValueType::Enum(enum_def) => enum_type_name(enum_def),
+            ValueType::Flags | ValueType::Variant => return,
+        };
+
+        if let Some(literal) = key
+            .default
+            .as_deref()
+            .and_then(|text| self.key_literal(key, text))
+        {
+            writeln!(
+                out,
+                "    pub const {const_name}_DEFAULT: {rust_type} = {literal};"
+            )
+            .unwrap();
+        }
+
+        let (min, max) = self.range_literals(key);
+        if let Some(min) = min {
+            writeln!(out, "    pub const {const_name}_MIN: {rust_type} = {min};").unwrap();
+        }
+        if let Some(max) = max {
+            writeln!(out, "    pub const {const_name}_MAX: {rust_type} = {max};").unwrap();
+        }
+    }
+
+    fn generate_key_methods(&self, out: &mut String, key: &Key) {
+        let name = &key.name;
+        let ident = snake_case(name);
+        let const_name = ident.to_uppercase();
+        let ident = raw_ident(&ident);
+        let value_type = self.value_type(key);
+
+        let (getter_type, getter, setter_type, setter) = match &value_type {
+            ValueType::Basic(rust_type) => (
+                rust_type.to_string(),
+                format!("gio::prelude::SettingsExtManual::get::<{rust_type}>(&self.0, {name:?})"),
+                rust_type.to_string(),
+                format!("gio::prelude::SettingsExtManual::set(&self.0, {name:?}, value)"),
+            ),
+            ValueType::String => (
+                "gio::glib::GString".to_owned(),
+                format!("gio::prelude::SettingsExt::string(&self.0, {name:?})"),
+                "&str".to_owned(),
+                format!("gio::prelude::SettingsExt::set_string(&self.0, {name:?}, value)"),
+            ),
+            ValueType::StrV => (
+                "gio::glib::StrV".to_owned(),
+                format!("gio::prelude::SettingsExtManual::strv(&self.0, {name:?})"),
+                "impl gio::glib::collections::strv::IntoStrV".to_owned(),
+                format!("gio::prelude::SettingsExtManual::set_strv(&self.0, {name:?}, value)"),
+            ),
+            ValueType::Enum(enum_def) => {
+                let enum_type = enum_type_name(enum_def);
+                (
+                    enum_type.clone(),
+                    format!(
+                        "{enum_type}::from_value(gio::prelude::SettingsExt::enum_(&self.0, {name:?})).unwrap()"
+                    ),
+                    enum_type,
+                    format!(
+                        "gio::prelude::SettingsExt::set_enum(&self.0, {name:?}, value.value())"
+                    ),
+                )
+            }
+            ValueType::Flags => (
+                "u32".to_owned(),
+                format!("gio::prelude::SettingsExt::flags(&self.0, {name:?})"),
+                "u32".to_owned(),
+                format!("gio::prelude::SettingsExt::set_flags(&self.0, {name:?}, value)"),
+            ),
+            ValueType::Variant => (
+                "gio::glib::Variant".to_owned(),
+                format!("gio::prelude::SettingsExt::value(&self.0, {name:?})"),
+                "&gio::glib::Variant".to_owned(),
+                format!("gio::prelude::SettingsExt::set_value(&self.0, {name:?}, value)"),
+            ),
+        };
+
+        writeln!(out).unwrap();
+        let mut has_docs = false;
+        for text in [&key.summary, &key.description].into_iter().flatten() {
+            if has_docs {
+                writeln!(out, "    ///").unwrap();
+            }
+            for line in text.lines() {
+                writeln!(out, "    /// {}", line.trim()).unwrap();
+            }
+            has_docs = true;
+        }
+        if !has_docs {
+            writeln!(out, "    /// The value of the `{name}` key.").unwrap();
+        }
+        writeln!(out, "    pub fn {ident}(&self) -> {getter_type} {{").unwrap();
+        writeln!(out, "        {getter}").unwrap();
+        writeln!(out, "    }}").unwrap();
+
+        writeln!(out).unwrap();
+        writeln!(out, "    /// Sets the value of the `{name}` key.").unwrap();
+        writeln!(
+            out,
+            "    pub fn set_{ident}(&self, value: {setter_type}) -> Result<(), gio::glib::BoolError> {{",
+            ident = snake_case(name),
+        )
+        .unwrap();
+        // Only check the bounds that have a constant, non-finite bounds don't restrict the
+        // value anyway. GSettings still rejects values outside of the range.
+        let bounds = match self.range_literals(key) {
+            (Some(_), Some(_)) => Some(format!(
+                "(Self::{const_name}_MIN..=Self::{const_name}_MAX).contains(&value)"
+            )),
+            (Some(_), None) => Some(format!("(Self::{const_name}_MIN..).contains(&value)")),
+            (None, Some(_)) => Some(format!("(..=Self::{const_name}_MAX).contains(&value)")),
+            (None, None) => None,
+        };
+        if let Some(bounds) = bounds {
+            writeln!(out, "        if !({bounds}) {{").unwrap();
+            writeln!(
+                out,
+                "            return Err(gio::glib::bool_error!(\"Value {{value}} is out of range for key `{name}`\"));"
+            )
+            .unwrap();
+            writeln!(out, "        }}").unwrap();
+        }
+        writeln!(out, "        {setter}").unwrap();
+        writeln!(out, "    }}").unwrap();
+
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "    /// Connects to changes of the value of the `{name}` key."
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "    pub fn connect_{ident}_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {{",
+            ident = snake_case(name),
+        )
+        .unwrap();
+        writeln!(
+            out,
+            "        gio::prelude::SettingsExt::connect_changed(&self.0, Some({name:?}), move |settings, _| {{"
+        )
+        .unwrap();
+        writeln!(out, "            f(&Self(settings.clone()))").unwrap();
+        writeln!(out, "        }})").unwrap();
+        writeln!(out, "    }}").unwrap();
+    }
+}
+
+fn generate_enum(out: &mut String, enum_def: &EnumDef) {
+    let type_name = enum_type_name(enum_def);
+    let variants = enum_def
+        .values
+        .iter()
+        .map(|(nick, value)| (upper_camel_case(nick), nick, value))
+        .collect::<Vec<_>>();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "/// The `{}` enum of the settings schemas.",
+        enum_def.id
+    )
+    .unwrap();
+    writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]").unwrap();
+    writeln!(out, "pub enum {type_name} {{").unwrap();
+    for (variant, _, _) in &variants {
+        writeln!(out, "    {variant},").unwrap();
+    }
+    writeln!(out, "}}").unwrap();
+    writeln!(out).unwrap();
+    writeln!(out, "#[allow(dead_code)]").unwrap();
+    writeln!(out, "impl {type_name} {{").unwrap();
+
+    writeln!(out, "    /// The nick of the value in the schema.").unwrap();
+    writeln!(out, "    pub fn nick(self) -> &'static str {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for (variant, nick, _) in &variants {
+        writeln!(out, "            Self::{variant} => {nick:?},").unwrap();
+    }
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// Returns the value with the nick `nick`, or `None` if there is none."
+    )
+    .unwrap();
+    writeln!(out, "    pub fn from_nick(nick: &str) -> Option<Self> {{").unwrap();
+    writeln!(out, "        match nick {{").unwrap();
+    for (variant, nick, _) in &variants {
+        writeln!(out, "            {nick:?} => Some(Self::{variant}),").unwrap();
+    }
+    writeln!(out, "            _ => None,").unwrap();
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+
+    writeln!(out).unwrap();
+    writeln!(out, "    /// The numeric value in the schema.").unwrap();
+    writeln!(out, "    pub fn value(self) -> i32 {{").unwrap();
+    writeln!(out, "        match self {{").unwrap();
+    for (variant, _, value) in &variants {
+        writeln!(out, "            Self::{variant} => {value},").unwrap();
+    }
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+
+    // Several nicks can have the same value, the first one wins.
+    let mut seen = HashSet::new();
+    writeln!(out).unwrap();
+    writeln!(
+        out,
+        "    /// Returns the first value with the numeric value `value`, or `None` if there is none."
+    )
+    .unwrap();
+    writeln!(out, "    pub fn from_value(value: i32) -> Option<Self> {{").unwrap();
+    writeln!(out, "        match value {{").unwrap();
+    for (variant, _, value) in &variants {
+        if seen.insert(*value) {
+            writeln!(out, "            {value} => Some(Self::{variant}),").unwrap();
+        }
+    }
+    writeln!(out, "            _ => None,").unwrap();
+    writeln!(out, "        }}").unwrap();
+    writeln!(out, "    }}").unwrap();
+    writeln!(out, "}}").unwrap();
+}
+
+fn enum_type_name(enum_def: &EnumDef) -> String {
+    upper_camel_case(enum_def.id.rsplit('.').next().unwrap())
+}
+
+fn rust_literal(value_type: &ValueType, value: &Variant) -> Option<String> {
+    match value_type {
+        ValueType::Basic("f64") => {
+            let value = value.get::<f64>()?;
+            value.is_finite().then(|| format!("{value:?}"))
+        }
+        ValueType::Basic(_) => Some(value.print(false).to_string()),
+        ValueType::String => Some(format!("{:?}", value.str()?)),
+        ValueType::StrV => {
+            let values = value.get::<Vec<String>>()?;
+            Some(format!("&{values:?}"))
+        }
+        ValueType::Enum(enum_def) => {
+            let nick = value.str()?;
+            enum_def
+                .values
+                .iter()
+                .any(|(value, _)| value.as_str() == nick)
+                .then(|| format!("{}::{}", enum_type_name(enum_def), upper_camel_case(nick)))
+        }
+        ValueType::Flags | ValueType::Variant => None,
+    }
+}
+
+fn upper_camel_case(name: &str) -> String {
+    let mut out = String::new();
+    for part in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, 'V');
+    }
+    out
+}
+
+fn snake_case(name: &str) -> String {
+    let mut out = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+fn raw_ident(ident: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do",
+        "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in",
+        "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+        "return", "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe",
+        "unsized", "use", "virtual", "where", "while", "yield",
+    ];
+
+    if KEYWORDS.contains(&ident) {
+        format!("r#{ident}")
+    } else {
+        ident.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = include_str!("../tests/settings/org.example.Test.gschema.xml");
+
+    fn schema_list() -> SchemaList {
+        let mut schema_list = SchemaList::default();
+        schema_list.parse(SCHEMA).unwrap();
+        schema_list
+    }
+
+    #[test]
+    fn parse() {
+        let schema_list = schema_list();
+
+        assert_eq!(schema_list.enums.len(), 1);
+        let enum_def = &schema_list.enums[0];
+        assert_eq!(enum_def.id, "org.example.Test.Mode");
+        assert!(!enum_def.is_flags);
+        assert_eq!(
+            enum_def.values,
+            [("fast".to_owned(), 0), ("slow-motion".to_owned(), 1)]
+        );
+
+        assert_eq!(schema_list.schemas.len(), 1);
+        let schema = &schema_list.schemas[0];
+        assert_eq!(schema.id, "org.example.Test");
+        assert_eq!(schema.path.as_deref(), Some("/org/example/Test/"));
+        assert_eq!(schema.keys.len(), 6);
+
+        let volume = &schema.keys[0];
+        assert_eq!(volume.name, "volume");
+        assert_eq!(volume.type_.as_deref(), Some("i"));
+        assert_eq!(volume.default.as_deref(), Some("5"));
+        assert_eq!(volume.summary.as_deref(), Some("The volume"));
+        assert_eq!(volume.description.as_deref(), Some("How loud it is."));
+        assert_eq!(volume.range, (Some("0".to_owned()), Some("11".to_owned())));
+
+        let mode = &schema.keys[4];
+        assert_eq!(mode.enum_id.as_deref(), Some("org.example.Test.Mode"));
+        assert!(matches!(
+            schema_list.value_type(mode),
+            ValueType::Enum(enum_def) if enum_def.id == "org.example.Test.Mode"
+        ));
+    }
+
+    #[test]
+    fn parse_error() {
+        let mut schema_list = SchemaList::default();
+        let err = schema_list
+            .parse("<schemalist><schema path=\"/a/\"/></schemalist>")
+            .unwrap_err();
+        assert!(err.contains("without id"));
+
+        let mut schema_list = SchemaList::default();
+        assert!(schema_list.parse("<schemalist><schema").is_err());
+
+        let mut schema_list = SchemaList::default();
+        let err = schema_list
+            .parse("<schemalist><enum id=\"a.E\"><value nick=\"big\" value=\"2147483648\"/></enum></schemalist>")
+            .unwrap_err();
+        assert!(err.contains("out of range"));
+    }
+
+    #[test]
+    fn parse_unsupported() {
+        for schema in [
+            "<schemalist><schema id=\"a.B\" extends=\"a.A\"/></schemalist>",
+            "<schemalist><schema id=\"a.B\"><child name=\"c\" schema=\"a.C\"/></schema></schemalist>",
+            "<schemalist><schema id=\"a.B\"><override name=\"k\">1</override></schema></schemalist>",
+        ] {
+            let err = SchemaList::default().parse(schema).unwrap_err();
+            assert!(err.contains("unsupported"), "{err}");
+        }
+    }
+
+    #[test]
+    fn non_finite_range() {
+        let out = schema_list().generate();
+
+        assert!(out.contains("pub const RATIO_MIN: f64 = 0.0;"));
+        assert!(!out.contains("RATIO_MAX"));
+        assert!(out.contains("if !((Self::RATIO_MIN..).contains(&value)) {"));
+        assert!(out.contains("if !((Self::VOLUME_MIN..=Self::VOLUME_MAX).contains(&value)) {"));
+    }
+
+    #[test]
+    fn generate() {
+        // The generated code is compiled and used in `tests/settings.rs`.
+        assert_eq!(
+            schema_list().generate(),
+            include_str!("../tests/settings/generated.rs")
+        );
+    }
+}
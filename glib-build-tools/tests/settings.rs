@@ -0,0 +1,66 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::env;
+
+use gio::prelude::*;
+
+// Generated from `settings/org.example.Test.gschema.xml`, kept up to date by the unit tests of
+// the generator.
+include!("settings/generated.rs");
+
+fn settings() -> TestSettings {
+    // `compile_schemas()` is meant for build scripts and writes to `OUT_DIR`.
+    let out_dir = env::temp_dir().join(format!("glib-build-tools-test-{}", std::process::id()));
+    // SAFETY: This is the only test of this binary and doesn't run concurrently with code
+    // reading the environment.
+    unsafe { env::set_var("OUT_DIR", &out_dir) };
+    let target_dir = glib_build_tools::compile_schemas(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/settings"),
+        "schemas",
+    );
+    assert_eq!(target_dir, out_dir.join("schemas"));
+    assert!(target_dir.join("gschemas.compiled").exists());
+
+    let source = gio::SettingsSchemaSource::from_directory(&target_dir, None, false).unwrap();
+    std::fs::remove_dir_all(&out_dir).unwrap();
+    let backend = gio::memory_settings_backend_new();
+    TestSettings::from_schema_source(&source, Some(&backend), None)
+}
+
+#[test]
+fn generated_settings() {
+    let settings = settings();
+
+    assert_eq!(settings.volume(), TestSettings::VOLUME_DEFAULT);
+    settings.set_volume(TestSettings::VOLUME_MAX).unwrap();
+    assert_eq!(settings.volume(), 11);
+    assert!(settings.set_volume(12).is_err());
+    assert_eq!(settings.volume(), 11);
+
+    assert_eq!(settings.ratio(), TestSettings::RATIO_DEFAULT);
+    settings.set_ratio(f64::INFINITY).unwrap();
+    assert_eq!(settings.ratio(), f64::INFINITY);
+    assert!(settings.set_ratio(-1.0).is_err());
+
+    assert_eq!(settings.name(), TestSettings::NAME_DEFAULT);
+    assert_eq!(settings.tags().as_slice(), TestSettings::TAGS_DEFAULT);
+    settings.set_tags(["c"]).unwrap();
+    assert_eq!(settings.tags().as_slice(), ["c"]);
+
+    assert_eq!(settings.mode(), TestSettings::MODE_DEFAULT);
+    let changed = std::rc::Rc::new(std::cell::Cell::new(false));
+    settings.connect_mode_changed({
+        let changed = changed.clone();
+        move |settings| {
+            assert_eq!(settings.mode(), Mode::Fast);
+            changed.set(true);
+        }
+    });
+    settings.set_mode(Mode::Fast).unwrap();
+    assert!(changed.get());
+    assert_eq!(Mode::from_nick("slow-motion"), Some(Mode::SlowMotion));
+
+    assert_eq!(settings.r#type(), (1i32, 2i32).to_variant());
+    settings.set_type(&(3i32, 4i32).to_variant()).unwrap();
+    assert_eq!(settings.r#type().get::<(i32, i32)>(), Some((3, 4)));
+}
@@ -0,0 +1,206 @@
+// This file was generated by glib-build-tools
+// DO NOT EDIT
+
+/// The `org.example.Test.Mode` enum of the settings schemas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mode {
+    Fast,
+    SlowMotion,
+}
+
+#[allow(dead_code)]
+impl Mode {
+    /// The nick of the value in the schema.
+    pub fn nick(self) -> &'static str {
+        match self {
+            Self::Fast => "fast",
+            Self::SlowMotion => "slow-motion",
+        }
+    }
+
+    /// Returns the value with the nick `nick`, or `None` if there is none.
+    pub fn from_nick(nick: &str) -> Option<Self> {
+        match nick {
+            "fast" => Some(Self::Fast),
+            "slow-motion" => Some(Self::SlowMotion),
+            _ => None,
+        }
+    }
+
+    /// The numeric value in the schema.
+    pub fn value(self) -> i32 {
+        match self {
+            Self::Fast => 0,
+            Self::SlowMotion => 1,
+        }
+    }
+
+    /// Returns the first value with the numeric value `value`, or `None` if there is none.
+    pub fn from_value(value: i32) -> Option<Self> {
+        match value {
+            0 => Some(Self::Fast),
+            1 => Some(Self::SlowMotion),
+            _ => None,
+        }
+    }
+}
+
+/// Typed access to the settings of the `org.example.Test` schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestSettings(gio::Settings);
+
+#[allow(dead_code)]
+impl TestSettings {
+    pub const SCHEMA_ID: &'static str = "org.example.Test";
+    pub const VOLUME_DEFAULT: i32 = 5;
+    pub const VOLUME_MIN: i32 = 0;
+    pub const VOLUME_MAX: i32 = 11;
+    pub const RATIO_DEFAULT: f64 = 1.5;
+    pub const RATIO_MIN: f64 = 0.0;
+    pub const NAME_DEFAULT: &'static str = "foo";
+    pub const TAGS_DEFAULT: &'static [&'static str] = &["a", "b"];
+    pub const MODE_DEFAULT: Mode = Mode::SlowMotion;
+
+    pub fn new() -> Self {
+        Self(gio::Settings::new(Self::SCHEMA_ID))
+    }
+
+    /// Creates the settings from the schema in `schema_source`, e.g. schemas compiled with `glib_build_tools::compile_schemas()`.
+    pub fn from_schema_source(
+        schema_source: &gio::SettingsSchemaSource,
+        backend: Option<&gio::SettingsBackend>,
+        path: Option<&str>,
+    ) -> Self {
+        let schema = schema_source
+            .lookup(Self::SCHEMA_ID, true)
+            .unwrap_or_else(|| panic!("Settings schema `{}` not found", Self::SCHEMA_ID));
+        Self(gio::Settings::new_full(&schema, backend, path))
+    }
+
+    /// The underlying `gio::Settings`.
+    pub fn settings(&self) -> &gio::Settings {
+        &self.0
+    }
+
+    /// The volume
+    ///
+    /// How loud it is.
+    pub fn volume(&self) -> i32 {
+        gio::prelude::SettingsExtManual::get::<i32>(&self.0, "volume")
+    }
+
+    /// Sets the value of the `volume` key.
+    pub fn set_volume(&self, value: i32) -> Result<(), gio::glib::BoolError> {
+        if !((Self::VOLUME_MIN..=Self::VOLUME_MAX).contains(&value)) {
+            return Err(gio::glib::bool_error!("Value {value} is out of range for key `volume`"));
+        }
+        gio::prelude::SettingsExtManual::set(&self.0, "volume", value)
+    }
+
+    /// Connects to changes of the value of the `volume` key.
+    pub fn connect_volume_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("volume"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    /// The value of the `ratio` key.
+    pub fn ratio(&self) -> f64 {
+        gio::prelude::SettingsExtManual::get::<f64>(&self.0, "ratio")
+    }
+
+    /// Sets the value of the `ratio` key.
+    pub fn set_ratio(&self, value: f64) -> Result<(), gio::glib::BoolError> {
+        if !((Self::RATIO_MIN..).contains(&value)) {
+            return Err(gio::glib::bool_error!("Value {value} is out of range for key `ratio`"));
+        }
+        gio::prelude::SettingsExtManual::set(&self.0, "ratio", value)
+    }
+
+    /// Connects to changes of the value of the `ratio` key.
+    pub fn connect_ratio_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("ratio"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    /// The value of the `name` key.
+    pub fn name(&self) -> gio::glib::GString {
+        gio::prelude::SettingsExt::string(&self.0, "name")
+    }
+
+    /// Sets the value of the `name` key.
+    pub fn set_name(&self, value: &str) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExt::set_string(&self.0, "name", value)
+    }
+
+    /// Connects to changes of the value of the `name` key.
+    pub fn connect_name_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("name"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    /// The value of the `tags` key.
+    pub fn tags(&self) -> gio::glib::StrV {
+        gio::prelude::SettingsExtManual::strv(&self.0, "tags")
+    }
+
+    /// Sets the value of the `tags` key.
+    pub fn set_tags(&self, value: impl gio::glib::collections::strv::IntoStrV) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExtManual::set_strv(&self.0, "tags", value)
+    }
+
+    /// Connects to changes of the value of the `tags` key.
+    pub fn connect_tags_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("tags"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    /// The value of the `mode` key.
+    pub fn mode(&self) -> Mode {
+        Mode::from_value(gio::prelude::SettingsExt::enum_(&self.0, "mode")).unwrap()
+    }
+
+    /// Sets the value of the `mode` key.
+    pub fn set_mode(&self, value: Mode) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExt::set_enum(&self.0, "mode", value.value())
+    }
+
+    /// Connects to changes of the value of the `mode` key.
+    pub fn connect_mode_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("mode"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+
+    /// The value of the `type` key.
+    pub fn r#type(&self) -> gio::glib::Variant {
+        gio::prelude::SettingsExt::value(&self.0, "type")
+    }
+
+    /// Sets the value of the `type` key.
+    pub fn set_type(&self, value: &gio::glib::Variant) -> Result<(), gio::glib::BoolError> {
+        gio::prelude::SettingsExt::set_value(&self.0, "type", value)
+    }
+
+    /// Connects to changes of the value of the `type` key.
+    pub fn connect_type_changed<F: Fn(&Self) + 'static>(&self, f: F) -> gio::glib::SignalHandlerId {
+        gio::prelude::SettingsExt::connect_changed(&self.0, Some("type"), move |settings, _| {
+            f(&Self(settings.clone()))
+        })
+    }
+}
+
+impl Default for TestSettings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AsRef<gio::Settings> for TestSettings {
+    fn as_ref(&self) -> &gio::Settings {
+        &self.0
+    }
+}
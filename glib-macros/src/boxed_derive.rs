@@ -3,7 +3,7 @@
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
-use crate::utils::{NestedMetaItem, crate_ident_new, parse_nested_meta_items};
+use crate::utils::{NestedMetaItem, crate_ident_new, parse_optional_nested_meta_items};
 
 fn gen_option_to_ptr() -> TokenStream {
     quote! {
@@ -93,27 +93,17 @@ fn gen_impl_to_value_optional(name: &Ident, crate_ident: &TokenStream) -> TokenS
 pub fn impl_boxed(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
     let name = &input.ident;
 
-    let mut gtype_name = NestedMetaItem::<syn::LitStr>::new("name")
-        .required()
-        .value_required();
+    let mut gtype_name = NestedMetaItem::<syn::LitStr>::new("name").value_required();
     let mut nullable = NestedMetaItem::<syn::LitBool>::new("nullable").value_optional();
     let mut allow_name_conflict =
         NestedMetaItem::<syn::LitBool>::new("allow_name_conflict").value_optional();
 
-    let found = parse_nested_meta_items(
+    parse_optional_nested_meta_items(
         &input.attrs,
         "boxed_type",
         &mut [&mut gtype_name, &mut nullable, &mut allow_name_conflict],
     )?;
 
-    if found.is_none() {
-        return Err(syn::Error::new_spanned(
-            input,
-            "#[derive(glib::Boxed)] requires #[boxed_type(name = \"BoxedTypeName\")]",
-        ));
-    }
-
-    let gtype_name = gtype_name.value.unwrap();
     let nullable = nullable.found || nullable.value.map(|b| b.value()).unwrap_or(false);
     let allow_name_conflict = allow_name_conflict.found
         || allow_name_conflict
@@ -123,6 +113,33 @@ pub fn impl_boxed(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
 
     let crate_ident = crate_ident_new();
 
+    if gtype_name.value.is_none() && allow_name_conflict {
+        return Err(syn::Error::new_spanned(
+            input,
+            "#[boxed_type(allow_name_conflict)] requires a type name",
+        ));
+    }
+
+    let (impl_boxed_type, register_type) = match gtype_name.value {
+        Some(gtype_name) => (
+            quote! {
+                impl #crate_ident::subclass::boxed::BoxedType for #name {
+                    const NAME: &'static ::core::primitive::str = #gtype_name;
+                    const ALLOW_NAME_CONFLICT: bool = #allow_name_conflict;
+                }
+            },
+            quote! {
+                #crate_ident::subclass::register_boxed_type::<#name>()
+            },
+        ),
+        None => (
+            quote! {},
+            quote! {
+                #crate_ident::subclass::register_boxed_type_on_demand::<#name>()
+            },
+        ),
+    };
+
     let impl_from_value = if !nullable {
         gen_impl_from_value(name, &crate_ident)
     } else {
@@ -135,17 +152,14 @@ pub fn impl_boxed(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
     };
 
     Ok(quote! {
-        impl #crate_ident::subclass::boxed::BoxedType for #name {
-            const NAME: &'static ::core::primitive::str = #gtype_name;
-            const ALLOW_NAME_CONFLICT: bool = #allow_name_conflict;
-        }
+        #impl_boxed_type
 
         impl #crate_ident::prelude::StaticType for #name {
             #[inline]
             fn static_type() -> #crate_ident::Type {
                 static TYPE: ::std::sync::OnceLock<#crate_ident::Type> = ::std::sync::OnceLock::new();
                 *TYPE.get_or_init(|| {
-                    #crate_ident::subclass::register_boxed_type::<#name>()
+                    #register_type
                 })
             }
         }
@@ -1,10 +1,12 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Delimiter, Ident, Span, TokenStream, TokenTree};
 use quote::{ToTokens, quote};
 use syn::{
-    Attribute, Expr, ExprAsync, ExprClosure, Token,
+    Attribute, Expr, ExprAsync, ExprClosure, Pat, Token,
+    ext::IdentExt,
     parse::{Parse, ParseStream},
+    punctuated::Punctuated,
     spanned::Spanned,
 };
 
@@ -309,6 +311,168 @@ impl Capture {
     }
 }
 
+// Parses a `#[connected_to(ident)]` attribute, which names the object the closure is going to be
+// connected to.
+pub(crate) fn maybe_parse_connected_to(attr: &Attribute) -> syn::Result<Option<Ident>> {
+    if !attr.path().is_ident("connected_to") {
+        return Ok(None);
+    }
+
+    let list = attr.meta.require_list()?;
+    let ident = list.parse_args_with(Ident::parse_any).map_err(|_| {
+        syn::Error::new_spanned(
+            attr,
+            "`connected_to` attribute requires the identifier of the object the closure is connected to",
+        )
+    })?;
+
+    Ok(Some(ident))
+}
+
+// Checks that the closure does not keep a strong reference to the object it is connected to.
+//
+// Returns the first place where the object is used in the closure body without being captured
+// via a weak or watch capture, which would move a strong reference into the closure. Usages in
+// format strings of macro invocations, e.g. `println!("{obj}")`, are detected as well.
+//
+// `weak_captures` lists the attributes the calling macro accepts for capturing the object
+// without a strong reference, for the error message.
+pub(crate) fn audit_connected_to(
+    connected_to: &Ident,
+    weak_captures: &str,
+    captures: &[Capture],
+    inputs: Option<&Punctuated<Pat, Token![,]>>,
+    body: TokenStream,
+) -> syn::Result<Option<Span>> {
+    let captures_object = |capture: &Capture| matches!(capture.name, Expr::Path(ref p) if p.path.is_ident(connected_to));
+
+    if let Some(capture) = captures.iter().find(|c| {
+        captures_object(c) && matches!(c.kind, CaptureKind::Strong | CaptureKind::ToOwned)
+    }) {
+        return Err(syn::Error::new_spanned(
+            &capture.name,
+            format!(
+                "strong capture of `{connected_to}` creates a reference cycle as the closure is connected to it. Use {weak_captures} instead",
+            ),
+        ));
+    }
+
+    // Either captured weakly, or the name refers to a renamed capture inside the closure.
+    if captures.iter().any(|c| {
+        (captures_object(c) && c.alias.is_none()) || c.alias.as_ref() == Some(connected_to)
+    }) {
+        return Ok(None);
+    }
+
+    if inputs.is_some_and(|inputs| {
+        inputs
+            .iter()
+            .any(|pat| pat_binds(pat.to_token_stream(), connected_to))
+    }) {
+        return Ok(None);
+    }
+
+    Ok(find_usage(body, connected_to))
+}
+
+fn pat_binds(pat: TokenStream, ident: &Ident) -> bool {
+    pat.into_iter().any(|tt| match tt {
+        TokenTree::Ident(ref i) => i == ident,
+        TokenTree::Group(g) => pat_binds(g.stream(), ident),
+        _ => false,
+    })
+}
+
+fn find_usage(tokens: TokenStream, ident: &Ident) -> Option<Span> {
+    find_usage_inner(tokens, ident, false)
+}
+
+fn find_usage_inner(tokens: TokenStream, ident: &Ident, in_macro: bool) -> Option<Span> {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut after_path_separator = false;
+    let mut after_bang = false;
+    let mut after_if_or_while = false;
+    // Set once a `let` binding shadows the variable: for plain `let` statements the rest of the
+    // block after the `;` doesn't refer to it anymore, for `if let` and `while let` the block
+    // following the condition doesn't.
+    let mut shadowed_after_semicolon = false;
+    let mut shadowed_in_next_block = false;
+    while let Some(tt) = tokens.next() {
+        match tt {
+            // The pattern of a `let` binding is never a usage, but may shadow the variable.
+            TokenTree::Ident(ref i) if i == "let" => {
+                let mut pat = TokenStream::new();
+                while let Some(tt) = tokens.next_if(
+                    |tt| !matches!(tt, TokenTree::Punct(p) if p.as_char() == '=' || p.as_char() == ';'),
+                ) {
+                    pat.extend([tt]);
+                }
+                if pat_binds(pat, ident) {
+                    if after_if_or_while {
+                        shadowed_in_next_block = true;
+                    } else {
+                        shadowed_after_semicolon = true;
+                    }
+                }
+            }
+            // Field accesses, method calls and path segments with the same name are not usages
+            // of the variable.
+            TokenTree::Ident(ref i) if i == ident && !after_path_separator => {
+                return Some(i.span());
+            }
+            TokenTree::Group(ref g)
+                if shadowed_in_next_block && g.delimiter() == Delimiter::Brace =>
+            {
+                shadowed_in_next_block = false;
+            }
+            TokenTree::Group(ref g) => {
+                if let Some(span) = find_usage_inner(g.stream(), ident, after_bang) {
+                    return Some(span);
+                }
+            }
+            // Implicit captures of format strings, like `format!("{obj}")`.
+            TokenTree::Literal(ref l)
+                if in_macro
+                    && syn::parse2::<syn::LitStr>(l.to_token_stream())
+                        .is_ok_and(|s| format_string_uses(&s.value(), ident)) =>
+            {
+                return Some(l.span());
+            }
+            TokenTree::Punct(ref p) if p.as_char() == ';' && shadowed_after_semicolon => {
+                return None;
+            }
+            _ => (),
+        }
+        after_path_separator =
+            matches!(tt, TokenTree::Punct(ref p) if p.as_char() == '.' || p.as_char() == ':');
+        after_bang = matches!(tt, TokenTree::Punct(ref p) if p.as_char() == '!');
+        after_if_or_while = matches!(tt, TokenTree::Ident(ref i) if i == "if" || i == "while");
+    }
+
+    None
+}
+
+// Checks if the format string has an argument like `{ident}` or `{ident:?}`.
+fn format_string_uses(format: &str, ident: &Ident) -> bool {
+    let ident = ident.to_string();
+    let mut rest = format;
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        if let Some(escaped) = rest.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+
+        let end = rest.find(['}', ':']).unwrap_or(rest.len());
+        if rest[..end].trim() == ident {
+            return true;
+        }
+        rest = &rest[end..];
+    }
+
+    false
+}
+
 #[derive(Clone)]
 enum ClosureOrAsync {
     Closure(ExprClosure),
@@ -373,6 +537,7 @@ impl Parse for Clone {
 
         let mut captures: Vec<Capture> = vec![];
         let mut upgrade_behaviour: Option<(UpgradeBehaviour, Span)> = None;
+        let mut connected_to: Option<Ident> = None;
 
         loop {
             // There must either be one or no attributes here. Multiple attributes are not
@@ -384,11 +549,31 @@ impl Parse for Clone {
             //
             // If this is not an attribute then it is a closure, async closure or async block which
             // is handled outside the loop
-            let attrs = input.call(Attribute::parse_outer)?;
+            let mut attrs = input.call(Attribute::parse_outer)?;
             if attrs.is_empty() {
                 break;
             };
 
+            // `connected_to` is not followed by anything and can directly precede the next
+            // attribute.
+            while !attrs.is_empty() {
+                let Some(ident) = maybe_parse_connected_to(&attrs[0])? else {
+                    break;
+                };
+                if connected_to.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &attrs[0],
+                        "multiple `connected_to` attributes are not supported",
+                    ));
+                }
+
+                connected_to = Some(ident);
+                attrs.remove(0);
+            }
+            if attrs.is_empty() {
+                continue;
+            }
+
             match Capture::maybe_parse(&attrs, input)? {
                 Some(capture) => {
                     if capture.kind == CaptureKind::Watch {
@@ -417,13 +602,13 @@ impl Parse for Clone {
                             return Err(syn::Error::new_spanned(
                                 &attrs[0],
                                 format!(
-                                    "unsupported attribute `{ident}`: only `strong`, `weak`, `weak_allow_none`, `to_owned`, `upgrade_or`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported",
+                                    "unsupported attribute `{ident}`: only `strong`, `weak`, `weak_allow_none`, `to_owned`, `connected_to`, `upgrade_or`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported",
                                 ),
                             ));
                         } else {
                             return Err(syn::Error::new_spanned(
                                 &attrs[0],
-                                "unsupported attribute: only `strong`, `weak`, `weak_allow_none`, `to_owned`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported",
+                                "unsupported attribute: only `strong`, `weak`, `weak_allow_none`, `to_owned`, `connected_to`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported",
                             ));
                         }
                     }
@@ -445,6 +630,34 @@ impl Parse for Clone {
         // Following is a closure or async block
         let body = input.parse::<ClosureOrAsync>()?;
 
+        if let Some(ref connected_to) = connected_to {
+            let usage = match body {
+                ClosureOrAsync::Closure(ref c) => audit_connected_to(
+                    connected_to,
+                    "`#[weak]` or `#[weak_allow_none]`",
+                    &captures,
+                    Some(&c.inputs),
+                    c.body.to_token_stream(),
+                )?,
+                ClosureOrAsync::Async(ref a) => audit_connected_to(
+                    connected_to,
+                    "`#[weak]` or `#[weak_allow_none]`",
+                    &captures,
+                    None,
+                    a.block.to_token_stream(),
+                )?,
+            };
+
+            if let Some(span) = usage {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "`{connected_to}` is captured strongly, which creates a reference cycle as the closure is connected to it. Capture it with `#[weak] {connected_to}` instead",
+                    ),
+                ));
+            }
+        }
+
         // Trailing comma, if any
         if input.peek(Token![,]) {
             input.parse::<Token![,]>()?;
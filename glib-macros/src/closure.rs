@@ -9,7 +9,7 @@ use syn::{
 };
 
 use crate::{
-    clone::{Capture, CaptureKind, UpgradeBehaviour},
+    clone::{Capture, CaptureKind, UpgradeBehaviour, audit_connected_to, maybe_parse_connected_to},
     utils::crate_ident_new,
 };
 
@@ -29,6 +29,7 @@ impl Parse for Closure {
 
         let mut captures: Vec<Capture> = vec![];
         let mut upgrade_behaviour: Option<(UpgradeBehaviour, Span)> = None;
+        let mut connected_to: Option<Ident> = None;
 
         loop {
             // There must either be one or no attributes here. Multiple attributes are not
@@ -40,11 +41,31 @@ impl Parse for Closure {
             //
             // If this is not an attribute then it is a closure, async closure or async block which
             // is handled outside the loop
-            let attrs = input.call(Attribute::parse_outer)?;
+            let mut attrs = input.call(Attribute::parse_outer)?;
             if attrs.is_empty() {
                 break;
             };
 
+            // `connected_to` is not followed by anything and can directly precede the next
+            // attribute.
+            while !attrs.is_empty() {
+                let Some(ident) = maybe_parse_connected_to(&attrs[0])? else {
+                    break;
+                };
+                if connected_to.is_some() {
+                    return Err(syn::Error::new_spanned(
+                        &attrs[0],
+                        "multiple `connected_to` attributes are not supported",
+                    ));
+                }
+
+                connected_to = Some(ident);
+                attrs.remove(0);
+            }
+            if attrs.is_empty() {
+                continue;
+            }
+
             match Capture::maybe_parse(&attrs, input)? {
                 Some(capture) => {
                     if capture.kind == CaptureKind::Watch
@@ -75,13 +96,13 @@ impl Parse for Closure {
                             return Err(syn::Error::new_spanned(
                                 &attrs[0],
                                 format!(
-                                    "unsupported attribute `{ident}`: only `watch`, `strong`, `weak`, `weak_allow_none`, `to_owned`, `upgrade_or`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported",
+                                    "unsupported attribute `{ident}`: only `watch`, `strong`, `weak`, `weak_allow_none`, `to_owned`, `connected_to`, `upgrade_or`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported",
                                 ),
                             ));
                         } else {
                             return Err(syn::Error::new_spanned(
                                 &attrs[0],
-                                "unsupported attribute: only `strong`, `weak`, `weak_allow_none`, `to_owned`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported",
+                                "unsupported attribute: only `strong`, `weak`, `weak_allow_none`, `to_owned`, `connected_to`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported",
                             ));
                         }
                     }
@@ -115,6 +136,33 @@ impl Parse for Closure {
         }
         closure.capture = None;
 
+        // Instead of moving a strong reference to the object the closure is connected to into
+        // the closure, watch it. This invalidates the closure once the object is disposed.
+        if let Some(ref connected_to) = connected_to
+            && let Some(span) = audit_connected_to(
+                connected_to,
+                "`#[weak]`, `#[weak_allow_none]` or `#[watch]`",
+                &captures,
+                Some(&closure.inputs),
+                closure.body.to_token_stream(),
+            )?
+        {
+            if captures.iter().any(|c| c.kind == CaptureKind::Watch) {
+                return Err(syn::Error::new(
+                    span,
+                    format!(
+                        "`{connected_to}` is captured strongly, which creates a reference cycle as the closure is connected to it. Capture it with `#[weak] {connected_to}` or `#[watch] {connected_to}` instead",
+                    ),
+                ));
+            }
+
+            captures.push(Capture {
+                name: syn::parse_quote!(#connected_to),
+                alias: None,
+                kind: CaptureKind::Watch,
+            });
+        }
+
         let args = closure
             .inputs
             .iter()
@@ -1,8 +1,8 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::Data;
+use quote::{quote, quote_spanned};
+use syn::{Data, Variant, punctuated::Punctuated, spanned::Spanned, token::Comma};
 
 use crate::utils::{NestedMetaItem, crate_ident_new, gen_enum_from_glib, parse_nested_meta_items};
 
@@ -22,7 +22,12 @@ pub fn impl_error_domain(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
     let mut domain_name = NestedMetaItem::<syn::LitStr>::new("name")
         .required()
         .value_required();
-    let found = parse_nested_meta_items(&input.attrs, "error_domain", &mut [&mut domain_name])?;
+    let mut dbus_prefix = NestedMetaItem::<syn::LitStr>::new("dbus_prefix").value_required();
+    let found = parse_nested_meta_items(
+        &input.attrs,
+        "error_domain",
+        &mut [&mut domain_name, &mut dbus_prefix],
+    )?;
 
     if found.is_none() {
         return Err(syn::Error::new_spanned(
@@ -34,6 +39,7 @@ pub fn impl_error_domain(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
     let crate_ident = crate_ident_new();
 
     let from_glib = gen_enum_from_glib(name, enum_variants);
+    let dbus_error_names = gen_dbus_error_names(name, enum_variants, dbus_prefix.value)?;
 
     Ok(quote! {
         impl #crate_ident::error::ErrorDomain for #name {
@@ -59,6 +65,44 @@ pub fn impl_error_domain(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
             {
                 #from_glib
             }
+
+            #dbus_error_names
+        }
+    })
+}
+
+// Generates `ErrorDomain::dbus_error_names()` from the `dbus_prefix` of the domain and the
+// `dbus_name` of the variants, if any of them is given.
+fn gen_dbus_error_names(
+    enum_name: &syn::Ident,
+    enum_variants: &Punctuated<Variant, Comma>,
+    dbus_prefix: Option<syn::LitStr>,
+) -> syn::Result<TokenStream> {
+    let mut entries = Vec::new();
+    for v in enum_variants {
+        let mut dbus_name = NestedMetaItem::<syn::LitStr>::new("dbus_name").value_required();
+        parse_nested_meta_items(&v.attrs, "error_domain", &mut [&mut dbus_name])?;
+
+        let variant = &v.ident;
+        let dbus_name = match (dbus_name.value, &dbus_prefix) {
+            (Some(dbus_name), _) => dbus_name.value(),
+            (None, Some(dbus_prefix)) => format!("{}.{variant}", dbus_prefix.value()),
+            (None, None) => continue,
+        };
+        entries.push(quote_spanned! { v.span() =>
+            (#enum_name::#variant as i32, #dbus_name)
+        });
+    }
+
+    if entries.is_empty() {
+        return Ok(TokenStream::new());
+    }
+
+    Ok(quote! {
+        #[inline]
+        fn dbus_error_names() -> &'static [(i32, &'static str)] {
+            const NAMES: &[(i32, &str)] = &[#(#entries),*];
+            NAMES
         }
     })
 }
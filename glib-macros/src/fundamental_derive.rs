@@ -0,0 +1,153 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    shared_boxed_derive::refcounted_type,
+    utils::{NestedMetaItem, crate_ident_new, parse_nested_meta_items},
+};
+
+pub fn impl_fundamental(input: &syn::DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+
+    let Some(instance_type) = refcounted_type(input) else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "#[derive(glib::Fundamental)] requires struct MyStruct(FundamentalRef<T>)",
+        ));
+    };
+
+    let mut gtype_name = NestedMetaItem::<syn::LitStr>::new("name")
+        .required()
+        .value_required();
+    let mut allow_name_conflict =
+        NestedMetaItem::<syn::LitBool>::new("allow_name_conflict").value_optional();
+    let mut derivable = NestedMetaItem::<syn::LitBool>::new("derivable").value_optional();
+
+    let found = parse_nested_meta_items(
+        &input.attrs,
+        "fundamental_type",
+        &mut [&mut gtype_name, &mut allow_name_conflict, &mut derivable],
+    )?;
+
+    if found.is_none() {
+        return Err(syn::Error::new_spanned(
+            input,
+            "#[derive(glib::Fundamental)] requires #[fundamental_type(name = \"FundamentalTypeName\")]",
+        ));
+    }
+
+    let gtype_name = gtype_name.value.unwrap();
+    let allow_name_conflict = allow_name_conflict.found
+        || allow_name_conflict
+            .value
+            .map(|b| b.value())
+            .unwrap_or(false);
+    let derivable = derivable.found || derivable.value.map(|b| b.value()).unwrap_or(false);
+
+    let crate_ident = crate_ident_new();
+
+    Ok(quote! {
+        impl #crate_ident::subclass::fundamental::FundamentalType for #name {
+            const NAME: &'static ::core::primitive::str = #gtype_name;
+            const ALLOW_NAME_CONFLICT: bool = #allow_name_conflict;
+            const DERIVABLE: bool = #derivable;
+
+            type Data = <#instance_type as ::std::ops::Deref>::Target;
+
+            #[inline]
+            fn from_instance(
+                this: #crate_ident::subclass::fundamental::FundamentalRef<Self::Data>,
+            ) -> Self {
+                Self(this)
+            }
+
+            #[inline]
+            fn into_instance(self) -> #crate_ident::subclass::fundamental::FundamentalRef<Self::Data> {
+                self.0
+            }
+        }
+
+        impl #crate_ident::prelude::StaticType for #name {
+            #[inline]
+            fn static_type() -> #crate_ident::Type {
+                static TYPE: ::std::sync::OnceLock<#crate_ident::Type> = ::std::sync::OnceLock::new();
+                *TYPE.get_or_init(|| {
+                    #crate_ident::subclass::fundamental::register_fundamental_type::<#name>()
+                })
+            }
+        }
+
+        impl #crate_ident::value::ValueType for #name {
+            type Type = #name;
+        }
+
+        impl #crate_ident::value::ValueTypeOptional for #name { }
+
+        impl #crate_ident::value::ToValue for #name {
+            #[inline]
+            fn to_value(&self) -> #crate_ident::Value {
+                ::std::convert::From::from(::core::clone::Clone::clone(self))
+            }
+
+            #[inline]
+            fn value_type(&self) -> #crate_ident::Type {
+                <#name as #crate_ident::prelude::StaticType>::static_type()
+            }
+        }
+
+        impl #crate_ident::value::ToValueOptional for #name {
+            #[inline]
+            fn to_value_optional(s: ::core::option::Option<&Self>) -> #crate_ident::Value {
+                let mut value = #crate_ident::Value::for_value_type::<Self>();
+                unsafe {
+                    #crate_ident::subclass::fundamental::value_take(
+                        &mut value,
+                        ::core::option::Option::map(s, ::core::clone::Clone::clone),
+                    );
+                }
+
+                value
+            }
+        }
+
+        impl ::std::convert::From<#name> for #crate_ident::Value {
+            #[inline]
+            fn from(v: #name) -> Self {
+                let mut value = #crate_ident::Value::for_value_type::<#name>();
+                unsafe {
+                    #crate_ident::subclass::fundamental::value_take(
+                        &mut value,
+                        ::core::option::Option::Some(v),
+                    );
+                }
+
+                value
+            }
+        }
+
+        unsafe impl<'a> #crate_ident::value::FromValue<'a> for #name {
+            type Checker = #crate_ident::value::GenericValueTypeOrNoneChecker<Self>;
+
+            #[inline]
+            unsafe fn from_value(value: &'a #crate_ident::Value) -> Self {
+                unsafe {
+                    let v = #crate_ident::subclass::fundamental::value_dup::<Self>(value);
+                    debug_assert!(v.is_some());
+                    v.unwrap_unchecked()
+                }
+            }
+        }
+
+        impl #crate_ident::HasParamSpec for #name {
+            type ParamSpec = #crate_ident::ParamSpec;
+            type SetValue = Self;
+            type BuilderFn = fn(&::core::primitive::str) -> #crate_ident::subclass::fundamental::ParamSpecFundamentalBuilder<Self>;
+
+            fn param_spec_builder() -> Self::BuilderFn {
+                |name| #crate_ident::subclass::fundamental::ParamSpecFundamentalBuilder::new(name)
+            }
+        }
+    })
+}
@@ -9,6 +9,7 @@ mod downgrade_derive;
 mod enum_derive;
 mod error_domain_derive;
 mod flags_attribute;
+mod fundamental_derive;
 mod object_impl_attributes;
 mod properties;
 mod shared_boxed_derive;
@@ -221,6 +222,44 @@ use utils::{NestedMetaItem, parse_nested_meta_items_from_stream};
 /// # assert_eq!(closure(2), false);
 /// ```
 ///
+/// ### Auditing captures of the object a closure is connected to
+///
+/// A closure that keeps a strong reference to the object it is connected to creates a reference
+/// cycle, and neither the object nor the closure are ever freed. With `#[connected_to]`, the
+/// macro fails to compile if the named object is captured with `#[strong]` or `#[to_owned]`, or
+/// is used in the closure without being captured with `#[weak]`.
+///
+/// ```
+/// use glib;
+/// use glib_macros::clone;
+/// use std::rc::Rc;
+///
+/// let v = Rc::new(1);
+/// let closure = clone!(
+///     #[connected_to(v)]
+///     #[weak] v,
+///     move |x| {
+///         println!("v: {}, x: {}", v, x);
+///     },
+/// );
+/// # closure(2);
+/// ```
+///
+/// ```compile_fail
+/// # use glib;
+/// # use glib_macros::clone;
+/// # use std::rc::Rc;
+/// # let v = Rc::new(1);
+/// // Fails because `v` is captured strongly.
+/// let closure = clone!(
+///     #[connected_to(v)]
+///     move |x| {
+///         println!("v: {}, x: {}", v, x);
+///     },
+/// );
+/// # closure(2);
+/// ```
+///
 /// ### Errors
 ///
 /// Here is a list of errors you might encounter:
@@ -461,6 +500,30 @@ pub fn clone(item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// ### Connected Objects
+///
+/// `#[connected_to]` names the object the closure is going to be connected to. Capturing it with
+/// `#[strong]` or `#[to_owned]` then fails to compile, as that would create a reference cycle.
+/// If the object is used in the closure without an explicit capture, it is watched as if it was
+/// captured with `#[watch]` instead of being moved into the closure.
+///
+/// ```
+/// use glib;
+/// use glib::prelude::*;
+/// use glib_macros::closure_local;
+///
+/// let obj = glib::Object::new::<glib::Object>();
+/// obj.connect_closure(
+///     "notify", false,
+///     closure_local!(
+///         #[connected_to(obj)]
+///         move |_: glib::Object, pspec: glib::ParamSpec| {
+///             println!("{} changed on {}", pspec.name(), obj.type_().name());
+///         },
+///     ),
+/// );
+/// ```
+///
 /// ### Weak and Strong References
 ///
 /// ```
@@ -807,6 +870,25 @@ pub fn flags(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// }
 /// ```
 ///
+/// # D-Bus Errors
+///
+/// With `dbus_prefix`, each variant is mapped to a D-Bus error name made of the prefix and the
+/// name of the variant. The name of a single variant can be overridden with `dbus_name`.
+/// Once the domain is registered with `gio::DBusError::register_error_domain()`, errors of
+/// the domain are sent as these D-Bus errors, and received D-Bus errors with these names are
+/// converted back to errors of the domain.
+///
+/// ```
+/// #[derive(Debug, Copy, Clone, glib::ErrorDomain)]
+/// #[error_domain(name = "ex-bar", dbus_prefix = "org.example.Bar.Error")]
+/// enum Bar {
+///     // org.example.Bar.Error.NotFound
+///     NotFound,
+///     #[error_domain(dbus_name = "org.freedesktop.DBus.Error.AccessDenied")]
+///     AccessDenied,
+/// }
+/// ```
+///
 /// [`ErrorDomain`]: ../glib/error/trait.ErrorDomain.html
 #[proc_macro_derive(ErrorDomain, attributes(error_domain))]
 pub fn error_domain_derive(input: TokenStream) -> TokenStream {
@@ -820,6 +902,10 @@ pub fn error_domain_derive(input: TokenStream) -> TokenStream {
 /// the [`glib::Value`] traits. Optionally, the type can be marked as
 /// `nullable` to get an implementation of `glib::value::ToValueOptional`.
 ///
+/// If no `name` is given, the type is registered on first use with a name
+/// generated from its `TypeId` and no [`BoxedType`] implementation is generated.
+/// See [`register_boxed_type_on_demand`].
+///
 /// # Example
 ///
 /// ```
@@ -833,9 +919,16 @@ pub fn error_domain_derive(input: TokenStream) -> TokenStream {
 /// #[derive(Clone, Debug, PartialEq, Eq, glib::Boxed)]
 /// #[boxed_type(name = "MyNullableBoxed", nullable)]
 /// struct MyNullableBoxed(String);
+///
+/// #[derive(Clone, Debug, PartialEq, Eq, glib::Boxed)]
+/// enum MyPayload {
+///     Text(String),
+///     Point { x: i32, y: i32 },
+/// }
 /// ```
 ///
 /// [`BoxedType`]: ../glib/subclass/boxed/trait.BoxedType.html
+/// [`register_boxed_type_on_demand`]: ../glib/subclass/boxed/fn.register_boxed_type_on_demand.html
 /// [`glib::Value`]: ../glib/value/struct.Value.html
 #[proc_macro_derive(Boxed, attributes(boxed_type))]
 pub fn boxed_derive(input: TokenStream) -> TokenStream {
@@ -879,6 +972,44 @@ pub fn shared_boxed_derive(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derive macro for defining a [`FundamentalType`]`::get_type` function and
+/// the [`glib::Value`] traits.
+///
+/// The type is registered as a new classed and instantiatable fundamental type together with a
+/// `GParamSpec` type for properties holding values of it. The struct must wrap a
+/// [`FundamentalRef`] to the instance data. Values of the type can always be `None`. Optionally,
+/// the type can be marked as `derivable` to allow registering other types derived from it.
+///
+/// # Example
+///
+/// ```
+/// use glib::prelude::*;
+/// use glib::subclass::prelude::*;
+///
+/// #[derive(Debug, PartialEq, Eq)]
+/// struct MyFundamentalInner {
+///   foo: String,
+/// }
+///
+/// #[derive(Clone, Debug, PartialEq, Eq, glib::Fundamental)]
+/// #[fundamental_type(name = "MyFundamental")]
+/// struct MyFundamental(glib::subclass::fundamental::FundamentalRef<MyFundamentalInner>);
+///
+/// let f = MyFundamental::with_data(MyFundamentalInner { foo: String::from("abc") });
+/// assert_eq!(f.to_value().get::<MyFundamental>().unwrap(), f);
+/// ```
+///
+/// [`FundamentalType`]: ../glib/subclass/fundamental/trait.FundamentalType.html
+/// [`FundamentalRef`]: ../glib/subclass/fundamental/struct.FundamentalRef.html
+/// [`glib::Value`]: ../glib/value/struct.Value.html
+#[proc_macro_derive(Fundamental, attributes(fundamental_type))]
+pub fn fundamental_derive(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    fundamental_derive::impl_fundamental(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
 /// Macro for boilerplate of [`ObjectSubclass`] implementations.
 ///
 /// This adds implementations for the `type_data()` and `type_()` methods,
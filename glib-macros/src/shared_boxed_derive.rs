@@ -67,7 +67,7 @@ fn gen_impl_from_value(name: &Ident, crate_ident: &TokenStream) -> TokenStream {
     }
 }
 
-fn refcounted_type(input: &syn::DeriveInput) -> Option<&syn::TypePath> {
+pub(crate) fn refcounted_type(input: &syn::DeriveInput) -> Option<&syn::TypePath> {
     let fields = match &input.data {
         syn::Data::Struct(s) => &s.fields,
         _ => return None,
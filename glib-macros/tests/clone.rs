@@ -164,6 +164,23 @@ fn clone() {
             1
         }
     );
+
+    // Shadowing bindings and fields with the same name are not usages of `v`.
+    struct S {
+        v: i32,
+    }
+    let v = Rc::new(1);
+    let s = S { v: 1 };
+    let _ = clone!(
+        #[connected_to(v)]
+        move || {
+            let v = s.v;
+            if let Some(v) = Some(v) {
+                println!("foo {v}");
+            }
+            v
+        }
+    );
 }
 
 const TESTS: &[(&str, &str)] = &[
@@ -234,7 +251,7 @@ const TESTS: &[(&str, &str)] = &[
     ),
     (
         "clone!(#[yolo] v, move || {})",
-        r#"error: unsupported attribute `yolo`: only `strong`, `weak`, `weak_allow_none`, `to_owned`, `upgrade_or`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported
+        r#"error: unsupported attribute `yolo`: only `strong`, `weak`, `weak_allow_none`, `to_owned`, `connected_to`, `upgrade_or`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported
  --> test_9.rs:1:66
   |
 1 | fn main() { use glib::clone; let v = std::rc::Rc::new(1); clone!(#[yolo] v, move || {}); }
@@ -321,6 +338,46 @@ const TESTS: &[(&str, &str)] = &[
 1 | fn main() { use glib::clone; let v = std::rc::Rc::new(1); clone!(#[strong] v, {println!("foo");}); }
   |                                                                               ^^^^^^^^^^^^^^^^^^"#,
     ),
+    (
+        "clone!(#[connected_to(v)] #[strong] v, move || {println!(\"{}\", v);})",
+        r#"error: strong capture of `v` creates a reference cycle as the closure is connected to it. Use `#[weak]` or `#[weak_allow_none]` instead
+ --> test_20.rs:1:95
+  |
+1 | fn main() { use glib::clone; let v = std::rc::Rc::new(1); clone!(#[connected_to(v)] #[strong] v, move || {println!("{}", v);}); }
+  |                                                                                               ^"#,
+    ),
+    (
+        "clone!(#[connected_to(v)] #[connected_to(v)] move || {})",
+        r#"error: multiple `connected_to` attributes are not supported
+ --> test_21.rs:1:85
+  |
+1 | fn main() { use glib::clone; let v = std::rc::Rc::new(1); clone!(#[connected_to(v)] #[connected_to(v)] move || {}); }
+  |                                                                                     ^^^^^^^^^^^^^^^^^^"#,
+    ),
+    (
+        "clone!(#[connected_to(v)] move || {println!(\"{}\", v);})",
+        r#"error: `v` is captured strongly, which creates a reference cycle as the closure is connected to it. Capture it with `#[weak] v` instead
+ --> test_22.rs:1:109
+  |
+1 | fn main() { use glib::clone; let v = std::rc::Rc::new(1); clone!(#[connected_to(v)] move || {println!("{}", v);}); }
+  |                                                                                                             ^"#,
+    ),
+    (
+        "clone!(#[connected_to(v)] move || {println!(\"{v:?}\");})",
+        r#"error: `v` is captured strongly, which creates a reference cycle as the closure is connected to it. Capture it with `#[weak] v` instead
+ --> test_23.rs:1:103
+  |
+1 | fn main() { use glib::clone; let v = std::rc::Rc::new(1); clone!(#[connected_to(v)] move || {println!("{v:?}");}); }
+  |                                                                                                       ^^^^^^^"#,
+    ),
+    (
+        "clone!(#[connected_to(v)] move || {let w = v.clone(); if let Some(v) = Some(1) {} drop(v); w})",
+        r#"error: `v` is captured strongly, which creates a reference cycle as the closure is connected to it. Capture it with `#[weak] v` instead
+ --> test_24.rs:1:102
+  |
+1 | fn main() { use glib::clone; let v = std::rc::Rc::new(1); clone!(#[connected_to(v)] move || {let w = v.clone(); if let Some(v) = Some(1) {} drop(v); w}); }
+  |                                                                                                      ^"#,
+    ),
 ];
 
 #[test]
@@ -68,7 +68,7 @@ const TESTS: &[(&str, &str)] = &[
     ),
     (
         "closure!(#[yolo] v, move || {})",
-        r#"error: unsupported attribute `yolo`: only `watch`, `strong`, `weak`, `weak_allow_none`, `to_owned`, `upgrade_or`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported
+        r#"error: unsupported attribute `yolo`: only `watch`, `strong`, `weak`, `weak_allow_none`, `to_owned`, `connected_to`, `upgrade_or`, `upgrade_or_else`, `upgrade_or_default` and `upgrade_or_panic` are supported
  --> test_9.rs:1:70
   |
 1 | fn main() { use glib::closure; let v = std::rc::Rc::new(1); closure!(#[yolo] v, move || {}); }
@@ -154,6 +154,30 @@ const TESTS: &[(&str, &str)] = &[
 1 | fn main() { use glib::closure; let v = std::rc::Rc::new(1); closure!(#[strong] v, #[upgrade_or_panic] move || {println!("foo");}); }
   |                                                                                   ^"#,
     ),
+    (
+        "closure!(#[connected_to(v)] #[strong] v, move || {println!(\"{}\", v);})",
+        r#"error: strong capture of `v` creates a reference cycle as the closure is connected to it. Use `#[weak]`, `#[weak_allow_none]` or `#[watch]` instead
+ --> test_20.rs:1:99
+  |
+1 | fn main() { use glib::closure; let v = std::rc::Rc::new(1); closure!(#[connected_to(v)] #[strong] v, move || {println!("{}", v);}); }
+  |                                                                                                   ^"#,
+    ),
+    (
+        "closure!(#[connected_to(v)] #[connected_to(v)] move || {})",
+        r#"error: multiple `connected_to` attributes are not supported
+ --> test_21.rs:1:89
+  |
+1 | fn main() { use glib::closure; let v = std::rc::Rc::new(1); closure!(#[connected_to(v)] #[connected_to(v)] move || {}); }
+  |                                                                                         ^^^^^^^^^^^^^^^^^^"#,
+    ),
+    (
+        "closure!(#[connected_to(v)] #[watch(rename_to = w)] v, move || drop(v))",
+        r#"error: `v` is captured strongly, which creates a reference cycle as the closure is connected to it. Capture it with `#[weak] v` or `#[watch] v` instead
+ --> test_22.rs:1:129
+  |
+1 | fn main() { use glib::closure; let v = std::rc::Rc::new(1); closure!(#[connected_to(v)] #[watch(rename_to = w)] v, move || drop(v)); }
+  |                                                                                                                                 ^"#,
+    ),
 ];
 
 #[test]
@@ -21,6 +21,33 @@ fn derive_error_domain() {
     assert!(matches!(err.kind::<TestError>(), Some(TestError::Bad)));
 }
 
+#[test]
+fn derive_error_domain_dbus() {
+    #[derive(Debug, Eq, PartialEq, Clone, Copy, glib::ErrorDomain)]
+    #[error_domain(name = "TestDBusError", dbus_prefix = "org.gtk_rs.Test.Error")]
+    enum TestDBusError {
+        Invalid,
+        #[error_domain(dbus_name = "org.freedesktop.DBus.Error.AccessDenied")]
+        Denied,
+    }
+
+    assert_eq!(
+        TestDBusError::dbus_error_names(),
+        &[
+            (0, "org.gtk_rs.Test.Error.Invalid"),
+            (1, "org.freedesktop.DBus.Error.AccessDenied"),
+        ]
+    );
+
+    #[derive(Debug, Eq, PartialEq, Clone, Copy, glib::ErrorDomain)]
+    #[error_domain(name = "TestNoDBusError")]
+    enum TestNoDBusError {
+        Invalid,
+    }
+
+    assert!(TestNoDBusError::dbus_error_names().is_empty());
+}
+
 #[test]
 fn derive_shared_arc() {
     #[derive(Debug, Eq, PartialEq, Clone)]
@@ -150,6 +177,39 @@ fn derive_boxed() {
     assert_eq!(b, v.get::<MyBoxed>().unwrap());
 }
 
+#[test]
+fn derive_fundamental() {
+    use glib::subclass::{fundamental::FundamentalRef, prelude::*};
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct MyFundamentalInner {
+        foo: String,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, glib::Fundamental)]
+    #[fundamental_type(name = "MyFundamental")]
+    struct MyFundamental(FundamentalRef<MyFundamentalInner>);
+
+    let t = MyFundamental::static_type();
+    assert_eq!(t.parent(), None);
+    assert_eq!(t.name(), "MyFundamental");
+
+    let f = MyFundamental::with_data(MyFundamentalInner {
+        foo: String::from("abc"),
+    });
+    assert_eq!(FundamentalRef::type_(&f.0), t);
+    assert_eq!(f.0.foo, "abc");
+
+    let v = f.to_value();
+    assert_eq!(v.type_(), t);
+    let f2 = v.get::<MyFundamental>().unwrap();
+    assert!(FundamentalRef::ptr_eq(&f.0, &f2.0));
+    assert_eq!(FundamentalRef::ref_count(&f.0), 3);
+
+    let v = None::<MyFundamental>.to_value();
+    assert_eq!(v.get::<Option<MyFundamental>>().unwrap(), None);
+}
+
 #[allow(clippy::unnecessary_literal_unwrap)]
 #[test]
 fn derive_boxed_nullable() {
@@ -572,6 +632,20 @@ fn closure() {
     };
     weak_test.invoke::<()>(&[]);
 
+    let connected_test = {
+        let obj = glib::Object::new::<glib::Object>();
+
+        let connected_test = glib::closure_local!(
+            #[connected_to(obj)]
+            move || obj.ref_count()
+        );
+        assert_eq!(obj.ref_count(), 1);
+        assert_eq!(connected_test.invoke::<u32>(&[]), 2);
+
+        connected_test
+    };
+    connected_test.invoke::<()>(&[]);
+
     {
         trait TestExt {
             fn ref_count_in_closure(&self) -> u32;
@@ -784,6 +784,69 @@ impl FromGlib<ffi::GOptionArg> for OptionArg {
     }
 }
 
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
+#[non_exhaustive]
+#[doc(alias = "GOptionError")]
+pub enum OptionError {
+    #[doc(alias = "G_OPTION_ERROR_UNKNOWN_OPTION")]
+    UnknownOption,
+    #[doc(alias = "G_OPTION_ERROR_BAD_VALUE")]
+    BadValue,
+    #[doc(alias = "G_OPTION_ERROR_FAILED")]
+    Failed,
+    #[doc(hidden)]
+    __Unknown(i32),
+}
+
+#[doc(hidden)]
+impl IntoGlib for OptionError {
+    type GlibType = ffi::GOptionError;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GOptionError {
+        match self {
+            Self::UnknownOption => ffi::G_OPTION_ERROR_UNKNOWN_OPTION,
+            Self::BadValue => ffi::G_OPTION_ERROR_BAD_VALUE,
+            Self::Failed => ffi::G_OPTION_ERROR_FAILED,
+            Self::__Unknown(value) => value,
+        }
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GOptionError> for OptionError {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GOptionError) -> Self {
+        match value {
+            ffi::G_OPTION_ERROR_UNKNOWN_OPTION => Self::UnknownOption,
+            ffi::G_OPTION_ERROR_BAD_VALUE => Self::BadValue,
+            ffi::G_OPTION_ERROR_FAILED => Self::Failed,
+            value => Self::__Unknown(value),
+        }
+    }
+}
+
+impl crate::error::ErrorDomain for OptionError {
+    #[inline]
+    fn domain() -> crate::Quark {
+        unsafe { from_glib(ffi::g_option_error_quark()) }
+    }
+
+    #[inline]
+    fn code(self) -> i32 {
+        self.into_glib()
+    }
+
+    #[inline]
+    #[allow(clippy::match_single_binding)]
+    fn from(code: i32) -> Option<Self> {
+        match unsafe { from_glib(code) } {
+            Self::__Unknown(_) => Some(Self::Failed),
+            value => Some(value),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Clone, Copy)]
 #[non_exhaustive]
 #[doc(alias = "GSeekType")]
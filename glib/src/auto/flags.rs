@@ -323,6 +323,43 @@ impl FromGlib<ffi::GMainContextFlags> for MainContextFlags {
     }
 }
 
+bitflags! {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    #[doc(alias = "GMarkupParseFlags")]
+    pub struct MarkupParseFlags: u32 {
+        #[cfg(feature = "v2_74")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "v2_74")))]
+        #[doc(alias = "G_MARKUP_DEFAULT_FLAGS")]
+        const DEFAULT_FLAGS = ffi::G_MARKUP_DEFAULT_FLAGS as _;
+        #[doc(alias = "G_MARKUP_DO_NOT_USE_THIS_UNSUPPORTED_FLAG")]
+        const DO_NOT_USE_THIS_UNSUPPORTED_FLAG = ffi::G_MARKUP_DO_NOT_USE_THIS_UNSUPPORTED_FLAG as _;
+        #[doc(alias = "G_MARKUP_TREAT_CDATA_AS_TEXT")]
+        const TREAT_CDATA_AS_TEXT = ffi::G_MARKUP_TREAT_CDATA_AS_TEXT as _;
+        #[doc(alias = "G_MARKUP_PREFIX_ERROR_POSITION")]
+        const PREFIX_ERROR_POSITION = ffi::G_MARKUP_PREFIX_ERROR_POSITION as _;
+        #[doc(alias = "G_MARKUP_IGNORE_QUALIFIED")]
+        const IGNORE_QUALIFIED = ffi::G_MARKUP_IGNORE_QUALIFIED as _;
+    }
+}
+
+#[doc(hidden)]
+impl IntoGlib for MarkupParseFlags {
+    type GlibType = ffi::GMarkupParseFlags;
+
+    #[inline]
+    fn into_glib(self) -> ffi::GMarkupParseFlags {
+        self.bits()
+    }
+}
+
+#[doc(hidden)]
+impl FromGlib<ffi::GMarkupParseFlags> for MarkupParseFlags {
+    #[inline]
+    unsafe fn from_glib(value: ffi::GMarkupParseFlags) -> Self {
+        Self::from_bits_truncate(value)
+    }
+}
+
 bitflags! {
     #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
     #[doc(alias = "GOptionFlags")]
@@ -33,27 +33,12 @@ pub fn base64_decode(text: &str) -> Vec<u8> {
 //    unsafe { TODO: call ffi:g_base64_decode_inplace() }
 //}
 
-//#[doc(alias = "g_base64_decode_step")]
-//pub fn base64_decode_step(in_: &[u8], out: Vec<u8>, state: &mut i32, save: &mut u32) -> usize {
-//    unsafe { TODO: call ffi:g_base64_decode_step() }
-//}
-
 #[doc(alias = "g_base64_encode")]
 pub fn base64_encode(data: &[u8]) -> crate::GString {
     let len = data.len() as _;
     unsafe { from_glib_full(ffi::g_base64_encode(data.to_glib_none().0, len)) }
 }
 
-//#[doc(alias = "g_base64_encode_close")]
-//pub fn base64_encode_close(break_lines: bool, out: Vec<u8>, state: &mut i32, save: &mut i32) -> usize {
-//    unsafe { TODO: call ffi:g_base64_encode_close() }
-//}
-
-//#[doc(alias = "g_base64_encode_step")]
-//pub fn base64_encode_step(in_: &[u8], break_lines: bool, out: Vec<u8>, state: &mut i32, save: &mut i32) -> usize {
-//    unsafe { TODO: call ffi:g_base64_encode_step() }
-//}
-
 #[doc(alias = "glib_check_version")]
 pub fn check_version(
     required_major: u32,
@@ -755,13 +740,6 @@ pub fn spawn_async(
     }
 }
 
-//#[cfg(feature = "v2_68")]
-//#[cfg_attr(docsrs, doc(cfg(feature = "v2_68")))]
-//#[doc(alias = "g_spawn_async_with_pipes_and_fds")]
-//pub fn spawn_async_with_pipes_and_fds(working_directory: Option<impl AsRef<std::path::Path>>, argv: &[&std::path::Path], envp: &[&std::path::Path], flags: SpawnFlags, child_setup: Option<Box_<dyn FnOnce() + 'static>>, stdin_fd: i32, stdout_fd: i32, stderr_fd: i32, source_fds: &[i32], target_fds: &[i32], n_fds: usize) -> Result<(Pid, i32, i32, i32), crate::Error> {
-//    unsafe { TODO: call ffi:g_spawn_async_with_pipes_and_fds() }
-//}
-
 #[cfg_attr(feature = "v2_70", deprecated = "Since 2.70")]
 #[allow(deprecated)]
 #[doc(alias = "g_spawn_check_exit_status")]
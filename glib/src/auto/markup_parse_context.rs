@@ -16,11 +16,6 @@ crate::wrapper! {
 }
 
 impl MarkupParseContext {
-    //#[doc(alias = "g_markup_parse_context_new")]
-    //pub fn new(parser: /*Ignored*/&MarkupParser, flags: /*Ignored*/MarkupParseFlags, user_data: /*Unimplemented*/Option<Basic: Pointer>) -> MarkupParseContext {
-    //    unsafe { TODO: call ffi:g_markup_parse_context_new() }
-    //}
-
     #[doc(alias = "g_markup_parse_context_end_parse")]
     pub fn end_parse(&self) -> Result<(), crate::Error> {
         unsafe {
@@ -120,14 +115,4 @@ impl MarkupParseContext {
             }
         }
     }
-
-    //#[doc(alias = "g_markup_parse_context_pop")]
-    //pub fn pop(&self) -> /*Unimplemented*/Option<Basic: Pointer> {
-    //    unsafe { TODO: call ffi:g_markup_parse_context_pop() }
-    //}
-
-    //#[doc(alias = "g_markup_parse_context_push")]
-    //pub fn push(&self, parser: /*Ignored*/&MarkupParser, user_data: /*Unimplemented*/Option<Basic: Pointer>) {
-    //    unsafe { TODO: call ffi:g_markup_parse_context_push() }
-    //}
 }
@@ -47,6 +47,7 @@ pub use self::enums::LogWriterOutput;
 pub use self::enums::MarkupError;
 pub use self::enums::NormalizeMode;
 pub use self::enums::OptionArg;
+pub use self::enums::OptionError;
 pub use self::enums::SeekType;
 pub use self::enums::TimeType;
 pub use self::enums::UnicodeBreakType;
@@ -69,6 +70,7 @@ pub use self::flags::LogLevelFlags;
 #[cfg(feature = "v2_72")]
 #[cfg_attr(docsrs, doc(cfg(feature = "v2_72")))]
 pub use self::flags::MainContextFlags;
+pub use self::flags::MarkupParseFlags;
 pub use self::flags::OptionFlags;
 pub use self::flags::RegexCompileFlags;
 pub use self::flags::RegexMatchFlags;
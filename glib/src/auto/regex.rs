@@ -79,9 +79,4 @@ impl Regex {
     pub fn pattern(&self) -> crate::GString {
         unsafe { from_glib_none(ffi::g_regex_get_pattern(self.to_glib_none().0)) }
     }
-
-    //#[doc(alias = "g_regex_replace_eval")]
-    //pub fn replace_eval(&self, string: &[&str], start_position: i32, match_options: RegexMatchFlags, eval: /*Unimplemented*/FnMut(&MatchInfo, /*Ignored*/String) -> bool, user_data: /*Unimplemented*/Option<Basic: Pointer>) -> Result<crate::GString, crate::Error> {
-    //    unsafe { TODO: call ffi:g_regex_replace_eval() }
-    //}
 }
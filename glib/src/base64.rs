@@ -0,0 +1,365 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::io::{self, Read, Write};
+
+use crate::{ffi, translate::*};
+
+// rustdoc-stripper-ignore-next
+/// Incremental base64 encoder.
+///
+/// Data can be passed in chunks of any size with [`encode_step`](Self::encode_step), the
+/// encoding is terminated with [`finish`](Self::finish).
+///
+/// ```
+/// let mut encoder = glib::Base64Encoder::new(false);
+/// let mut out = Vec::new();
+/// encoder.encode_step(b"Hello, ", &mut out);
+/// encoder.encode_step(b"world!", &mut out);
+/// encoder.finish(&mut out);
+/// assert_eq!(out, b"SGVsbG8sIHdvcmxkIQ==");
+/// ```
+#[derive(Debug, Clone)]
+#[doc(alias = "g_base64_encode_step")]
+pub struct Base64Encoder {
+    break_lines: bool,
+    state: i32,
+    save: i32,
+}
+
+impl Base64Encoder {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new encoder. If `break_lines` is `true`, a newline is inserted after every 76
+    /// characters of output, as required for MIME.
+    pub fn new(break_lines: bool) -> Self {
+        Self {
+            break_lines,
+            state: 0,
+            save: 0,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Encodes `input` and appends the output to `out`. Up to 2 bytes of input are kept until
+    /// the next step or [`finish`](Self::finish).
+    #[doc(alias = "g_base64_encode_step")]
+    pub fn encode_step(&mut self, input: &[u8], out: &mut Vec<u8>) {
+        let mut max_len = (input.len() / 3 + 1) * 4 + 4;
+        if self.break_lines {
+            max_len += max_len / 76 + 1;
+        }
+        out.reserve(max_len);
+
+        unsafe {
+            let len = out.len();
+            let written = ffi::g_base64_encode_step(
+                input.as_ptr(),
+                input.len(),
+                self.break_lines.into_glib(),
+                out.as_mut_ptr().add(len),
+                &mut self.state,
+                &mut self.save,
+            );
+            out.set_len(len + written);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends the remaining output to `out`. Afterwards, the encoder can be used for new data.
+    #[doc(alias = "g_base64_encode_close")]
+    pub fn finish(&mut self, out: &mut Vec<u8>) {
+        out.reserve(5);
+
+        unsafe {
+            let len = out.len();
+            let written = ffi::g_base64_encode_close(
+                self.break_lines.into_glib(),
+                out.as_mut_ptr().add(len),
+                &mut self.state,
+                &mut self.save,
+            );
+            out.set_len(len + written);
+        }
+
+        self.reset();
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Discards the pending input.
+    pub fn reset(&mut self) {
+        self.state = 0;
+        self.save = 0;
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Incremental base64 decoder.
+///
+/// Invalid characters in the input are skipped, e.g. newlines.
+///
+/// ```
+/// let mut decoder = glib::Base64Decoder::new();
+/// let mut out = Vec::new();
+/// decoder.decode_step(b"SGVsbG8sIH", &mut out);
+/// decoder.decode_step(b"dvcmxkIQ==", &mut out);
+/// assert_eq!(out, b"Hello, world!");
+/// ```
+#[derive(Debug, Clone, Default)]
+#[doc(alias = "g_base64_decode_step")]
+pub struct Base64Decoder {
+    state: i32,
+    save: u32,
+}
+
+impl Base64Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Decodes `input` and appends the output to `out`. Up to 3 characters of input are kept
+    /// until the next step.
+    #[doc(alias = "g_base64_decode_step")]
+    pub fn decode_step(&mut self, input: &[u8], out: &mut Vec<u8>) {
+        out.reserve((input.len() / 4) * 3 + 3);
+
+        unsafe {
+            let len = out.len();
+            let written = ffi::g_base64_decode_step(
+                input.as_ptr(),
+                input.len(),
+                out.as_mut_ptr().add(len),
+                &mut self.state,
+                &mut self.save,
+            );
+            out.set_len(len + written);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Discards the pending input.
+    pub fn reset(&mut self) {
+        self.state = 0;
+        self.save = 0;
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A writer that base64-encodes all data written to it into another writer.
+///
+/// The encoding has to be terminated with [`finish`](Self::finish). Otherwise it is terminated
+/// when the writer is dropped, ignoring errors.
+///
+/// ```
+/// use std::io::Write;
+///
+/// let mut writer = glib::Base64EncodeWriter::new(Vec::new(), false);
+/// writer.write_all(b"Hello, world!").unwrap();
+/// assert_eq!(writer.finish().unwrap(), b"SGVsbG8sIHdvcmxkIQ==");
+/// ```
+#[derive(Debug)]
+pub struct Base64EncodeWriter<W: Write> {
+    encoder: Base64Encoder,
+    inner: Option<W>,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> Base64EncodeWriter<W> {
+    pub fn new(inner: W, break_lines: bool) -> Self {
+        Self {
+            encoder: Base64Encoder::new(break_lines),
+            inner: Some(inner),
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().unwrap()
+    }
+
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner.as_mut().unwrap()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Terminates the encoding, flushes the inner writer and returns it.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.encoder.finish(&mut self.buf);
+        self.flush()?;
+        Ok(self.inner.take().unwrap())
+    }
+
+    fn write_buf(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().unwrap();
+        while !self.buf.is_empty() {
+            match inner.write(&self.buf) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => {
+                    self.buf.drain(..n);
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => (),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for Base64EncodeWriter<W> {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        // Output of previous writes that couldn't be written yet is written first so that no
+        // data is consumed if writing fails.
+        self.write_buf()?;
+        self.encoder.encode_step(data, &mut self.buf);
+        let _ = self.write_buf();
+        Ok(data.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_buf()?;
+        self.inner.as_mut().unwrap().flush()
+    }
+}
+
+impl<W: Write> Drop for Base64EncodeWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() && !std::thread::panicking() {
+            self.encoder.finish(&mut self.buf);
+            let _ = self.flush();
+        }
+    }
+}
+
+// Amount of encoded input read from the inner reader at once.
+const DECODE_CHUNK_SIZE: usize = 4096;
+
+// rustdoc-stripper-ignore-next
+/// A reader that base64-decodes all data read from another reader.
+///
+/// ```
+/// use std::io::Read;
+///
+/// let mut reader = glib::Base64DecodeReader::new(&b"SGVsbG8sIHdvcmxkIQ=="[..]);
+/// let mut out = String::new();
+/// reader.read_to_string(&mut out).unwrap();
+/// assert_eq!(out, "Hello, world!");
+/// ```
+#[derive(Debug)]
+pub struct Base64DecodeReader<R: Read> {
+    decoder: Base64Decoder,
+    inner: R,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<R: Read> Base64DecodeReader<R> {
+    pub fn new(inner: R) -> Self {
+        Self {
+            decoder: Base64Decoder::new(),
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the inner reader. Decoded data that was not read yet is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for Base64DecodeReader<R> {
+    fn read(&mut self, data: &mut [u8]) -> io::Result<usize> {
+        // A chunk of input may not produce any output, e.g. if it only contains newlines.
+        let mut chunk = [0; DECODE_CHUNK_SIZE];
+        while self.pos == self.buf.len() {
+            let n = self.inner.read(&mut chunk)?;
+            if n == 0 {
+                return Ok(0);
+            }
+            self.buf.clear();
+            self.pos = 0;
+            self.decoder.decode_step(&chunk[..n], &mut self.buf);
+        }
+
+        let n = data.len().min(self.buf.len() - self.pos);
+        data[..n].copy_from_slice(&self.buf[self.pos..self.pos + n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &[u8] =
+        b"The quick brown fox jumps over the lazy dog, over and over and over again.";
+
+    #[test]
+    fn encode_chunks() {
+        for chunk_size in 1..8 {
+            let mut encoder = Base64Encoder::new(true);
+            let mut out = Vec::new();
+            for chunk in DATA.chunks(chunk_size) {
+                encoder.encode_step(chunk, &mut out);
+            }
+            encoder.finish(&mut out);
+
+            let expected = crate::base64_encode(DATA);
+            assert_eq!(out.len(), expected.len() + 2);
+            assert_eq!(
+                out.iter()
+                    .filter(|&&b| b != b'\n')
+                    .copied()
+                    .collect::<Vec<_>>(),
+                expected.as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn decode_chunks() {
+        let encoded = crate::base64_encode(DATA);
+        for chunk_size in 1..8 {
+            let mut decoder = Base64Decoder::new();
+            let mut out = Vec::new();
+            for chunk in encoded.as_bytes().chunks(chunk_size) {
+                decoder.decode_step(chunk, &mut out);
+            }
+            assert_eq!(out, DATA);
+        }
+    }
+
+    #[test]
+    fn roundtrip_io() {
+        let mut writer = Base64EncodeWriter::new(Vec::new(), true);
+        for chunk in DATA.chunks(5) {
+            writer.write_all(chunk).unwrap();
+        }
+        let encoded = writer.finish().unwrap();
+
+        let mut reader = Base64DecodeReader::new(&encoded[..]);
+        let mut decoded = Vec::new();
+        reader.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, DATA);
+    }
+
+    #[test]
+    fn finish_on_drop() {
+        let mut encoded = Vec::new();
+        {
+            let mut writer = Base64EncodeWriter::new(&mut encoded, false);
+            writer.write_all(b"a").unwrap();
+        }
+        assert_eq!(encoded, b"YQ==");
+    }
+}
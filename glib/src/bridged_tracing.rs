@@ -0,0 +1,273 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::fmt::{self, Write};
+
+use tracing::{
+    Event, Id, Level, Subscriber,
+    field::{Field, Visit},
+    span::{Attributes, Record},
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan};
+
+use crate::{GString, LogField, LogLevel, LogWriterOutput, gstr, log_structured_array};
+
+// Fields that are set by the layer itself and are not overridden by fields of events or spans.
+const RESERVED_FIELDS: &[&str] = &[
+    "PRIORITY",
+    "MESSAGE",
+    "GLIB_DOMAIN",
+    "CODE_FILE",
+    "CODE_LINE",
+    "RUST_MODULE",
+    "TRACING_SPAN",
+];
+
+// rustdoc-stripper-ignore-next
+/// A [`tracing_subscriber::Layer`] which logs all events over glib structured logging.
+///
+/// In order to use this type, `glib` must be built with the `tracing` feature enabled.
+///
+/// Besides the message, the level and the code location, all fields of the event and of the
+/// spans it happens in are passed on as log fields, with their names converted to the
+/// uppercase format journald expects, e.g. `user.id` becomes `USER_ID`. The names of the spans
+/// are passed on as `TRACING_SPAN`, separated by `:` and starting with the outermost span, and
+/// the module path of the event as `RUST_MODULE`.
+///
+/// The target of the event is used as log domain unless a fixed domain is set with
+/// [`with_domain`](Self::with_domain).
+///
+/// NOTE: This should never be used when [`tracing_log_writer`] has been registered as the glib
+/// log writer, otherwise a stack overflow will occur.
+///
+/// ```no_run
+/// use tracing_subscriber::prelude::*;
+///
+/// tracing_subscriber::registry()
+///     .with(glib::GlibTracingLayer::new())
+///     .init();
+///
+/// tracing::info!(user.id = 42, "This line will get logged by glib");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GlibTracingLayer {
+    domain: Option<&'static str>,
+}
+
+impl GlibTracingLayer {
+    pub const fn new() -> Self {
+        Self { domain: None }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a layer that logs all events with `domain` as log domain.
+    pub const fn with_domain(domain: &'static str) -> Self {
+        Self {
+            domain: Some(domain),
+        }
+    }
+
+    fn level_to_glib(level: Level) -> LogLevel {
+        match level {
+            // Errors are mapped to critical to avoid automatic termination
+            Level::ERROR => LogLevel::Critical,
+            Level::WARN => LogLevel::Warning,
+            Level::INFO => LogLevel::Info,
+            // There is no equivalent to trace level in glib
+            _ => LogLevel::Debug,
+        }
+    }
+}
+
+// The fields recorded for a span.
+struct SpanFields(Vec<(GString, String)>);
+
+#[derive(Default)]
+struct FieldVisitor {
+    message: Option<String>,
+    fields: Vec<(GString, String)>,
+}
+
+impl FieldVisitor {
+    fn record(&mut self, field: &Field, value: String) {
+        if field.name() == "message" {
+            self.message = Some(value);
+            return;
+        }
+
+        let mut key = field
+            .name()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_uppercase()
+                } else {
+                    '_'
+                }
+            })
+            .collect::<String>();
+        // journald field names must start with a letter.
+        if !key.starts_with(|c: char| c.is_ascii_uppercase()) {
+            key.insert_str(0, "FIELD");
+        }
+        if !RESERVED_FIELDS.contains(&key.as_str()) {
+            self.fields.push((key.into(), value));
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, value.to_owned());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, format!("{value:?}"));
+    }
+}
+
+impl<S> tracing_subscriber::Layer<S> for GlibTracingLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(SpanFields(visitor.fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else {
+            return;
+        };
+        let mut visitor = FieldVisitor::default();
+        values.record(&mut visitor);
+        if let Some(fields) = span.extensions_mut().get_mut::<SpanFields>() {
+            fields.0.extend(visitor.fields);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let level = Self::level_to_glib(*metadata.level());
+
+        let mut visitor = FieldVisitor::default();
+        event.record(&mut visitor);
+
+        let mut span_names = String::new();
+        let mut span_fields = Vec::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if !span_names.is_empty() {
+                    span_names.push(':');
+                }
+                span_names.push_str(span.name());
+                if let Some(fields) = span.extensions().get::<SpanFields>() {
+                    span_fields.extend(fields.0.iter().cloned());
+                }
+            }
+        }
+
+        let mut line = String::new();
+        if let Some(lineno) = metadata.line() {
+            let _ = write!(line, "{lineno}");
+        }
+
+        let mut fields = vec![
+            LogField::new(gstr!("PRIORITY"), level.priority().as_bytes()),
+            LogField::new(
+                gstr!("MESSAGE"),
+                visitor.message.as_deref().unwrap_or_default().as_bytes(),
+            ),
+            LogField::new(
+                gstr!("GLIB_DOMAIN"),
+                self.domain.unwrap_or(metadata.target()).as_bytes(),
+            ),
+        ];
+        if let Some(file) = metadata.file() {
+            fields.push(LogField::new(gstr!("CODE_FILE"), file.as_bytes()));
+        }
+        if !line.is_empty() {
+            fields.push(LogField::new(gstr!("CODE_LINE"), line.as_bytes()));
+        }
+        // tracing doesn't know the function, so `CODE_FUNC` is left unset.
+        if let Some(module_path) = metadata.module_path() {
+            fields.push(LogField::new(gstr!("RUST_MODULE"), module_path.as_bytes()));
+        }
+        if !span_names.is_empty() {
+            fields.push(LogField::new(gstr!("TRACING_SPAN"), span_names.as_bytes()));
+        }
+        for (key, value) in span_fields.iter().chain(&visitor.fields) {
+            fields.push(LogField::new(key.as_gstr(), value.as_bytes()));
+        }
+
+        log_structured_array(level, &fields);
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Provides a glib log writer which routes all structured logging messages to
+/// [`tracing`](https://crates.io/crates/tracing) events.
+///
+/// In order to use this function, `glib` must be built with the `tracing` feature enabled.
+///
+/// The events have `glib` as target and the log domain, code location and function as `domain`,
+/// `code.file`, `code.lineno` and `code.func` fields.
+///
+/// NOTE: This should never be used when [`GlibTracingLayer`] is registered with the current
+/// subscriber, otherwise a stack overflow will occur.
+///
+/// ```no_run
+/// glib::log_set_writer_func(glib::tracing_log_writer);
+/// ```
+pub fn tracing_log_writer(log_level: LogLevel, fields: &[LogField<'_>]) -> LogWriterOutput {
+    let mut domain = None::<&str>;
+    let mut message = None::<&str>;
+    let mut file = None::<&str>;
+    let mut line = None::<u32>;
+    let mut func = None::<&str>;
+
+    for field in fields {
+        let Some(value) = field.value_str() else {
+            continue;
+        };
+
+        match field.key() {
+            "GLIB_DOMAIN" => domain = Some(value),
+            "MESSAGE" => message = Some(value),
+            "CODE_FILE" => file = Some(value),
+            "CODE_LINE" => line = value.parse().ok(),
+            "CODE_FUNC" => func = Some(value),
+            _ => continue,
+        };
+    }
+
+    let Some(message) = message else {
+        return LogWriterOutput::Handled;
+    };
+
+    macro_rules! event {
+        ($level:expr) => {
+            tracing::event!(
+                target: "glib",
+                $level,
+                domain,
+                code.file = file,
+                code.lineno = line,
+                code.func = func,
+                "{message}"
+            )
+        };
+    }
+
+    match log_level {
+        LogLevel::Error | LogLevel::Critical => event!(Level::ERROR),
+        LogLevel::Warning => event!(Level::WARN),
+        LogLevel::Message | LogLevel::Info => event!(Level::INFO),
+        LogLevel::Debug => event!(Level::DEBUG),
+    }
+
+    LogWriterOutput::Handled
+}
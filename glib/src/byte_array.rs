@@ -19,6 +19,8 @@ use std::{
     ops::Deref,
     slice,
 };
+#[cfg(feature = "bytes")]
+use std::{mem, ptr};
 
 use crate::{ffi, translate::*};
 
@@ -135,6 +137,208 @@ impl Hash for ByteArray {
         Hash::hash_slice(&self[..], state)
     }
 }
+
+// rustdoc-stripper-ignore-next
+/// A uniquely owned, growable [`ByteArray`] that implements [`bytes::Buf`] and
+/// [`bytes::BufMut`].
+///
+/// `ByteArray` itself is shared, so growing or consuming it through one reference could
+/// reallocate data that is still borrowed through another. This owns the only reference until it
+/// is converted into a `ByteArray`, which keeps the written data that was not read yet.
+///
+/// ```
+/// use bytes::{Buf, BufMut};
+///
+/// let mut buf = glib::ByteArrayBuf::new();
+/// buf.put_u16(42);
+/// buf.put_slice(b"payload");
+/// assert_eq!(buf.get_u16(), 42);
+///
+/// let ba = glib::ByteArray::from(buf);
+/// assert_eq!(ba, b"payload"[..]);
+/// ```
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub struct ByteArrayBuf {
+    array: ptr::NonNull<ffi::GByteArray>,
+    // The data that was written and not read yet. The length of the array is its capacity.
+    start: usize,
+    end: usize,
+}
+
+#[cfg(feature = "bytes")]
+unsafe impl Send for ByteArrayBuf {}
+#[cfg(feature = "bytes")]
+unsafe impl Sync for ByteArrayBuf {}
+
+#[cfg(feature = "bytes")]
+impl ByteArrayBuf {
+    #[doc(alias = "g_byte_array_new")]
+    pub fn new() -> Self {
+        Self::with_capacity(0)
+    }
+
+    #[doc(alias = "g_byte_array_sized_new")]
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = u32::try_from(capacity).expect("Capacity too large");
+        unsafe {
+            Self {
+                array: ptr::NonNull::new_unchecked(ffi::g_byte_array_sized_new(capacity)),
+                start: 0,
+                end: 0,
+            }
+        }
+    }
+
+    fn capacity(&self) -> usize {
+        unsafe { self.array.as_ref().len as usize }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Default for ByteArrayBuf {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Drop for ByteArrayBuf {
+    fn drop(&mut self) {
+        unsafe { ffi::g_byte_array_unref(self.array.as_ptr()) }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Deref for ByteArrayBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        if self.start == self.end {
+            return &[];
+        }
+        unsafe {
+            slice::from_raw_parts(
+                self.array.as_ref().data.add(self.start) as *const u8,
+                self.end - self.start,
+            )
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl AsRef<[u8]> for ByteArrayBuf {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl fmt::Debug for ByteArrayBuf {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.as_ref()).finish()
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl From<ByteArrayBuf> for ByteArray {
+    // rustdoc-stripper-ignore-next
+    /// Takes over the data that was not read yet without copying, unless some data was read.
+    #[doc(alias = "g_byte_array_set_size")]
+    #[doc(alias = "g_byte_array_remove_range")]
+    fn from(buf: ByteArrayBuf) -> Self {
+        let buf = mem::ManuallyDrop::new(buf);
+        unsafe {
+            let array = buf.array.as_ptr();
+            ffi::g_byte_array_set_size(array, buf.end as u32);
+            if buf.start > 0 {
+                ffi::g_byte_array_remove_range(array, 0, buf.start as u32);
+            }
+            from_glib_full(array)
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl From<ByteArrayBuf> for ::bytes::Bytes {
+    // rustdoc-stripper-ignore-next
+    /// Shares the data that was not read yet without copying.
+    #[inline]
+    fn from(buf: ByteArrayBuf) -> Self {
+        ::bytes::Bytes::from_owner(buf)
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl ::bytes::Buf for ByteArrayBuf {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.end - self.start
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.remaining(),
+            "cannot advance past `remaining`: {cnt} <= {}",
+            self.remaining(),
+        );
+        self.start += cnt;
+        // Reuse the space of the read data once everything was read.
+        if self.start == self.end {
+            self.start = 0;
+            self.end = 0;
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+unsafe impl ::bytes::BufMut for ByteArrayBuf {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        u32::MAX as usize - self.end
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.capacity() - self.end,
+            "cannot advance past the chunk: {cnt} <= {}",
+            self.capacity() - self.end,
+        );
+        self.end += cnt;
+    }
+
+    #[doc(alias = "g_byte_array_set_size")]
+    fn chunk_mut(&mut self) -> &mut ::bytes::buf::UninitSlice {
+        if self.end == self.capacity() {
+            let capacity = self.capacity().max(32).saturating_mul(2);
+            // The new bytes are not initialized because the array does not clear them.
+            unsafe {
+                ffi::g_byte_array_set_size(
+                    self.array.as_ptr(),
+                    capacity.min(u32::MAX as usize) as u32,
+                );
+            }
+        }
+        unsafe {
+            ::bytes::buf::UninitSlice::from_raw_parts_mut(
+                self.array.as_ref().data.add(self.end),
+                self.capacity() - self.end,
+            )
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -157,4 +361,33 @@ mod tests {
         assert!(set.contains(&b2));
         assert!(!set.contains(&b3));
     }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn buf() {
+        use ::bytes::{Buf, BufMut};
+
+        let mut buf = ByteArrayBuf::new();
+        assert!(!buf.has_remaining());
+        buf.put_u16(42);
+        buf.put_slice(b"header:payload");
+        assert_eq!(buf.remaining(), 16);
+
+        assert_eq!(buf.get_u16(), 42);
+        let header = buf.copy_to_bytes(7);
+        assert_eq!(header, b"header:"[..]);
+        assert_eq!(buf.chunk(), b"payload");
+
+        let ba = ByteArray::from(buf);
+        assert_eq!(ba, b"payload"[..]);
+
+        let mut buf = ByteArrayBuf::with_capacity(4);
+        let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+        buf.put_slice(&data);
+        assert_eq!(&*buf, &data[..]);
+        buf.advance(1000);
+        assert!(buf.is_empty());
+        buf.put_u8(1);
+        assert_eq!(::bytes::Bytes::from(buf), [1u8][..]);
+    }
 }
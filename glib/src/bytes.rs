@@ -9,6 +9,11 @@ use std::{
     ops::{Bound, Deref, RangeBounds},
     slice,
 };
+#[cfg(feature = "bytes")]
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard, OnceLock},
+};
 
 use crate::{ffi, translate::*};
 
@@ -272,6 +277,148 @@ impl Hash for Bytes {
     }
 }
 
+// The owner of `bytes::Bytes` created from `Bytes`. The `GBytes` are registered by their data,
+// so converting `bytes::Bytes` with the same data back returns them instead of wrapping them again.
+#[cfg(feature = "bytes")]
+struct SharedBytes(Bytes);
+
+// The `GBytes` pointers by the address and length of their data.
+#[cfg(feature = "bytes")]
+type SharedBytesRegistry = HashMap<(usize, usize), Vec<usize>>;
+
+#[cfg(feature = "bytes")]
+impl SharedBytes {
+    fn registry() -> MutexGuard<'static, SharedBytesRegistry> {
+        static REGISTRY: OnceLock<Mutex<SharedBytesRegistry>> = OnceLock::new();
+        REGISTRY.get_or_init(Default::default).lock().unwrap()
+    }
+
+    fn key(data: &[u8]) -> (usize, usize) {
+        (data.as_ptr() as usize, data.len())
+    }
+
+    fn new(bytes: Bytes) -> Self {
+        Self::registry()
+            .entry(Self::key(&bytes))
+            .or_default()
+            .push(bytes.as_ptr() as usize);
+        Self(bytes)
+    }
+
+    fn lookup(data: &[u8]) -> Option<Bytes> {
+        // The data is kept alive by `data`, so all `GBytes` registered for it are still alive.
+        Self::registry()
+            .get(&Self::key(data))
+            .and_then(|bytes| bytes.first())
+            .map(|&ptr| unsafe { from_glib_none(ptr as *mut ffi::GBytes) })
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl AsRef<[u8]> for SharedBytes {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Drop for SharedBytes {
+    fn drop(&mut self) {
+        let key = Self::key(&self.0);
+        let ptr = self.0.as_ptr() as usize;
+        let mut registry = Self::registry();
+        if let Some(bytes) = registry.get_mut(&key) {
+            if let Some(i) = bytes.iter().position(|&p| p == ptr) {
+                bytes.swap_remove(i);
+            }
+            if bytes.is_empty() {
+                registry.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl From<Bytes> for ::bytes::Bytes {
+    // rustdoc-stripper-ignore-next
+    /// Shares the data of `value` without copying.
+    #[inline]
+    fn from(value: Bytes) -> Self {
+        if value.is_empty() {
+            return ::bytes::Bytes::new();
+        }
+        ::bytes::Bytes::from_owner(SharedBytes::new(value))
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl From<::bytes::Bytes> for Bytes {
+    // rustdoc-stripper-ignore-next
+    /// Shares the data of `value` without copying.
+    ///
+    /// If `value` was converted from `Bytes` and still covers all of its data, this returns the
+    /// original `Bytes`.
+    fn from(value: ::bytes::Bytes) -> Self {
+        if value.is_empty() {
+            return Bytes::from_static(b"");
+        }
+        SharedBytes::lookup(&value).unwrap_or_else(|| Bytes::from_owned(value))
+    }
+}
+
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl From<::bytes::BytesMut> for Bytes {
+    // rustdoc-stripper-ignore-next
+    /// Takes over the data of `value` without copying.
+    #[inline]
+    fn from(value: ::bytes::BytesMut) -> Self {
+        Bytes::from_owned(value.freeze())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Reading from a `Bytes` buffer advances it by replacing it with a view into the remaining data,
+/// without copying.
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+impl ::bytes::Buf for Bytes {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn chunk(&self) -> &[u8] {
+        self
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.len(),
+            "cannot advance past `remaining`: {cnt} <= {}",
+            self.len(),
+        );
+        if cnt > 0 {
+            *self = Bytes::from_bytes(self, cnt..);
+        }
+    }
+
+    fn copy_to_bytes(&mut self, len: usize) -> ::bytes::Bytes {
+        assert!(
+            len <= self.len(),
+            "`len` greater than remaining: {len} <= {}",
+            self.len(),
+        );
+        let bytes = Bytes::from_bytes(self, ..len);
+        self.advance(len);
+        bytes.into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashSet;
@@ -350,4 +497,54 @@ mod tests {
         let d = b.into_data();
         assert_eq!(d.as_slice(), b"this is a test");
     }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn bytes_conversions() {
+        let b = Bytes::from_static(b"shared");
+        let gbytes = b.as_ptr();
+        let ptr = (*b).as_ptr();
+
+        let other = ::bytes::Bytes::from(b);
+        assert_eq!(other, b"shared"[..]);
+        assert_eq!(other.as_ptr(), ptr);
+
+        // Converting back returns the original `GBytes`, unless only part of it is converted.
+        let part = Bytes::from(other.slice(1..));
+        assert_eq!(part, b"hared"[..]);
+        assert_ne!(part.as_ptr(), gbytes);
+
+        let b = Bytes::from(other);
+        assert_eq!(b, b"shared"[..]);
+        assert_eq!(b.as_ptr(), gbytes);
+        drop(part);
+        assert!(!SharedBytes::registry().contains_key(&SharedBytes::key(&b)));
+
+        let mut other = ::bytes::BytesMut::new();
+        other.extend_from_slice(b"mutable");
+        let ptr = other.as_ptr();
+        let b = Bytes::from(other);
+        assert_eq!(b, b"mutable"[..]);
+        assert_eq!((*b).as_ptr(), ptr);
+    }
+
+    #[cfg(feature = "bytes")]
+    #[test]
+    fn buf() {
+        use ::bytes::Buf;
+
+        let mut b = Bytes::from(b"\x00\x2aheader:payload");
+        let ptr = (*b).as_ptr();
+
+        assert_eq!(b.get_u16(), 42);
+        assert_eq!(b.remaining(), 14);
+
+        let header = b.copy_to_bytes(7);
+        assert_eq!(header, b"header:"[..]);
+        assert_eq!(header.as_ptr(), ptr.wrapping_add(2));
+
+        assert_eq!(b.chunk(), b"payload");
+        b.advance(7);
+        assert!(!b.has_remaining());
+    }
 }
@@ -1,5 +1,7 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+use std::{hash::Hasher, io};
+
 use libc::size_t;
 
 use crate::{Checksum, ffi, translate::*};
@@ -35,6 +37,159 @@ impl Checksum {
     }
 }
 
+impl Hasher for Checksum {
+    // rustdoc-stripper-ignore-next
+    /// Returns the first 8 bytes of the digest of the data written so far as little-endian
+    /// integer.
+    fn finish(&self) -> u64 {
+        let digest = self.clone().digest();
+        u64::from_le_bytes(digest[..8].try_into().unwrap())
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+}
+
+impl io::Write for Checksum {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+impl digest::Update for Checksum {
+    fn update(&mut self, data: &[u8]) {
+        Checksum::update(self, data);
+    }
+}
+
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+impl digest::Reset for Checksum {
+    fn reset(&mut self) {
+        Checksum::reset(self);
+    }
+}
+
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+impl digest::DynDigest for Checksum {
+    fn update(&mut self, data: &[u8]) {
+        Checksum::update(self, data);
+    }
+
+    fn finalize_into(self, buf: &mut [u8]) -> Result<(), digest::InvalidBufferSize> {
+        let digest = self.digest();
+        if buf.len() != digest.len() {
+            return Err(digest::InvalidBufferSize);
+        }
+        buf.copy_from_slice(&digest);
+        Ok(())
+    }
+
+    fn finalize_into_reset(&mut self, buf: &mut [u8]) -> Result<(), digest::InvalidBufferSize> {
+        let res = digest::DynDigest::finalize_into(self.clone(), buf);
+        Checksum::reset(self);
+        res
+    }
+
+    fn reset(&mut self) {
+        Checksum::reset(self);
+    }
+
+    fn output_size(&self) -> usize {
+        self.clone().digest().len()
+    }
+
+    fn box_clone(&self) -> Box<dyn digest::DynDigest> {
+        Box::new(self.clone())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Digests with a fixed checksum type, implementing the RustCrypto [`digest::Digest`] trait.
+///
+/// ```
+/// use digest::Digest;
+///
+/// let digest = glib::digests::Sha256::digest(b"hello world!");
+/// assert_eq!(digest[..4], [0x75, 0x09, 0xe5, 0xbd]);
+/// ```
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub mod digests {
+    use digest::{
+        FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update,
+        consts::{U16, U20, U32, U48, U64},
+    };
+
+    use crate::{Checksum, ChecksumType};
+
+    macro_rules! define_digest {
+        ($name:ident, $checksum_type:ident, $output_size:ty) => {
+            #[doc = concat!("[`ChecksumType::", stringify!($checksum_type), "`] digest.")]
+            #[derive(Debug, Clone)]
+            pub struct $name(Checksum);
+
+            impl Default for $name {
+                fn default() -> Self {
+                    Self(Checksum::new(ChecksumType::$checksum_type).unwrap())
+                }
+            }
+
+            impl HashMarker for $name {}
+
+            impl OutputSizeUser for $name {
+                type OutputSize = $output_size;
+            }
+
+            impl Update for $name {
+                fn update(&mut self, data: &[u8]) {
+                    self.0.update(data);
+                }
+            }
+
+            impl Reset for $name {
+                fn reset(&mut self) {
+                    self.0.reset();
+                }
+            }
+
+            impl FixedOutput for $name {
+                fn finalize_into(self, out: &mut Output<Self>) {
+                    out.copy_from_slice(&self.0.digest());
+                }
+            }
+
+            impl FixedOutputReset for $name {
+                fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                    out.copy_from_slice(&self.0.clone().digest());
+                    self.0.reset();
+                }
+            }
+
+            impl From<$name> for Checksum {
+                fn from(digest: $name) -> Self {
+                    digest.0
+                }
+            }
+        };
+    }
+
+    define_digest!(Md5, Md5, U16);
+    define_digest!(Sha1, Sha1, U20);
+    define_digest!(Sha256, Sha256, U32);
+    define_digest!(Sha384, Sha384, U48);
+    define_digest!(Sha512, Sha512, U64);
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{Checksum, ChecksumType};
@@ -69,4 +224,51 @@ mod tests {
         let vec = cs.digest();
         assert_eq!(vec, CS_SLICE);
     }
+
+    #[test]
+    fn write() {
+        let mut cs = Checksum::new(CS_TYPE).unwrap();
+        std::io::copy(&mut &b"hello world!"[..], &mut cs).unwrap();
+        assert_eq!(cs.string().unwrap(), CS_VALUE);
+    }
+
+    #[test]
+    fn hasher() {
+        use std::hash::{Hash, Hasher};
+
+        let mut cs = Checksum::new(CS_TYPE).unwrap();
+        cs.write(b"hello world!");
+        assert_eq!(
+            cs.finish(),
+            u64::from_le_bytes(CS_SLICE[..8].try_into().unwrap())
+        );
+        // Finishing doesn't reset the state.
+        assert_eq!(cs.string().unwrap(), CS_VALUE);
+
+        let mut a = Checksum::new(CS_TYPE).unwrap();
+        let mut b = Checksum::new(CS_TYPE).unwrap();
+        ("hello", 42u32).hash(&mut a);
+        ("hello", 42u32).hash(&mut b);
+        assert_eq!(a.finish(), b.finish());
+    }
+
+    #[cfg(feature = "digest")]
+    #[test]
+    fn digest_traits() {
+        use digest::{Digest, DynDigest};
+
+        let mut digest = super::digests::Md5::new();
+        Digest::update(&mut digest, b"hello ");
+        Digest::update(&mut digest, b"world!");
+        assert_eq!(&Digest::finalize_reset(&mut digest)[..], CS_SLICE);
+        assert_eq!(
+            &Digest::finalize(digest)[..],
+            Checksum::new(CS_TYPE).unwrap().digest()
+        );
+
+        let mut dyn_digest: Box<dyn DynDigest> = Box::new(Checksum::new(CS_TYPE).unwrap());
+        dyn_digest.update(b"hello world!");
+        assert_eq!(dyn_digest.output_size(), CS_SLICE.len());
+        assert_eq!(&*dyn_digest.finalize(), CS_SLICE);
+    }
 }
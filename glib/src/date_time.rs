@@ -1,5 +1,139 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+use crate::{BoolError, DateTime, TimeSpan, TimeZone};
+
+#[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+impl DateTime {
+    // Microseconds since the unix epoch.
+    fn unix_usec(&self) -> i64 {
+        self.to_unix() * 1_000_000 + i64::from(self.microsecond())
+    }
+
+    fn utc_offset_seconds(&self) -> i32 {
+        (self.utc_offset().as_microseconds() / 1_000_000) as i32
+    }
+
+    fn from_unix_usec_in(usecs: i64, tz: &TimeZone) -> Result<Self, BoolError> {
+        Self::from_unix_utc(usecs.div_euclid(1_000_000))?
+            .add(TimeSpan::from_microseconds(usecs.rem_euclid(1_000_000)))?
+            .to_timezone(tz)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts the date and time with its UTC offset.
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl From<&DateTime> for chrono::DateTime<chrono::FixedOffset> {
+    fn from(dt: &DateTime) -> Self {
+        // The range of `GDateTime` is a subset of the range of chrono.
+        let offset = chrono::FixedOffset::east_opt(dt.utc_offset_seconds()).unwrap();
+        chrono::DateTime::from_timestamp_micros(dt.unix_usec())
+            .unwrap()
+            .with_timezone(&offset)
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl From<&DateTime> for chrono::DateTime<chrono::Utc> {
+    fn from(dt: &DateTime) -> Self {
+        chrono::DateTime::from_timestamp_micros(dt.unix_usec()).unwrap()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts the date and time with a time zone with the fixed UTC offset of `dt`. Nanoseconds are
+/// truncated to microseconds.
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl<Tz: chrono::TimeZone> TryFrom<chrono::DateTime<Tz>> for DateTime {
+    type Error = BoolError;
+
+    fn try_from(dt: chrono::DateTime<Tz>) -> Result<Self, Self::Error> {
+        use chrono::Offset;
+
+        let tz = TimeZone::from_offset_seconds(dt.offset().fix().local_minus_utc());
+        Self::from_unix_usec_in(dt.timestamp_micros(), &tz)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts the date and time with its UTC offset.
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl From<&DateTime> for time::OffsetDateTime {
+    fn from(dt: &DateTime) -> Self {
+        // The range of `GDateTime` is a subset of the range of time.
+        let offset = time::UtcOffset::from_whole_seconds(dt.utc_offset_seconds()).unwrap();
+        time::OffsetDateTime::from_unix_timestamp_nanos(i128::from(dt.unix_usec()) * 1000)
+            .unwrap()
+            .to_offset(offset)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts the date and time with a time zone with the UTC offset of `dt`. Nanoseconds are
+/// truncated to microseconds.
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl TryFrom<time::OffsetDateTime> for DateTime {
+    type Error = BoolError;
+
+    fn try_from(dt: time::OffsetDateTime) -> Result<Self, Self::Error> {
+        let usecs = i64::try_from(dt.unix_timestamp_nanos().div_euclid(1000))
+            .map_err(|_| crate::bool_error!("Date out of range"))?;
+        Self::from_unix_usec_in(usecs, &TimeZone::from(dt.offset()))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts the date and time with its time zone if the time zone has an IANA identifier that
+/// is known to jiff, otherwise with its UTC offset.
+#[cfg(feature = "jiff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+impl From<&DateTime> for jiff::Zoned {
+    fn from(dt: &DateTime) -> Self {
+        // The range of `GDateTime` is a subset of the range of jiff.
+        let timestamp = jiff::Timestamp::from_microsecond(dt.unix_usec()).unwrap();
+
+        #[cfg(feature = "v2_58")]
+        if let Ok(tz) = jiff::tz::TimeZone::try_from(&dt.timezone()) {
+            return timestamp.to_zoned(tz);
+        }
+
+        timestamp.to_zoned(jiff::tz::TimeZone::fixed(
+            jiff::tz::Offset::from_seconds(dt.utc_offset_seconds()).unwrap(),
+        ))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts the date and time with its time zone if it has an IANA identifier, otherwise with
+/// its UTC offset. Nanoseconds are truncated to microseconds.
+#[cfg(feature = "jiff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+impl TryFrom<&jiff::Zoned> for DateTime {
+    type Error = BoolError;
+
+    fn try_from(zoned: &jiff::Zoned) -> Result<Self, Self::Error> {
+        let tz = TimeZone::try_from(zoned.time_zone())
+            .unwrap_or_else(|_| TimeZone::from_offset_seconds(zoned.offset().seconds()));
+        Self::from_unix_usec_in(zoned.timestamp().as_microsecond(), &tz)
+    }
+}
+
+#[cfg(feature = "jiff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+impl TryFrom<jiff::Zoned> for DateTime {
+    type Error = BoolError;
+
+    fn try_from(zoned: jiff::Zoned) -> Result<Self, Self::Error> {
+        Self::try_from(&zoned)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{DateTime, prelude::*};
@@ -12,4 +146,71 @@ mod tests {
 
         assert_eq!(dt1.as_ptr(), dt2.as_ptr());
     }
+
+    // 2024-02-29T13:44:15.123456+01:30
+    #[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+    const USECS: i64 = 1_709_208_855_123_456;
+    #[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+    const OFFSET: i32 = 5400;
+
+    #[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+    fn date_time() -> DateTime {
+        let tz = crate::TimeZone::from_offset_seconds(OFFSET);
+        DateTime::from_unix_usec_in(USECS, &tz).unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn chrono() {
+        let dt = date_time();
+        assert_eq!((dt.hour(), dt.minute(), dt.microsecond()), (13, 44, 123456));
+
+        let converted = chrono::DateTime::<chrono::FixedOffset>::from(&dt);
+        assert_eq!(converted.timestamp_micros(), USECS);
+        assert_eq!(converted.offset().local_minus_utc(), OFFSET);
+        assert_eq!(
+            chrono::DateTime::<chrono::Utc>::from(&dt).timestamp_micros(),
+            USECS
+        );
+
+        let back = DateTime::try_from(converted).unwrap();
+        assert_eq!(back, dt);
+        assert_eq!(back.utc_offset(), dt.utc_offset());
+        assert_eq!(back.microsecond(), 123456);
+    }
+
+    #[cfg(feature = "time")]
+    #[test]
+    fn time() {
+        let dt = date_time();
+
+        let converted = time::OffsetDateTime::from(&dt);
+        assert_eq!(converted.unix_timestamp_nanos(), i128::from(USECS) * 1000);
+        assert_eq!(converted.offset().whole_seconds(), OFFSET);
+        assert_eq!(converted.hour(), 13);
+
+        let back = DateTime::try_from(converted).unwrap();
+        assert_eq!(back, dt);
+        assert_eq!(back.utc_offset(), dt.utc_offset());
+    }
+
+    #[cfg(feature = "jiff")]
+    #[test]
+    fn jiff() {
+        let dt = date_time();
+
+        let converted = jiff::Zoned::from(&dt);
+        assert_eq!(converted.timestamp().as_microsecond(), USECS);
+        assert_eq!(converted.offset().seconds(), OFFSET);
+
+        let back = DateTime::try_from(&converted).unwrap();
+        assert_eq!(back, dt);
+        assert_eq!(back.utc_offset(), dt.utc_offset());
+
+        let utc = DateTime::from_unix_usec_in(USECS, &crate::TimeZone::utc()).unwrap();
+        let converted = jiff::Zoned::from(&utc);
+        #[cfg(feature = "v2_58")]
+        assert_eq!(converted.time_zone().iana_name(), Some("UTC"));
+        assert_eq!(DateTime::try_from(converted).unwrap(), utc);
+    }
 }
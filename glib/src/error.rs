@@ -179,6 +179,18 @@ pub trait ErrorDomain: Copy {
     fn from(code: i32) -> Option<Self>
     where
         Self: Sized;
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the D-Bus error names of the codes of the domain.
+    ///
+    /// gio uses these to map errors of the domain to and from D-Bus errors, see
+    /// `gio::DBusError::register_error_domain()`. By default, no codes are mapped.
+    fn dbus_error_names() -> &'static [(i32, &'static str)]
+    where
+        Self: Sized,
+    {
+        &[]
+    }
 }
 
 // rustdoc-stripper-ignore-next
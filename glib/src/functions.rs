@@ -9,6 +9,9 @@ use std::mem;
 #[cfg(feature = "v2_58")]
 use std::os::unix::io::{AsFd, AsRawFd};
 #[cfg(not(windows))]
+#[cfg(feature = "v2_68")]
+use std::os::unix::io::{BorrowedFd, RawFd};
+#[cfg(not(windows))]
 use std::os::unix::io::{FromRawFd, OwnedFd};
 use std::ptr;
 
@@ -62,7 +65,7 @@ pub fn spawn_async_with_fds<P: AsRef<std::path::Path>>(
             flags.into_glib(),
             child_setup,
             Box_::into_raw(super_callback0) as *mut _,
-            child_pid.as_mut_ptr(),
+            &mut child_pid,
             stdin_raw_fd,
             stdout_raw_fd,
             stderr_raw_fd,
@@ -119,7 +122,7 @@ pub fn spawn_async_with_fds<P: AsRef<std::path::Path>>(
 //             flags.into_glib(),
 //             child_setup,
 //             Box_::into_raw(super_callback0) as *mut _,
-//             child_pid.as_mut_ptr(),
+//             &mut child_pid,
 //             stdin_fd.as_raw_handle() as usize as _,
 //             stdout_fd.as_raw_handle() as usize as _,
 //             stderr_fd.as_raw_handle() as usize as _,
@@ -177,7 +180,7 @@ pub fn spawn_async_with_pipes<
             flags.into_glib(),
             child_setup,
             Box_::into_raw(super_callback0) as *mut _,
-            child_pid.as_mut_ptr(),
+            &mut child_pid,
             standard_input.as_mut_ptr(),
             standard_output.as_mut_ptr(),
             standard_error.as_mut_ptr(),
@@ -213,6 +216,176 @@ pub fn spawn_async_with_pipes<
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Spawns a child process, optionally redirecting its standard streams to `stdin_fd`,
+/// `stdout_fd` and `stderr_fd` and passing additional file descriptors.
+///
+/// Each of `source_fds` is duplicated to the descriptor with the same index in `target_fds` in the
+/// child. Descriptors of the parent are not affected and can be closed after this returns.
+///
+/// For each standard stream without a descriptor a pipe is returned, unless `flags` inherit it
+/// or redirect it to `/dev/null`. Pipes can be wrapped with `gio_unix::InputStream::take_fd()`
+/// and `gio_unix::OutputStream::take_fd()` for asynchronous I/O.
+///
+/// If `envp` is `None`, the child inherits the environment of the parent.
+///
+/// See [`SpawnCommand`](crate::SpawnCommand) for a more convenient API.
+#[cfg(feature = "v2_68")]
+#[cfg(not(windows))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "v2_68", not(windows)))))]
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+#[doc(alias = "g_spawn_async_with_pipes_and_fds")]
+pub fn spawn_async_with_pipes_and_fds(
+    working_directory: Option<impl AsRef<std::path::Path>>,
+    argv: &[&std::ffi::OsStr],
+    envp: Option<&[&std::ffi::OsStr]>,
+    flags: SpawnFlags,
+    child_setup: Option<Box_<dyn FnOnce() + 'static>>,
+    stdin_fd: Option<BorrowedFd<'_>>,
+    stdout_fd: Option<BorrowedFd<'_>>,
+    stderr_fd: Option<BorrowedFd<'_>>,
+    source_fds: &[BorrowedFd<'_>],
+    target_fds: &[RawFd],
+) -> Result<(Pid, Option<OwnedFd>, Option<OwnedFd>, Option<OwnedFd>), Error> {
+    let [stdin_flags, stdout_flags, stderr_flags] = spawn_stdio_flags();
+    let stdin_pipe = stdin_fd.is_none() && !flags.intersects(stdin_flags);
+    let stdout_pipe = stdout_fd.is_none() && !flags.intersects(stdout_flags);
+    let stderr_pipe = stderr_fd.is_none() && !flags.intersects(stderr_flags);
+    spawn_with_pipes_and_fds(
+        working_directory.as_ref().map(|dir| dir.as_ref()),
+        argv,
+        envp,
+        flags,
+        child_setup,
+        [stdin_fd, stdout_fd, stderr_fd],
+        [stdin_pipe, stdout_pipe, stderr_pipe],
+        source_fds,
+        target_fds,
+    )
+}
+
+// Like `spawn_async_with_pipes_and_fds()`, but with explicit control over which pipes are created.
+#[cfg(feature = "v2_68")]
+#[cfg(not(windows))]
+#[allow(clippy::too_many_arguments)]
+#[allow(clippy::type_complexity)]
+pub(crate) fn spawn_with_pipes_and_fds(
+    working_directory: Option<&std::path::Path>,
+    argv: &[&std::ffi::OsStr],
+    envp: Option<&[&std::ffi::OsStr]>,
+    flags: SpawnFlags,
+    child_setup: Option<Box_<dyn FnOnce() + 'static>>,
+    [stdin_fd, stdout_fd, stderr_fd]: [Option<BorrowedFd<'_>>; 3],
+    [stdin_pipe, stdout_pipe, stderr_pipe]: [bool; 3],
+    source_fds: &[BorrowedFd<'_>],
+    target_fds: &[RawFd],
+) -> Result<(Pid, Option<OwnedFd>, Option<OwnedFd>, Option<OwnedFd>), Error> {
+    assert_eq!(
+        source_fds.len(),
+        target_fds.len(),
+        "source_fds and target_fds must have the same length"
+    );
+
+    // The child gets a copy of the closure, the one of the parent is dropped normally.
+    unsafe extern "C" fn child_setup_func(user_data: ffi::gpointer) {
+        unsafe {
+            let callback = &mut *(user_data as *mut Option<Box_<dyn FnOnce() + 'static>>);
+            if let Some(callback) = callback.take() {
+                callback()
+            }
+        }
+    }
+    let mut child_setup = child_setup;
+    let (child_setup_func, child_setup_data) = if child_setup.is_some() {
+        (
+            Some(child_setup_func as _),
+            &mut child_setup as *mut _ as ffi::gpointer,
+        )
+    } else {
+        (None, ptr::null_mut())
+    };
+
+    let source_fds = source_fds
+        .iter()
+        .map(|fd| fd.as_raw_fd())
+        .collect::<Vec<_>>();
+
+    unsafe {
+        let mut child_pid = 0;
+        let mut stdin_pipe_out = -1;
+        let mut stdout_pipe_out = -1;
+        let mut stderr_pipe_out = -1;
+        let mut error = ptr::null_mut();
+        let ret: bool = from_glib(ffi::g_spawn_async_with_pipes_and_fds(
+            working_directory.to_glib_none().0,
+            argv.to_glib_none().0,
+            envp.to_glib_none().0,
+            flags.into_glib(),
+            child_setup_func,
+            child_setup_data,
+            stdin_fd.map_or(-1, |fd| fd.as_raw_fd()),
+            stdout_fd.map_or(-1, |fd| fd.as_raw_fd()),
+            stderr_fd.map_or(-1, |fd| fd.as_raw_fd()),
+            source_fds.as_ptr(),
+            target_fds.as_ptr(),
+            source_fds.len(),
+            &mut child_pid,
+            if stdin_pipe {
+                &mut stdin_pipe_out as *mut _
+            } else {
+                ptr::null_mut()
+            },
+            if stdout_pipe {
+                &mut stdout_pipe_out as *mut _
+            } else {
+                ptr::null_mut()
+            },
+            if stderr_pipe {
+                &mut stderr_pipe_out as *mut _
+            } else {
+                ptr::null_mut()
+            },
+            &mut error,
+        ));
+        if ret {
+            let pipe = |fd: RawFd| (fd >= 0).then(|| OwnedFd::from_raw_fd(fd));
+            Ok((
+                from_glib(child_pid),
+                pipe(stdin_pipe_out),
+                pipe(stdout_pipe_out),
+                pipe(stderr_pipe_out),
+            ))
+        } else if error.is_null() {
+            // GLib returns without an error if a precondition fails, e.g. for conflicting flags.
+            Err(Error::with_domain(
+                from_glib(ffi::g_spawn_error_quark()),
+                ffi::G_SPAWN_ERROR_INVAL,
+                "Invalid arguments for spawning the child",
+            ))
+        } else {
+            Err(from_glib_full(error))
+        }
+    }
+}
+
+// The flags that redirect standard input, output and error, which conflict with a pipe or file
+// descriptor for the same stream.
+#[cfg(feature = "v2_68")]
+#[cfg(not(windows))]
+pub(crate) fn spawn_stdio_flags() -> [SpawnFlags; 3] {
+    let stdin = SpawnFlags::CHILD_INHERITS_STDIN;
+    let stdout = SpawnFlags::STDOUT_TO_DEV_NULL;
+    let stderr = SpawnFlags::STDERR_TO_DEV_NULL;
+    #[cfg(feature = "v2_74")]
+    let (stdin, stdout, stderr) = (
+        stdin | SpawnFlags::STDIN_FROM_DEV_NULL,
+        stdout | SpawnFlags::CHILD_INHERITS_STDOUT,
+        stderr | SpawnFlags::CHILD_INHERITS_STDERR,
+    );
+    [stdin, stdout, stderr]
+}
+
 // rustdoc-stripper-ignore-next
 /// Obtain the character set for the current locale.
 ///
@@ -0,0 +1,136 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::io;
+
+use libc::size_t;
+
+use crate::{ChecksumType, ffi, translate::*};
+
+crate::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// Keyed-hash message authentication code computed with one of the [`ChecksumType`]s.
+    ///
+    /// ```
+    /// let mut hmac = glib::Hmac::new(glib::ChecksumType::Sha256, b"key").unwrap();
+    /// hmac.update(b"The quick brown fox jumps over the lazy dog");
+    /// assert_eq!(
+    ///     hmac.string().unwrap(),
+    ///     "f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+    /// );
+    /// ```
+    #[derive(Debug)]
+    #[doc(alias = "GHmac")]
+    pub struct Hmac(Boxed<ffi::GHmac>);
+
+    match fn {
+        copy => |ptr| ffi::g_hmac_copy(ptr),
+        free => |ptr| ffi::g_hmac_unref(ptr),
+        type_ => || ffi::g_hmac_get_type(),
+    }
+}
+
+impl Hmac {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new HMAC with `key`. Returns `None` if `digest_type` is not supported.
+    #[doc(alias = "g_hmac_new")]
+    pub fn new(digest_type: ChecksumType, key: &[u8]) -> Option<Hmac> {
+        unsafe {
+            from_glib_full(ffi::g_hmac_new(
+                digest_type.into_glib(),
+                key.as_ptr(),
+                key.len(),
+            ))
+        }
+    }
+
+    #[doc(alias = "g_hmac_update")]
+    pub fn update(&mut self, data: &[u8]) {
+        unsafe {
+            ffi::g_hmac_update(
+                self.to_glib_none_mut().0,
+                data.as_ptr(),
+                data.len() as isize,
+            );
+        }
+    }
+
+    #[doc(alias = "g_hmac_get_digest")]
+    #[doc(alias = "get_digest")]
+    pub fn digest(self) -> Vec<u8> {
+        unsafe {
+            //Don't forget update when `ChecksumType` contains type bigger that Sha512.
+            let mut digest_len: size_t = 512 / 8;
+            let mut vec = Vec::with_capacity(digest_len as _);
+
+            ffi::g_hmac_get_digest(
+                mut_override(self.to_glib_none().0),
+                vec.as_mut_ptr(),
+                &mut digest_len,
+            );
+
+            vec.set_len(digest_len);
+            vec
+        }
+    }
+
+    #[doc(alias = "g_hmac_get_string")]
+    #[doc(alias = "get_string")]
+    pub fn string(self) -> Option<String> {
+        unsafe { from_glib_none(ffi::g_hmac_get_string(mut_override(self.to_glib_none().0))) }
+    }
+}
+
+impl io::Write for Hmac {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+impl digest::Update for Hmac {
+    fn update(&mut self, data: &[u8]) {
+        Hmac::update(self, data);
+    }
+}
+
+unsafe impl Send for Hmac {}
+unsafe impl Sync for Hmac {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test case 2 of RFC 4231.
+    const KEY: &[u8] = b"Jefe";
+    const DATA: &[u8] = b"what do ya want for nothing?";
+    const SHA256_VALUE: &str = "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843";
+
+    #[test]
+    fn update() {
+        let mut hmac = Hmac::new(ChecksumType::Sha256, KEY).unwrap();
+        hmac.update(&DATA[..10]);
+        hmac.update(&DATA[10..]);
+        assert_eq!(hmac.string().unwrap(), SHA256_VALUE);
+    }
+
+    #[test]
+    fn digest() {
+        let mut hmac = Hmac::new(ChecksumType::Sha256, KEY).unwrap();
+        std::io::copy(&mut &DATA[..], &mut hmac).unwrap();
+
+        // The digest can be retrieved from a copy while the original is updated further.
+        let copy = hmac.clone();
+        hmac.update(b"more");
+
+        let digest = copy.digest();
+        assert_eq!(digest.len(), 32);
+        assert_eq!(&digest[..4], [0x5b, 0xdc, 0xc1, 0x46]);
+        assert_ne!(hmac.string().unwrap(), SHA256_VALUE);
+    }
+}
@@ -279,4 +279,41 @@ impl KeyFile {
             }
         }
     }
+
+    #[doc(alias = "g_key_file_set_string_list")]
+    pub fn set_string_list(&self, group_name: &str, key: &str, list: impl IntoStrV) {
+        unsafe {
+            list.run_with_strv(|list| {
+                ffi::g_key_file_set_string_list(
+                    self.to_glib_none().0,
+                    group_name.to_glib_none().0,
+                    key.to_glib_none().0,
+                    list.as_ptr() as *const _,
+                    list.len(),
+                );
+            })
+        }
+    }
+
+    #[doc(alias = "g_key_file_set_locale_string_list")]
+    pub fn set_locale_string_list(
+        &self,
+        group_name: &str,
+        key: &str,
+        locale: &str,
+        list: impl IntoStrV,
+    ) {
+        unsafe {
+            list.run_with_strv(|list| {
+                ffi::g_key_file_set_locale_string_list(
+                    self.to_glib_none().0,
+                    group_name.to_glib_none().0,
+                    key.to_glib_none().0,
+                    locale.to_glib_none().0,
+                    list.as_ptr() as *const _,
+                    list.len(),
+                );
+            })
+        }
+    }
 }
@@ -0,0 +1,1870 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{collections::BTreeMap, fmt, marker::PhantomData, ptr, str::FromStr};
+
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{
+        self, DeserializeOwned, DeserializeSeed, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+    },
+    ser::{self, Impossible, SerializeMap, SerializeSeq, SerializeStruct, SerializeTuple},
+};
+
+use crate::{Error, GString, KeyFile, KeyFileError, ffi, translate::*};
+
+// Names of the newtype structs the (de)serializers handle specially.
+const LOCALIZED: &str = "$glib::KeyFileLocalized";
+const COMMENTED: &str = "$glib::KeyFileCommented";
+
+// Error of the (de)serializers, converted to the wrapped `Error` by the `KeyFile` methods. This
+// keeps the serde error traits off the public `Error` type.
+#[derive(Debug)]
+struct SerdeError(Error);
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for SerdeError {}
+
+impl From<Error> for SerdeError {
+    fn from(err: Error) -> Self {
+        Self(err)
+    }
+}
+
+fn invalid_value(message: &str) -> SerdeError {
+    SerdeError(Error::new(KeyFileError::InvalidValue, message))
+}
+
+impl ser::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        invalid_value(&msg.to_string())
+    }
+}
+
+impl de::Error for SerdeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        invalid_value(&msg.to_string())
+    }
+
+    fn missing_field(field: &'static str) -> Self {
+        SerdeError(Error::new(
+            KeyFileError::KeyNotFound,
+            &format!("Missing group or key `{field}`"),
+        ))
+    }
+}
+
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+impl KeyFile {
+    // rustdoc-stripper-ignore-next
+    /// Deserializes the key file into a value.
+    ///
+    /// The value has to be a struct or map whose fields are groups, which in turn are structs or
+    /// maps whose fields are keys. Keys can be strings, numbers, booleans, unit enum variants or
+    /// lists of those. Use [`KeyFileLocalized`] for keys with translations and
+    /// [`KeyFileCommented`] to access the comments of groups and keys.
+    ///
+    /// Missing groups and keys can be handled with `Option` fields, unknown ones are ignored.
+    /// Translations of keys, i.e. `Name[de]`, are not passed on as unknown fields of structs.
+    /// An empty key is `Some` empty string, but `Option` elements of lists can't be told apart
+    /// from empty strings and empty elements are always `None`.
+    ///
+    /// ```
+    /// #[derive(serde::Deserialize)]
+    /// struct DesktopFile {
+    ///     #[serde(rename = "Desktop Entry")]
+    ///     entry: DesktopEntry,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// #[serde(rename_all = "PascalCase")]
+    /// struct DesktopEntry {
+    ///     name: glib::KeyFileLocalized,
+    ///     exec: Option<String>,
+    ///     categories: Vec<String>,
+    ///     terminal: bool,
+    /// }
+    ///
+    /// let key_file = glib::KeyFile::new();
+    /// key_file
+    ///     .load_from_data(
+    ///         "[Desktop Entry]\n\
+    ///          Name=Files\n\
+    ///          Name[de]=Dateien\n\
+    ///          Categories=GNOME;Utility;\n\
+    ///          Terminal=false\n",
+    ///         glib::KeyFileFlags::NONE,
+    ///     )
+    ///     .unwrap();
+    ///
+    /// let desktop_file: DesktopFile = key_file.to_serde().unwrap();
+    /// assert_eq!(desktop_file.entry.name.translations["de"], "Dateien");
+    /// assert_eq!(desktop_file.entry.exec, None);
+    /// assert_eq!(desktop_file.entry.categories, ["GNOME", "Utility"]);
+    /// ```
+    pub fn to_serde<T: DeserializeOwned>(&self) -> Result<T, Error> {
+        T::deserialize(KeyFileDeserializer { key_file: self }).map_err(|err| err.0)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Serializes a value into the key file, see [`to_serde`](Self::to_serde) for the
+    /// supported values.
+    ///
+    /// Groups and keys that already exist are updated in place, so comments, the order of
+    /// groups and keys and groups and keys that are not part of the value are preserved. New
+    /// groups and keys are appended in the order of the fields. Fields that are `None` remove
+    /// the corresponding group or key, and translations that are missing from a
+    /// [`KeyFileLocalized`] are removed.
+    pub fn from_serde<T: Serialize + ?Sized>(&self, value: &T) -> Result<(), Error> {
+        value
+            .serialize(KeyFileSerializer { key_file: self })
+            .map_err(|err| err.0)
+    }
+
+    // `g_key_file_get_comment()` returns `NULL` without error if there is no comment.
+    fn comment_opt(&self, group_name: &str, key: Option<&str>) -> Option<GString> {
+        unsafe {
+            let ret = ffi::g_key_file_get_comment(
+                self.to_glib_none().0,
+                group_name.to_glib_none().0,
+                key.to_glib_none().0,
+                ptr::null_mut(),
+            );
+            Option::<GString>::from_glib_full(ret)
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A key file value with translations, i.e. `Name=Files` and `Name[de]=Dateien`.
+///
+/// When used with other serde formats, the value is represented as a map from locales to
+/// values, with the untranslated value stored under the empty locale.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyFileLocalized<T = String> {
+    pub value: T,
+    // rustdoc-stripper-ignore-next
+    /// Translations by locale, e.g. `de` or `pt_BR`.
+    pub translations: BTreeMap<String, T>,
+}
+
+impl<T> KeyFileLocalized<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            value,
+            translations: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for KeyFileLocalized<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct Entries<'a, T>(&'a KeyFileLocalized<T>);
+
+        impl<T: Serialize> Serialize for Entries<'_, T> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.0.translations.len() + 1))?;
+                map.serialize_entry("", &self.0.value)?;
+                for (locale, value) in &self.0.translations {
+                    map.serialize_entry(locale, value)?;
+                }
+                map.end()
+            }
+        }
+
+        serializer.serialize_newtype_struct(LOCALIZED, &Entries(self))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for KeyFileLocalized<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct LocalizedVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for LocalizedVisitor<T> {
+            type Value = KeyFileLocalized<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a localized value")
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                let mut translations = BTreeMap::<String, T>::deserialize(deserializer)?;
+                let value = translations
+                    .remove("")
+                    .ok_or_else(|| de::Error::custom("missing untranslated value"))?;
+                Ok(KeyFileLocalized {
+                    value,
+                    translations,
+                })
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(LOCALIZED, LocalizedVisitor(PhantomData))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A key file group or key together with the comment above it.
+///
+/// When serializing, the comment replaces the existing comment, or removes it if it is `None`.
+/// Each line of the comment is written with a leading `#`.
+///
+/// When used with other serde formats, the value is represented as a tuple of comment and value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyFileCommented<T> {
+    pub comment: Option<String>,
+    pub value: T,
+}
+
+impl<T> KeyFileCommented<T> {
+    pub fn new(comment: Option<String>, value: T) -> Self {
+        Self { comment, value }
+    }
+}
+
+impl<T: Serialize> Serialize for KeyFileCommented<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_newtype_struct(COMMENTED, &(&self.comment, &self.value))
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for KeyFileCommented<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct CommentedVisitor<T>(PhantomData<T>);
+
+        impl<'de, T: Deserialize<'de>> Visitor<'de> for CommentedVisitor<T> {
+            type Value = KeyFileCommented<T>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a commented value")
+            }
+
+            fn visit_newtype_struct<D: Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                let (comment, value) = <(Option<String>, T)>::deserialize(deserializer)?;
+                Ok(KeyFileCommented { comment, value })
+            }
+        }
+
+        deserializer.deserialize_newtype_struct(COMMENTED, CommentedVisitor(PhantomData))
+    }
+}
+
+// Implements the serializer methods for scalar values by converting them to a string with
+// `ValueSerializer` and passing it to `serialize_value()`.
+macro_rules! serialize_scalars {
+    ($($method:ident($ty:ty)),* $(,)?) => {
+        $(
+            fn $method(self, v: $ty) -> Result<(), SerdeError> {
+                let value = ValueSerializer.$method(v)?;
+                self.serialize_value(value)
+            }
+        )*
+    };
+}
+
+macro_rules! impl_serialize_scalars {
+    () => {
+        serialize_scalars!(
+            serialize_bool(bool),
+            serialize_i8(i8),
+            serialize_i16(i16),
+            serialize_i32(i32),
+            serialize_i64(i64),
+            serialize_u8(u8),
+            serialize_u16(u16),
+            serialize_u32(u32),
+            serialize_u64(u64),
+            serialize_f32(f32),
+            serialize_f64(f64),
+            serialize_char(char),
+            serialize_str(&str),
+        );
+    };
+}
+
+// Serializes scalar values to their string representation in a key file. `None` is serialized as
+// empty string.
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = String;
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<String, SerdeError>;
+    type SerializeTuple = Impossible<String, SerdeError>;
+    type SerializeTupleStruct = Impossible<String, SerdeError>;
+    type SerializeTupleVariant = Impossible<String, SerdeError>;
+    type SerializeMap = Impossible<String, SerdeError>;
+    type SerializeStruct = Impossible<String, SerdeError>;
+    type SerializeStructVariant = Impossible<String, SerdeError>;
+
+    fn serialize_bool(self, v: bool) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, SerdeError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_str(self, v: &str) -> Result<String, SerdeError> {
+        Ok(v.to_owned())
+    }
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, SerdeError> {
+        Err(invalid_value("Byte arrays are not supported in key files"))
+    }
+
+    fn serialize_none(self) -> Result<String, SerdeError> {
+        Ok(String::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, SerdeError> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, SerdeError> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<String, SerdeError> {
+        Ok(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, SerdeError> {
+        Err(nested_value())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(nested_value())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(nested_value())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(nested_value())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(nested_value())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Err(nested_value())
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(nested_value())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(nested_value())
+    }
+}
+
+fn nested_value() -> SerdeError {
+    invalid_value("Nested values are not supported in key files")
+}
+
+// Serializes the whole key file, i.e. a struct or map of groups.
+struct KeyFileSerializer<'a> {
+    key_file: &'a KeyFile,
+}
+
+impl KeyFileSerializer<'_> {
+    fn unsupported(&self) -> SerdeError {
+        invalid_value("Key files can only be serialized from structs or maps")
+    }
+
+    fn serialize_value(self, _value: String) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+}
+
+impl<'a> Serializer for KeyFileSerializer<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<(), SerdeError>;
+    type SerializeTuple = Impossible<(), SerdeError>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = EntriesSerializer<'a>;
+    type SerializeStruct = EntriesSerializer<'a>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    impl_serialize_scalars!();
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerdeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Ok(EntriesSerializer::new(self.key_file, Entries::Groups))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerdeError> {
+        Ok(EntriesSerializer::new(self.key_file, Entries::Groups))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(self.unsupported())
+    }
+}
+
+// Serializes a group, i.e. a struct or map of keys.
+struct GroupSerializer<'a> {
+    key_file: &'a KeyFile,
+    group: String,
+    // Whether a `KeyFileCommented` is being serialized.
+    commented: bool,
+}
+
+impl GroupSerializer<'_> {
+    fn unsupported(&self) -> SerdeError {
+        invalid_value(&format!(
+            "Group `{}` can only be serialized from a struct or map",
+            self.group
+        ))
+    }
+
+    fn serialize_value(self, _value: String) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+}
+
+impl<'a> Serializer for GroupSerializer<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = Impossible<(), SerdeError>;
+    type SerializeTuple = CommentedSerializer<'a>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = EntriesSerializer<'a>;
+    type SerializeStruct = EntriesSerializer<'a>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    impl_serialize_scalars!();
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        let _ = self.key_file.remove_group(&self.group);
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        if name == COMMENTED {
+            value.serialize(GroupSerializer {
+                commented: true,
+                ..self
+            })
+        } else {
+            value.serialize(self)
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        if self.commented {
+            Ok(CommentedSerializer {
+                key_file: self.key_file,
+                group: self.group,
+                key: None,
+                comment: None,
+            })
+        } else {
+            Err(self.unsupported())
+        }
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        Ok(EntriesSerializer::new(
+            self.key_file,
+            Entries::Keys { group: self.group },
+        ))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerdeError> {
+        Ok(EntriesSerializer::new(
+            self.key_file,
+            Entries::Keys { group: self.group },
+        ))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(self.unsupported())
+    }
+}
+
+// Serializes the value of a key.
+struct KeySerializer<'a> {
+    key_file: &'a KeyFile,
+    group: String,
+    key: String,
+    // Whether a `KeyFileLocalized` is being serialized.
+    localized: bool,
+    // Whether a `KeyFileCommented` is being serialized.
+    commented: bool,
+}
+
+impl<'a> KeySerializer<'a> {
+    fn new(key_file: &'a KeyFile, group: String, key: String) -> Self {
+        Self {
+            key_file,
+            group,
+            key,
+            localized: false,
+            commented: false,
+        }
+    }
+
+    fn serialize_value(self, value: String) -> Result<(), SerdeError> {
+        self.key_file.set_string(&self.group, &self.key, &value);
+        Ok(())
+    }
+
+    fn unsupported(&self) -> SerdeError {
+        invalid_value(&format!(
+            "Key `{}` in group `{}` can only be serialized from a scalar or a sequence of scalars",
+            self.key, self.group
+        ))
+    }
+}
+
+impl<'a> Serializer for KeySerializer<'a> {
+    type Ok = ();
+    type Error = SerdeError;
+    type SerializeSeq = ListSerializer<'a>;
+    type SerializeTuple = KeyTupleSerializer<'a>;
+    type SerializeTupleStruct = Impossible<(), SerdeError>;
+    type SerializeTupleVariant = Impossible<(), SerdeError>;
+    type SerializeMap = EntriesSerializer<'a>;
+    type SerializeStruct = Impossible<(), SerdeError>;
+    type SerializeStructVariant = Impossible<(), SerdeError>;
+
+    impl_serialize_scalars!();
+
+    fn serialize_bytes(self, _v: &[u8]) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_none(self) -> Result<(), SerdeError> {
+        // Remove the translations together with the untranslated value.
+        let prefix = format!("{}[", self.key);
+        if let Ok(keys) = self.key_file.keys(&self.group) {
+            for key in keys.iter() {
+                if key.as_str() == self.key || key.starts_with(&prefix) {
+                    let _ = self.key_file.remove_key(&self.group, key.as_str());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<(), SerdeError> {
+        self.serialize_value(String::new())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<(), SerdeError> {
+        self.serialize_value(String::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerdeError> {
+        self.serialize_value(variant.to_owned())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        match name {
+            LOCALIZED => value.serialize(KeySerializer {
+                localized: true,
+                ..self
+            }),
+            COMMENTED => value.serialize(KeySerializer {
+                commented: true,
+                ..self
+            }),
+            _ => value.serialize(self),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, SerdeError> {
+        Ok(ListSerializer {
+            key_file: self.key_file,
+            group: self.group,
+            key: self.key,
+            values: Vec::with_capacity(len.unwrap_or_default()),
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, SerdeError> {
+        if self.commented {
+            Ok(KeyTupleSerializer::Commented(CommentedSerializer {
+                key_file: self.key_file,
+                group: self.group,
+                key: Some(self.key),
+                comment: None,
+            }))
+        } else {
+            self.serialize_seq(Some(len)).map(KeyTupleSerializer::List)
+        }
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerdeError> {
+        if self.localized {
+            Ok(EntriesSerializer::new(
+                self.key_file,
+                Entries::Locales {
+                    group: self.group,
+                    key: self.key,
+                },
+            ))
+        } else {
+            Err(self.unsupported())
+        }
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerdeError> {
+        Err(self.unsupported())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerdeError> {
+        Err(self.unsupported())
+    }
+}
+
+enum Entries {
+    // The entries are groups of the key file.
+    Groups,
+    // The entries are keys of a group.
+    Keys { group: String },
+    // The entries are translations of a key, by locale.
+    Locales { group: String, key: String },
+}
+
+// Serializes structs and maps as groups, keys or translations.
+struct EntriesSerializer<'a> {
+    key_file: &'a KeyFile,
+    entries: Entries,
+    pending_name: Option<String>,
+    // Names of the serialized map entries, to remove stale translations.
+    names: Vec<String>,
+}
+
+impl<'a> EntriesSerializer<'a> {
+    fn new(key_file: &'a KeyFile, entries: Entries) -> Self {
+        Self {
+            key_file,
+            entries,
+            pending_name: None,
+            names: Vec::new(),
+        }
+    }
+
+    fn serialize_entry_value<T: Serialize + ?Sized>(
+        &self,
+        name: String,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        match &self.entries {
+            Entries::Groups => value.serialize(GroupSerializer {
+                key_file: self.key_file,
+                group: name,
+                commented: false,
+            }),
+            Entries::Keys { group } => {
+                value.serialize(KeySerializer::new(self.key_file, group.clone(), name))
+            }
+            Entries::Locales { group, key } => {
+                let key = if name.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{key}[{name}]")
+                };
+                value.serialize(KeySerializer::new(self.key_file, group.clone(), key))
+            }
+        }
+    }
+}
+
+impl SerializeStruct for EntriesSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerdeError> {
+        self.serialize_entry_value(key.to_owned(), value)
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        Ok(())
+    }
+}
+
+impl SerializeMap for EntriesSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        self.pending_name = Some(key.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let name = self
+            .pending_name
+            .take()
+            .expect("serialize_value() called before serialize_key()");
+        self.serialize_entry_value(name.clone(), value)?;
+        self.names.push(name);
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        // Remove translations that are not part of the map anymore.
+        if let Entries::Locales { group, key } = &self.entries {
+            let prefix = format!("{key}[");
+            for name in self.key_file.keys(group)?.iter() {
+                let stale = name
+                    .strip_prefix(&prefix)
+                    .and_then(|locale| locale.strip_suffix(']'))
+                    .is_some_and(|locale| !self.names.iter().any(|written| written == locale));
+                if stale {
+                    self.key_file.remove_key(group, name)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// Serializes sequences as lists.
+struct ListSerializer<'a> {
+    key_file: &'a KeyFile,
+    group: String,
+    key: String,
+    values: Vec<String>,
+}
+
+impl SerializeSeq for ListSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.values.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        self.key_file
+            .set_string_list(&self.group, &self.key, self.values);
+        Ok(())
+    }
+}
+
+// Serializes the `(comment, value)` tuple of a `KeyFileCommented`.
+struct CommentedSerializer<'a> {
+    key_file: &'a KeyFile,
+    group: String,
+    key: Option<String>,
+    comment: Option<String>,
+}
+
+impl SerializeTuple for CommentedSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        // The comment is always serialized to a string, `None` to an empty one.
+        if self.comment.is_none() {
+            self.comment = Some(value.serialize(ValueSerializer)?);
+            return Ok(());
+        }
+
+        match &self.key {
+            Some(key) => value.serialize(KeySerializer::new(
+                self.key_file,
+                self.group.clone(),
+                key.clone(),
+            )),
+            None => value.serialize(GroupSerializer {
+                key_file: self.key_file,
+                group: self.group.clone(),
+                commented: false,
+            }),
+        }
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        // The value may have removed the group or key.
+        let exists = match &self.key {
+            Some(key) => self.key_file.has_key(&self.group, key).unwrap_or(false),
+            None => self.key_file.has_group(&self.group),
+        };
+        if !exists {
+            return Ok(());
+        }
+
+        match self.comment.as_deref() {
+            Some(comment) if !comment.is_empty() => {
+                self.key_file
+                    .set_comment(Some(&self.group), self.key.as_deref(), comment)
+            }
+            _ => {
+                let _ = self
+                    .key_file
+                    .remove_comment(Some(&self.group), self.key.as_deref());
+                Ok(())
+            }
+        }
+    }
+}
+
+// A tuple as value of a key is either a list or a `KeyFileCommented`.
+enum KeyTupleSerializer<'a> {
+    List(ListSerializer<'a>),
+    Commented(CommentedSerializer<'a>),
+}
+
+impl SerializeTuple for KeyTupleSerializer<'_> {
+    type Ok = ();
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        match self {
+            Self::List(serializer) => serializer.serialize_element(value),
+            Self::Commented(serializer) => SerializeTuple::serialize_element(serializer, value),
+        }
+    }
+
+    fn end(self) -> Result<(), SerdeError> {
+        match self {
+            Self::List(serializer) => SerializeSeq::end(serializer),
+            Self::Commented(serializer) => SerializeTuple::end(serializer),
+        }
+    }
+}
+
+// Deserializes scalar values from their string representation in a key file. An empty string is
+// deserialized as `None`.
+struct ValueDeserializer(String);
+
+impl ValueDeserializer {
+    fn parse<T: FromStr>(&self) -> Result<T, SerdeError>
+    where
+        T::Err: fmt::Display,
+    {
+        self.0
+            .trim()
+            .parse()
+            .map_err(|err| invalid_value(&format!("Invalid value `{}`: {err}", self.0)))
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+                visitor.$visit(self.parse()?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for ValueDeserializer {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_string(self.0)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        match self.0.trim() {
+            "true" | "1" => visitor.visit_bool(true),
+            "false" | "0" => visitor.visit_bool(false),
+            _ => Err(invalid_value(&format!(
+                "Invalid boolean value `{}`",
+                self.0
+            ))),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_enum(de::value::StringDeserializer::<SerdeError>::new(self.0))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        str string bytes byte_buf seq tuple tuple_struct map struct identifier
+    }
+}
+
+impl IntoDeserializer<'_, SerdeError> for ValueDeserializer {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+// Deserializes the whole key file, i.e. a struct or map of groups.
+struct KeyFileDeserializer<'a> {
+    key_file: &'a KeyFile,
+}
+
+impl<'de> Deserializer<'de> for KeyFileDeserializer<'_> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let groups = self.key_file.groups();
+        visitor.visit_map(EntriesAccess {
+            key_file: self.key_file,
+            entries: Entries::Groups,
+            names: groups.iter().map(|group| group.to_string()).collect(),
+            pos: 0,
+        })
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct enum identifier ignored_any
+    }
+}
+
+// Deserializes a group, i.e. a struct or map of keys.
+struct GroupDeserializer<'a> {
+    key_file: &'a KeyFile,
+    group: String,
+}
+
+impl GroupDeserializer<'_> {
+    fn keys(&self, include_translations: bool) -> Result<Vec<String>, SerdeError> {
+        Ok(self
+            .key_file
+            .keys(&self.group)?
+            .iter()
+            .map(|key| key.to_string())
+            .filter(|key| include_translations || !key.contains('['))
+            .collect())
+    }
+}
+
+impl<'de> Deserializer<'de> for GroupDeserializer<'_> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_map(EntriesAccess {
+            key_file: self.key_file,
+            names: self.keys(true)?,
+            entries: Entries::Keys { group: self.group },
+            pos: 0,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        visitor.visit_map(EntriesAccess {
+            key_file: self.key_file,
+            names: self.keys(false)?,
+            entries: Entries::Keys { group: self.group },
+            pos: 0,
+        })
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        if name == COMMENTED {
+            visitor.visit_newtype_struct(CommentedDeserializer {
+                comment: self.key_file.comment_opt(&self.group, None),
+                inner: self,
+            })
+        } else {
+            visitor.visit_newtype_struct(self)
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map enum identifier
+    }
+}
+
+// Deserializes the value of a key.
+struct KeyDeserializer<'a> {
+    key_file: &'a KeyFile,
+    group: String,
+    key: String,
+}
+
+impl KeyDeserializer<'_> {
+    fn value(&self) -> Result<ValueDeserializer, SerdeError> {
+        Ok(ValueDeserializer(
+            self.key_file.string(&self.group, &self.key)?.into(),
+        ))
+    }
+}
+
+macro_rules! deserialize_value {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+                self.value()?.$method(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for KeyDeserializer<'_> {
+    type Error = SerdeError;
+
+    deserialize_value! {
+        deserialize_any deserialize_bool deserialize_i8 deserialize_i16 deserialize_i32
+        deserialize_i64 deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_f32 deserialize_f64 deserialize_char deserialize_str deserialize_string
+        deserialize_bytes deserialize_byte_buf deserialize_unit deserialize_identifier
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.value()?.deserialize_unit_struct(name, visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        match name {
+            LOCALIZED => visitor.visit_newtype_struct(LocalizedDeserializer(self)),
+            COMMENTED => visitor.visit_newtype_struct(CommentedDeserializer {
+                comment: self.key_file.comment_opt(&self.group, Some(&self.key)),
+                inner: self,
+            }),
+            _ => visitor.visit_newtype_struct(self),
+        }
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let list = self.key_file.string_list(&self.group, &self.key)?;
+        let mut seq = de::value::SeqDeserializer::<_, SerdeError>::new(
+            list.iter()
+                .map(|value| ValueDeserializer(value.to_string())),
+        );
+        let value = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(value)
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, SerdeError> {
+        self.value()?.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        i128 u128 map struct
+    }
+}
+
+// Deserializes the translations of a key as map by locale, with the untranslated value under
+// the empty locale.
+struct LocalizedDeserializer<'a>(KeyDeserializer<'a>);
+
+impl<'de> Deserializer<'de> for LocalizedDeserializer<'_> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        let KeyDeserializer {
+            key_file,
+            group,
+            key,
+        } = self.0;
+
+        let prefix = format!("{key}[");
+        let mut names = vec![String::new()];
+        names.extend(key_file.keys(&group)?.iter().filter_map(|k| {
+            k.strip_prefix(&prefix)
+                .and_then(|locale| locale.strip_suffix(']'))
+                .map(str::to_owned)
+        }));
+
+        visitor.visit_map(EntriesAccess {
+            key_file,
+            entries: Entries::Locales { group, key },
+            names,
+            pos: 0,
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+// Deserializes the `(comment, value)` tuple of a `KeyFileCommented`.
+struct CommentedDeserializer<D> {
+    comment: Option<GString>,
+    inner: D,
+}
+
+impl<'de, D: Deserializer<'de, Error = SerdeError>> Deserializer<'de> for CommentedDeserializer<D> {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, SerdeError> {
+        // GLib strips the `#` of each line but keeps the line breaks.
+        let comment = self
+            .comment
+            .as_deref()
+            .map(|comment| comment.trim_end_matches('\n').to_owned())
+            .unwrap_or_default();
+
+        visitor.visit_seq(CommentedAccess {
+            comment: Some(comment),
+            inner: Some(self.inner),
+        })
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+        option unit unit_struct newtype_struct seq tuple tuple_struct map struct enum
+        identifier ignored_any
+    }
+}
+
+struct CommentedAccess<D> {
+    comment: Option<String>,
+    inner: Option<D>,
+}
+
+impl<'de, D: Deserializer<'de, Error = SerdeError>> SeqAccess<'de> for CommentedAccess<D> {
+    type Error = SerdeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, SerdeError> {
+        if let Some(comment) = self.comment.take() {
+            return seed.deserialize(ValueDeserializer(comment)).map(Some);
+        }
+        match self.inner.take() {
+            Some(inner) => seed.deserialize(inner).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.comment.is_some() as usize + self.inner.is_some() as usize)
+    }
+}
+
+// Gives access to groups, keys or translations as entries of a map.
+struct EntriesAccess<'a> {
+    key_file: &'a KeyFile,
+    entries: Entries,
+    names: Vec<String>,
+    pos: usize,
+}
+
+impl<'de> MapAccess<'de> for EntriesAccess<'_> {
+    type Error = SerdeError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, SerdeError> {
+        let Some(name) = self.names.get(self.pos) else {
+            return Ok(None);
+        };
+        seed.deserialize(ValueDeserializer(name.clone())).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, SerdeError> {
+        let name = self.names[self.pos].clone();
+        self.pos += 1;
+
+        match &self.entries {
+            Entries::Groups => seed.deserialize(GroupDeserializer {
+                key_file: self.key_file,
+                group: name,
+            }),
+            Entries::Keys { group } => seed.deserialize(KeyDeserializer {
+                key_file: self.key_file,
+                group: group.clone(),
+                key: name,
+            }),
+            Entries::Locales { group, key } => seed.deserialize(KeyDeserializer {
+                key_file: self.key_file,
+                group: group.clone(),
+                key: if name.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{key}[{name}]")
+                },
+            }),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.names.len() - self.pos)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+    use crate::KeyFileFlags;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    enum EntryType {
+        Application,
+        Link,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct DesktopEntry {
+        r#type: EntryType,
+        name: KeyFileLocalized,
+        keywords: Option<KeyFileLocalized<Vec<String>>>,
+        exec: KeyFileCommented<String>,
+        terminal: bool,
+        #[serde(rename = "X-Priority")]
+        priority: i32,
+        #[serde(rename = "X-Scale")]
+        scale: f64,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct DesktopFile {
+        #[serde(rename = "Desktop Entry")]
+        entry: DesktopEntry,
+        #[serde(rename = "Desktop Action new-window")]
+        new_window: Option<BTreeMap<String, String>>,
+    }
+
+    const DATA: &str = "\
+# The application
+[Desktop Entry]
+Type=Application
+Name=Files
+Name[de]=Dateien
+Keywords=folder;manager;
+Keywords[de]=Ordner;Verwaltung;
+# Start it
+Exec=nautilus --new-window %U
+X-Unknown=kept
+Terminal=false
+X-Priority=-3
+X-Scale=1.5
+
+[Desktop Action new-window]
+Name=New Window
+Exec=nautilus --new-window
+";
+
+    fn load() -> KeyFile {
+        let key_file = KeyFile::new();
+        key_file
+            .load_from_data(
+                DATA,
+                KeyFileFlags::KEEP_COMMENTS | KeyFileFlags::KEEP_TRANSLATIONS,
+            )
+            .unwrap();
+        key_file
+    }
+
+    #[test]
+    fn deserialize() {
+        let desktop_file: DesktopFile = load().to_serde().unwrap();
+
+        let entry = &desktop_file.entry;
+        assert_eq!(entry.r#type, EntryType::Application);
+        assert_eq!(entry.name.value, "Files");
+        assert_eq!(
+            entry.name.translations,
+            BTreeMap::from([("de".to_owned(), "Dateien".to_owned())])
+        );
+        let keywords = entry.keywords.as_ref().unwrap();
+        assert_eq!(keywords.value, ["folder", "manager"]);
+        assert_eq!(keywords.translations["de"], ["Ordner", "Verwaltung"]);
+        assert_eq!(entry.exec.comment.as_deref(), Some(" Start it"));
+        assert_eq!(entry.exec.value, "nautilus --new-window %U");
+        assert!(!entry.terminal);
+        assert_eq!(entry.priority, -3);
+        assert_eq!(entry.scale, 1.5);
+
+        let new_window = desktop_file.new_window.unwrap();
+        assert_eq!(new_window["Name"], "New Window");
+        assert_eq!(new_window.len(), 2);
+    }
+
+    #[test]
+    fn update_in_place() {
+        let key_file = load();
+        let mut desktop_file: DesktopFile = key_file.to_serde().unwrap();
+        desktop_file.entry.terminal = true;
+        desktop_file
+            .entry
+            .name
+            .translations
+            .insert("fr".to_owned(), "Fichiers".to_owned());
+        desktop_file.entry.exec.comment = Some(" Start it; with a window".to_owned());
+        desktop_file.entry.keywords = None;
+        desktop_file.new_window = None;
+
+        key_file.from_serde(&desktop_file).unwrap();
+        assert!(!key_file.has_group("Desktop Action new-window"));
+        assert_eq!(
+            key_file
+                .keys("Desktop Entry")
+                .unwrap()
+                .iter()
+                .map(|key| key.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "Type",
+                "Name",
+                "Name[de]",
+                "Exec",
+                "X-Unknown",
+                "Terminal",
+                "X-Priority",
+                "X-Scale",
+                "Name[fr]",
+            ]
+        );
+        assert_eq!(
+            key_file.string("Desktop Entry", "Terminal").unwrap(),
+            "true"
+        );
+        let data = key_file.to_data();
+        assert!(data.starts_with("# The application\n"));
+        assert!(data.contains("# Start it; with a window\nExec=nautilus --new-window %U\n"));
+
+        let reloaded: DesktopFile = key_file.to_serde().unwrap();
+        assert_eq!(reloaded.entry.name.translations.len(), 2);
+        assert_eq!(reloaded.entry.keywords, None);
+        assert_eq!(reloaded.new_window, None);
+    }
+
+    #[test]
+    fn remove_translations() {
+        let key_file = load();
+        let mut desktop_file: DesktopFile = key_file.to_serde().unwrap();
+        desktop_file.entry.name.translations.clear();
+        desktop_file
+            .entry
+            .keywords
+            .as_mut()
+            .unwrap()
+            .translations
+            .insert("fr".to_owned(), vec!["dossier".to_owned()]);
+        desktop_file
+            .entry
+            .keywords
+            .as_mut()
+            .unwrap()
+            .translations
+            .remove("de");
+
+        key_file.from_serde(&desktop_file).unwrap();
+        assert!(!key_file.has_key("Desktop Entry", "Name[de]").unwrap());
+        assert!(!key_file.has_key("Desktop Entry", "Keywords[de]").unwrap());
+        assert!(key_file.has_key("Desktop Entry", "Keywords[fr]").unwrap());
+        assert_eq!(key_file.to_serde::<DesktopFile>().unwrap(), desktop_file);
+    }
+
+    #[test]
+    fn empty_values() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Group {
+            value: Option<String>,
+            list: Vec<Option<String>>,
+        }
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct File {
+            group: Group,
+        }
+
+        let file = File {
+            group: Group {
+                value: Some(String::new()),
+                list: vec![Some("a".to_owned()), Some(String::new()), None],
+            },
+        };
+        let key_file = KeyFile::new();
+        key_file.from_serde(&file).unwrap();
+        assert_eq!(key_file.string("group", "value").unwrap(), "");
+
+        // Empty keys are `Some`, empty list elements are `None`.
+        let reloaded: File = key_file.to_serde().unwrap();
+        assert_eq!(reloaded.group.value, Some(String::new()));
+        assert_eq!(reloaded.group.list, [Some("a".to_owned()), None, None]);
+    }
+
+    #[test]
+    fn roundtrip_new() {
+        let desktop_file = DesktopFile {
+            entry: DesktopEntry {
+                r#type: EntryType::Link,
+                name: KeyFileLocalized::new("Docs; and more".to_owned()),
+                keywords: Some(KeyFileLocalized::new(vec![
+                    "a;b".to_owned(),
+                    "c".to_owned(),
+                ])),
+                exec: KeyFileCommented::new(None, "xdg-open".to_owned()),
+                terminal: false,
+                priority: 7,
+                scale: 0.1,
+            },
+            new_window: Some(BTreeMap::from([("Name".to_owned(), "\tTab".to_owned())])),
+        };
+
+        let key_file = KeyFile::new();
+        key_file.from_serde(&desktop_file).unwrap();
+        assert_eq!(key_file.to_serde::<DesktopFile>().unwrap(), desktop_file);
+
+        let reloaded = KeyFile::new();
+        reloaded
+            .load_from_data(&key_file.to_data(), KeyFileFlags::NONE)
+            .unwrap();
+        assert_eq!(reloaded.to_serde::<DesktopFile>().unwrap(), desktop_file);
+    }
+
+    #[test]
+    fn errors() {
+        #[derive(Debug, Deserialize)]
+        struct Group {
+            #[serde(rename = "X-Priority")]
+            _priority: u8,
+        }
+        #[derive(Debug, Deserialize)]
+        struct File {
+            #[serde(rename = "Desktop Entry")]
+            _entry: Group,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Missing {
+            #[serde(rename = "Missing")]
+            _missing: Group,
+        }
+
+        let key_file = load();
+        let err = key_file.to_serde::<File>().unwrap_err();
+        assert!(err.matches(KeyFileError::InvalidValue));
+        let err = key_file.to_serde::<Missing>().unwrap_err();
+        assert!(err.matches(KeyFileError::KeyNotFound));
+
+        let err = key_file.from_serde(&42).unwrap_err();
+        assert!(err.matches(KeyFileError::InvalidValue));
+    }
+}
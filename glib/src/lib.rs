@@ -33,13 +33,17 @@ pub use bitflags;
 #[doc(hidden)]
 pub use glib_macros::cstr_bytes;
 pub use glib_macros::{
-    Boxed, Downgrade, Enum, ErrorDomain, Properties, SharedBoxed, ValueDelegate, Variant,
-    async_test, clone, closure, closure_local, derived_properties, flags, object_interface,
-    object_subclass,
+    Boxed, Downgrade, Enum, ErrorDomain, Fundamental, Properties, SharedBoxed, ValueDelegate,
+    Variant, async_test, clone, closure, closure_local, derived_properties, flags,
+    object_interface, object_subclass,
 };
 pub use glib_sys as ffi;
 pub use gobject_sys as gobject_ffi;
 
+#[cfg(feature = "bytes")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bytes")))]
+pub use self::byte_array::ByteArrayBuf;
+
 pub use self::{
     FileError,
     byte_array::ByteArray,
@@ -119,6 +123,8 @@ pub mod object;
 
 mod boxed_any_object;
 pub use boxed_any_object::BoxedAnyObject;
+mod weak_ref_collections;
+pub use weak_ref_collections::{WeakRefMap, WeakRefSet, WeakRefVec};
 mod exit_code;
 pub use exit_code::{ExitCode, InvalidExitCode};
 
@@ -146,6 +152,11 @@ pub use self::gobject::{BindingGroup, BindingGroupBuilder};
 
 mod gobject;
 
+mod hmac;
+pub use self::hmac::Hmac;
+
+mod base64;
+pub use self::base64::{Base64DecodeReader, Base64Decoder, Base64EncodeWriter, Base64Encoder};
 mod byte_array;
 mod bytes;
 mod control_flow;
@@ -153,13 +164,35 @@ pub use self::control_flow::ControlFlow;
 pub mod char;
 pub use self::char::{Char, UChar};
 mod checksum;
+#[cfg(feature = "digest")]
+#[cfg_attr(docsrs, doc(cfg(feature = "digest")))]
+pub use self::checksum::digests;
 pub mod closure;
 mod convert;
 pub use self::convert::*;
 pub mod enums;
 mod functions;
 pub use self::functions::*;
+#[cfg(all(feature = "v2_68", not(windows)))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "v2_68", not(windows)))))]
+mod spawn_command;
+#[cfg(all(feature = "v2_68", not(windows)))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "v2_68", not(windows)))))]
+pub use self::spawn_command::{SpawnCommand, SpawnStdio, SpawnedChild};
 mod key_file;
+#[cfg(feature = "serde")]
+mod key_file_serde;
+#[cfg(feature = "serde")]
+#[cfg_attr(docsrs, doc(cfg(feature = "serde")))]
+pub use self::key_file_serde::{KeyFileCommented, KeyFileLocalized};
+mod markup_parse_context;
+pub use self::markup_parse_context::{
+    MarkupAttributes, MarkupCollector, MarkupEvent, MarkupEvents, MarkupParser,
+};
+mod option_context;
+pub use self::option_context::{
+    OptionContext, OptionEntries, OptionEntry, OptionEntryBuilder, OptionGroup,
+};
 pub mod prelude;
 pub mod signal;
 pub mod source;
@@ -184,6 +217,12 @@ mod date_time;
 mod time_span;
 mod time_zone;
 pub use self::time_span::TimeSpan;
+#[cfg(feature = "v2_66")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v2_66")))]
+mod uri;
+#[cfg(feature = "v2_66")]
+#[cfg_attr(docsrs, doc(cfg(feature = "v2_66")))]
+pub use self::uri::{UriBuilder, UriParamsIter};
 pub mod value;
 pub mod variant;
 mod variant_dict;
@@ -208,11 +247,12 @@ mod log;
 pub use rs_log;
 
 pub use self::log::{
-    LogField, LogHandlerId, LogLevel, LogLevels, log_default_handler, log_remove_handler,
-    log_set_always_fatal, log_set_default_handler, log_set_fatal_mask, log_set_handler,
-    log_set_writer_func, log_structured_array, log_unset_default_handler, log_variant,
-    log_writer_default, log_writer_format_fields, log_writer_journald, log_writer_standard_streams,
-    set_print_handler, set_printerr_handler, unset_print_handler, unset_printerr_handler,
+    LogField, LogHandlerId, LogLevel, LogLevels, LogRouter, log_default_handler,
+    log_remove_handler, log_set_always_fatal, log_set_default_handler, log_set_fatal_mask,
+    log_set_handler, log_set_writer_func, log_structured_array, log_unset_default_handler,
+    log_variant, log_writer_default, log_writer_format_fields, log_writer_journald,
+    log_writer_standard_streams, set_print_handler, set_printerr_handler, unset_print_handler,
+    unset_printerr_handler,
 };
 #[cfg(feature = "v2_68")]
 pub use self::log::{log_writer_default_set_use_stderr, log_writer_default_would_drop};
@@ -229,6 +269,13 @@ pub use self::bridged_logging::{
     GlibLogger, GlibLoggerDomain, GlibLoggerFormat, rust_log_handler, rust_log_writer,
 };
 
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+mod bridged_tracing;
+#[cfg(feature = "tracing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tracing")))]
+pub use self::bridged_tracing::{GlibTracingLayer, tracing_log_writer};
+
 #[macro_use]
 pub mod subclass;
 
@@ -247,10 +294,12 @@ mod future_with_timeout;
 pub use self::future_with_timeout::*;
 
 mod thread_pool;
-pub use self::thread_pool::{ThreadHandle, ThreadPool};
+pub use self::thread_pool::{ScopedThreadHandle, ThreadHandle, ThreadPool, ThreadPoolScope};
 
 pub mod thread_guard;
 
+pub mod test;
+
 // rustdoc-stripper-ignore-next
 /// This is the log domain used by the [`clone!`][crate::clone!] macro. If you want to use a custom
 /// logger (it prints to stdout by default), you can set your own logger using the corresponding
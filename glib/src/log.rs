@@ -4,10 +4,12 @@
 use std::os::unix::io::{AsFd, AsRawFd};
 use std::{
     boxed::Box as Box_,
+    collections::HashMap,
     sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
 };
 
-use crate::{GStr, GString, LogWriterOutput, ffi, translate::*};
+use crate::{BoolError, GStr, GString, LogWriterOutput, ffi, gstr, translate::*};
 
 #[derive(Debug)]
 pub struct LogHandlerId(u32);
@@ -1091,3 +1093,502 @@ pub fn log_writer_default_would_drop(log_level: LogLevel, log_domain: Option<&st
         ))
     }
 }
+
+// Returns `true` if messages of `level` pass a filter with `min_level` as minimum level.
+fn level_passes(level: LogLevel, min_level: LogLevel) -> bool {
+    fn severity(level: LogLevel) -> u8 {
+        match level {
+            LogLevel::Error => 0,
+            LogLevel::Critical => 1,
+            LogLevel::Warning => 2,
+            LogLevel::Message => 3,
+            LogLevel::Info => 4,
+            LogLevel::Debug => 5,
+        }
+    }
+    severity(level) <= severity(min_level)
+}
+
+fn parse_log_level(s: &str) -> Option<LogLevel> {
+    match s.to_ascii_lowercase().as_str() {
+        "error" => Some(LogLevel::Error),
+        "critical" => Some(LogLevel::Critical),
+        "warning" => Some(LogLevel::Warning),
+        "message" => Some(LogLevel::Message),
+        "info" => Some(LogLevel::Info),
+        "debug" => Some(LogLevel::Debug),
+        _ => None,
+    }
+}
+
+// Matches `s` against a glob pattern where `*` matches any sequence of characters and `?`
+// matches any single character.
+fn glob_matches(pattern: &str, s: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let s = s.chars().collect::<Vec<_>>();
+    let (mut p, mut i) = (0, 0);
+    // Position after the last `*` in the pattern and the position in `s` it was matched at.
+    let mut backtrack = None;
+
+    while i < s.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                p += 1;
+                backtrack = Some((p, i));
+            }
+            Some(&c) if c == '?' || c == s[i] => {
+                p += 1;
+                i += 1;
+            }
+            _ => match backtrack {
+                Some((star_p, star_i)) => {
+                    p = star_p;
+                    i = star_i + 1;
+                    backtrack = Some((star_p, star_i + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogRoute {
+    // A rule or the debug flag of the router allows the message.
+    Pass,
+    // A rule filters out the message.
+    Drop,
+    // No rule applies, GLib's default filtering is used.
+    Default,
+}
+
+#[derive(Debug)]
+struct RateLimitEntry {
+    window_start: Instant,
+    count: u32,
+    suppressed: u32,
+}
+
+#[derive(Debug, Default)]
+struct LogRouterState {
+    rules: Vec<(String, LogLevel)>,
+    debug_enabled: bool,
+    rate_limit: Option<(u32, Duration)>,
+    recent: HashMap<(Option<String>, Vec<u8>), RateLimitEntry>,
+}
+
+impl LogRouterState {
+    // Upper bound for the number of messages that are remembered for rate limiting.
+    const MAX_RECENT: usize = 1024;
+
+    fn route(&self, log_level: LogLevel, log_domain: Option<&str>) -> LogRoute {
+        // Errors are always fatal, so there is no point in dropping them.
+        if log_level == LogLevel::Error || self.debug_enabled {
+            return LogRoute::Pass;
+        }
+
+        let log_domain = log_domain.unwrap_or_default();
+        match self
+            .rules
+            .iter()
+            .rev()
+            .find(|(pattern, _)| glob_matches(pattern, log_domain))
+        {
+            Some(&(_, min_level)) if level_passes(log_level, min_level) => LogRoute::Pass,
+            Some(_) => LogRoute::Drop,
+            None => LogRoute::Default,
+        }
+    }
+
+    // Returns `None` if the message has to be dropped, otherwise the number of repetitions of
+    // the message that were suppressed before.
+    fn rate_limit(
+        &mut self,
+        log_level: LogLevel,
+        log_domain: Option<&str>,
+        message: &[u8],
+        now: Instant,
+    ) -> Option<u32> {
+        let Some((burst, interval)) = self.rate_limit else {
+            return Some(0);
+        };
+        if log_level == LogLevel::Error {
+            return Some(0);
+        }
+
+        let key = (log_domain.map(str::to_owned), message.to_owned());
+        if self.recent.len() >= Self::MAX_RECENT && !self.recent.contains_key(&key) {
+            self.recent
+                .retain(|_, entry| now.duration_since(entry.window_start) < interval);
+
+            // All remembered messages are still rate-limited, forget the oldest half of them.
+            if self.recent.len() >= Self::MAX_RECENT {
+                let mut oldest = self
+                    .recent
+                    .iter()
+                    .map(|(key, entry)| (entry.window_start, key.clone()))
+                    .collect::<Vec<_>>();
+                oldest.sort_unstable_by_key(|(window_start, _)| *window_start);
+                for (_, key) in oldest.drain(..self.recent.len() - Self::MAX_RECENT / 2) {
+                    self.recent.remove(&key);
+                }
+            }
+        }
+
+        let entry = self.recent.entry(key).or_insert(RateLimitEntry {
+            window_start: now,
+            count: 0,
+            suppressed: 0,
+        });
+
+        if now.duration_since(entry.window_start) >= interval {
+            entry.window_start = now;
+            entry.count = 0;
+        }
+
+        if entry.count < burst {
+            entry.count += 1;
+            Some(std::mem::take(&mut entry.suppressed))
+        } else {
+            entry.suppressed += 1;
+            None
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A log writer that filters messages by log domain and level and rate-limits repeated
+/// messages before passing them on to GLib's default log writer.
+///
+/// Rules map glob patterns on log domains, e.g. `GLib-GIO` or `Gtk*`, to the minimum level of
+/// messages that are written for matching domains. If several rules match a domain, the one that
+/// was set last is used. Messages of domains for which no rule matches are passed on to
+/// [`log_writer_default`], which applies the usual `G_MESSAGES_DEBUG` filtering. Messages that
+/// are allowed by a rule are written even if `G_MESSAGES_DEBUG` does not include their domain.
+/// Messages without a domain are matched as an empty domain.
+///
+/// Messages of level [`LogLevel::Error`] are never dropped.
+///
+/// The router is a cheap to clone handle, rules can be changed at any time after it has been
+/// installed with [`install`](Self::install).
+///
+/// ```no_run
+/// let router = glib::LogRouter::from_env("MY_APP_LOG").unwrap();
+/// router.set_level("GLib-GIO", glib::LogLevel::Debug);
+/// router.set_rate_limit(10, std::time::Duration::from_secs(1));
+/// router.install();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LogRouter(Arc<Mutex<LogRouterState>>);
+
+impl LogRouter {
+    // rustdoc-stripper-ignore-next
+    /// Creates a router without any rules, which passes all messages on to
+    /// [`log_writer_default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a router with the rules from the environment variable `var`, see
+    /// [`add_rules`](Self::add_rules) for the syntax. If the variable is not set, the router
+    /// has no rules.
+    pub fn from_env(var: &str) -> Result<Self, BoolError> {
+        let router = Self::new();
+        if let Some(spec) = std::env::var_os(var) {
+            let spec = spec
+                .into_string()
+                .map_err(|_| bool_error!("Environment variable {} is not valid UTF-8", var))?;
+            router.add_rules(&spec)?;
+        }
+        Ok(router)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds rules from a comma or whitespace separated list of `pattern=level` entries, e.g.
+    /// `GLib-GIO=debug,Gtk*=critical`.
+    ///
+    /// Level names are the lowercase names of [`LogLevel`]. An entry without level enables debug
+    /// output for the pattern and `all` matches all domains, so the value of `G_MESSAGES_DEBUG`
+    /// is accepted as well.
+    ///
+    /// No rules are added if any entry is invalid.
+    pub fn add_rules(&self, spec: &str) -> Result<(), BoolError> {
+        let rules = spec
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (pattern, level) = match entry.split_once('=') {
+                    Some((pattern, level)) => (
+                        pattern,
+                        parse_log_level(level)
+                            .ok_or_else(|| bool_error!("Invalid log level '{}'", level))?,
+                    ),
+                    None => (entry, LogLevel::Debug),
+                };
+                if pattern.is_empty() {
+                    return Err(bool_error!("Missing log domain pattern in '{}'", entry));
+                }
+                let pattern = if pattern == "all" { "*" } else { pattern };
+                Ok((pattern, level))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for (pattern, level) in rules {
+            self.set_level(pattern, level);
+        }
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the minimum level of messages that are written for domains matching `pattern`,
+    /// replacing any previous rule for the same pattern.
+    pub fn set_level(&self, pattern: &str, min_level: LogLevel) {
+        let mut state = self.0.lock().unwrap();
+        state.rules.retain(|(p, _)| p != pattern);
+        state.rules.push((pattern.to_owned(), min_level));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes the rule for `pattern`.
+    pub fn unset_level(&self, pattern: &str) {
+        self.0.lock().unwrap().rules.retain(|(p, _)| p != pattern);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes all rules.
+    pub fn clear_levels(&self) {
+        self.0.lock().unwrap().rules.clear();
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets whether all messages of all domains are written, regardless of the rules.
+    ///
+    /// This is the equivalent of `g_log_set_debug_enabled()` for the router. Rate limiting still
+    /// applies.
+    pub fn set_debug_enabled(&self, debug_enabled: bool) {
+        self.0.lock().unwrap().debug_enabled = debug_enabled;
+    }
+
+    pub fn is_debug_enabled(&self) -> bool {
+        self.0.lock().unwrap().debug_enabled
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Limits identical messages of the same domain to `burst` messages per `interval`.
+    ///
+    /// The number of suppressed repetitions is reported once the message is written again.
+    pub fn set_rate_limit(&self, burst: u32, interval: Duration) {
+        let mut state = self.0.lock().unwrap();
+        state.rate_limit = Some((burst, interval));
+        state.recent.clear();
+    }
+
+    pub fn unset_rate_limit(&self) {
+        let mut state = self.0.lock().unwrap();
+        state.rate_limit = None;
+        state.recent.clear();
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns whether a message of `log_level` and `log_domain` would be dropped by the
+    /// router, not taking rate limiting into account.
+    #[cfg(feature = "v2_68")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "v2_68")))]
+    pub fn would_drop(&self, log_level: LogLevel, log_domain: Option<&str>) -> bool {
+        match self.0.lock().unwrap().route(log_level, log_domain) {
+            LogRoute::Pass => false,
+            LogRoute::Drop => true,
+            LogRoute::Default => log_writer_default_would_drop(log_level, log_domain),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Filters the message and passes it on to GLib's default log writer.
+    ///
+    /// This can be used to chain the router into a custom writer function, otherwise
+    /// [`install`](Self::install) takes care of this.
+    pub fn write(&self, log_level: LogLevel, fields: &[LogField<'_>]) -> LogWriterOutput {
+        let field = |key: &str| fields.iter().find(|field| field.key() == key);
+        let log_domain = field("GLIB_DOMAIN").and_then(LogField::value_str);
+        let message = field("MESSAGE")
+            .and_then(LogField::value_bytes)
+            .unwrap_or_default();
+
+        let (route, suppressed) = {
+            let mut state = self.0.lock().unwrap();
+            let route = state.route(log_level, log_domain);
+            if route == LogRoute::Drop {
+                return LogWriterOutput::Handled;
+            }
+            match state.rate_limit(log_level, log_domain, message, Instant::now()) {
+                Some(suppressed) => (route, suppressed),
+                None => return LogWriterOutput::Handled,
+            }
+        };
+
+        let write = |log_level, fields: &[LogField<'_>]| match route {
+            LogRoute::Default => log_writer_default(log_level, fields),
+            _ => Self::write_unfiltered(log_level, fields),
+        };
+
+        if suppressed > 0 {
+            let notice = format!("Suppressed {suppressed} repetitions of the following message");
+            let mut notice_fields = vec![
+                LogField::new(gstr!("PRIORITY"), log_level.priority().as_bytes()),
+                LogField::new(gstr!("MESSAGE"), notice.as_bytes()),
+            ];
+            if let Some(log_domain) = log_domain {
+                notice_fields.push(LogField::new(gstr!("GLIB_DOMAIN"), log_domain.as_bytes()));
+            }
+            write(log_level, &notice_fields);
+        }
+
+        write(log_level, fields)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the router as the log writer function with [`log_set_writer_func`].
+    ///
+    /// As with [`log_set_writer_func`], this can only be done once.
+    pub fn install(&self) {
+        let router = self.clone();
+        log_set_writer_func(move |log_level, fields| router.write(log_level, fields));
+    }
+
+    // Writes the message like `log_writer_default` does, but without the debug output filtering.
+    fn write_unfiltered(log_level: LogLevel, fields: &[LogField<'_>]) -> LogWriterOutput {
+        // GLib only filters debug and info messages, everything else goes through the default
+        // writer so that `g_log_set_always_fatal()` and `G_DEBUG=fatal-*` are still handled.
+        if !matches!(log_level, LogLevel::Debug | LogLevel::Info) {
+            return log_writer_default(log_level, fields);
+        }
+
+        #[cfg(unix)]
+        if log_writer_is_journald(std::io::stderr())
+            && log_writer_journald(log_level, fields) == LogWriterOutput::Handled
+        {
+            return LogWriterOutput::Handled;
+        }
+        log_writer_standard_streams(log_level, fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob() {
+        assert!(glob_matches("GLib-GIO", "GLib-GIO"));
+        assert!(!glob_matches("GLib-GIO", "GLib"));
+        assert!(glob_matches("GLib*", "GLib-GIO"));
+        assert!(glob_matches("GLib*", "GLib"));
+        assert!(glob_matches("*-GIO", "GLib-GIO"));
+        assert!(glob_matches("G*b-?IO", "GLib-GIO"));
+        assert!(!glob_matches("G*b-?IO", "GLib-IO"));
+        assert!(glob_matches("*", ""));
+        assert!(!glob_matches("?", ""));
+    }
+
+    #[test]
+    fn rules() {
+        let router = LogRouter::new();
+        router
+            .add_rules("all=warning, GLib-GIO=debug,Gtk*=critical Gdk")
+            .unwrap();
+
+        let state = router.0.lock().unwrap();
+        assert_eq!(
+            state.route(LogLevel::Debug, Some("GLib-GIO")),
+            LogRoute::Pass
+        );
+        assert_eq!(state.route(LogLevel::Info, Some("GLib")), LogRoute::Drop);
+        assert_eq!(state.route(LogLevel::Warning, None), LogRoute::Pass);
+        assert_eq!(state.route(LogLevel::Warning, Some("Gtk")), LogRoute::Drop);
+        assert_eq!(state.route(LogLevel::Error, Some("Gtk")), LogRoute::Pass);
+        assert_eq!(state.route(LogLevel::Debug, Some("Gdk")), LogRoute::Pass);
+        drop(state);
+
+        router.clear_levels();
+        router.set_level("Gtk", LogLevel::Message);
+        let state = router.0.lock().unwrap();
+        assert_eq!(state.route(LogLevel::Debug, Some("Gtk")), LogRoute::Drop);
+        assert_eq!(
+            state.route(LogLevel::Debug, Some("GLib")),
+            LogRoute::Default
+        );
+        drop(state);
+
+        router.set_debug_enabled(true);
+        let state = router.0.lock().unwrap();
+        assert_eq!(state.route(LogLevel::Debug, Some("Gtk")), LogRoute::Pass);
+    }
+
+    #[test]
+    fn invalid_rules() {
+        let router = LogRouter::new();
+        assert!(router.add_rules("GLib=debug,Gtk=verbose").is_err());
+        assert!(router.add_rules("=debug").is_err());
+        assert!(router.0.lock().unwrap().rules.is_empty());
+    }
+
+    #[test]
+    fn rate_limit() {
+        let router = LogRouter::new();
+        router.set_rate_limit(2, Duration::from_secs(1));
+
+        let mut state = router.0.lock().unwrap();
+        let start = Instant::now();
+        let mut limit = |message: &[u8], secs| {
+            state.rate_limit(
+                LogLevel::Debug,
+                Some("GLib-GIO"),
+                message,
+                start + Duration::from_secs_f32(secs),
+            )
+        };
+        assert_eq!(limit(b"a", 0.0), Some(0));
+        assert_eq!(limit(b"a", 0.1), Some(0));
+        assert_eq!(limit(b"a", 0.2), None);
+        assert_eq!(limit(b"b", 0.3), Some(0));
+        assert_eq!(limit(b"a", 0.4), None);
+        assert_eq!(limit(b"a", 1.1), Some(2));
+        assert_eq!(limit(b"a", 1.2), Some(0));
+        assert_eq!(limit(b"a", 1.3), None);
+    }
+
+    #[test]
+    fn rate_limit_pruning() {
+        let router = LogRouter::new();
+        router.set_rate_limit(1, Duration::from_secs(60));
+
+        let mut state = router.0.lock().unwrap();
+        let start = Instant::now();
+        for i in 0..LogRouterState::MAX_RECENT {
+            let message = i.to_string();
+            let now = start + Duration::from_millis(i as u64);
+            assert_eq!(
+                state.rate_limit(LogLevel::Debug, None, message.as_bytes(), now),
+                Some(0)
+            );
+        }
+
+        let now = start + Duration::from_secs(1);
+        assert_eq!(
+            state.rate_limit(LogLevel::Debug, None, b"new", now),
+            Some(0)
+        );
+        assert!(state.recent.len() <= LogRouterState::MAX_RECENT / 2 + 1);
+
+        // The most recent messages are still limited, the oldest ones were forgotten.
+        let last = (LogRouterState::MAX_RECENT - 1).to_string();
+        assert_eq!(
+            state.rate_limit(LogLevel::Debug, None, last.as_bytes(), now),
+            None
+        );
+        assert_eq!(state.rate_limit(LogLevel::Debug, None, b"0", now), Some(0));
+    }
+}
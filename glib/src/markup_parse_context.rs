@@ -0,0 +1,894 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{any::Any, cell::RefCell, collections::VecDeque, ptr, rc::Rc, slice};
+
+use libc::c_char;
+
+use crate::{
+    Error, GStr, GString, MarkupError, MarkupParseContext, MarkupParseFlags, ffi, translate::*,
+};
+
+// rustdoc-stripper-ignore-next
+/// Handler for the events emitted while parsing with a [`MarkupParseContext`].
+///
+/// All methods have default implementations that ignore the event. Returning an error from any
+/// of them stops parsing, and the error is returned from [`MarkupParseContext::parse`] or
+/// [`MarkupParseContext::end_parse`].
+pub trait MarkupParser: Any {
+    // rustdoc-stripper-ignore-next
+    /// Called for open tags like `<foo bar="baz">`, and for empty tags like `<empty/>`.
+    fn start_element(
+        &mut self,
+        _context: &MarkupParseContext,
+        _element_name: &GStr,
+        _attributes: &MarkupAttributes,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Called for close tags like `</foo>`, and for empty tags like `<empty/>`.
+    fn end_element(
+        &mut self,
+        _context: &MarkupParseContext,
+        _element_name: &GStr,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Called for character data. Text may be split over multiple calls, but is never empty.
+    fn text(&mut self, _context: &MarkupParseContext, _text: &str) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Called for comments, processing instructions and doctype declarations, as well as for
+    /// CDATA sections unless [`MarkupParseFlags::TREAT_CDATA_AS_TEXT`] is set.
+    fn passthrough(
+        &mut self,
+        _context: &MarkupParseContext,
+        _passthrough_text: &str,
+    ) -> Result<(), Error> {
+        Ok(())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Called when an error occurs, including errors returned by the other methods.
+    fn error(&mut self, _context: &MarkupParseContext, _error: &Error) {}
+}
+
+// The user data of every parser that is set on a context.
+struct ParserData {
+    parser: RefCell<Box<dyn MarkupParser>>,
+    // Parsers pushed from the callbacks of this parser that were not popped yet. They are kept
+    // here so that they are freed together with the context if they are never popped, e.g.
+    // because parsing failed. They are boxed as GLib keeps pointers to them as user data.
+    #[allow(clippy::vec_box)]
+    pushed: RefCell<Vec<Box<ParserData>>>,
+}
+
+impl ParserData {
+    fn new<P: MarkupParser>(parser: P) -> Box<Self> {
+        Box::new(Self {
+            parser: RefCell::new(Box::new(parser)),
+            pushed: RefCell::new(Vec::new()),
+        })
+    }
+}
+
+static PARSER: ffi::GMarkupParser = ffi::GMarkupParser {
+    start_element: Some(start_element_trampoline),
+    end_element: Some(end_element_trampoline),
+    text: Some(text_trampoline),
+    passthrough: Some(passthrough_trampoline),
+    error: Some(error_trampoline),
+};
+
+unsafe fn with_parser(
+    context: *mut ffi::GMarkupParseContext,
+    user_data: ffi::gpointer,
+    error: *mut *mut ffi::GError,
+    f: impl FnOnce(&mut dyn MarkupParser, &MarkupParseContext) -> Result<(), Error>,
+) {
+    unsafe {
+        let data = &*(user_data as *const ParserData);
+        let context: Borrowed<MarkupParseContext> = from_glib_borrow(context);
+        let mut parser = data.parser.borrow_mut();
+        if let Err(err) = f(&mut **parser, &context) {
+            *error = err.into_glib_ptr();
+        }
+    }
+}
+
+unsafe extern "C" fn start_element_trampoline(
+    context: *mut ffi::GMarkupParseContext,
+    element_name: *const c_char,
+    attribute_names: *mut *const c_char,
+    attribute_values: *mut *const c_char,
+    user_data: ffi::gpointer,
+    error: *mut *mut ffi::GError,
+) {
+    unsafe {
+        let element_name = GStr::from_ptr(element_name);
+        let attributes = MarkupAttributes::from_raw(attribute_names, attribute_values);
+        with_parser(context, user_data, error, |parser, context| {
+            parser.start_element(context, element_name, &attributes)
+        });
+    }
+}
+
+unsafe extern "C" fn end_element_trampoline(
+    context: *mut ffi::GMarkupParseContext,
+    element_name: *const c_char,
+    user_data: ffi::gpointer,
+    error: *mut *mut ffi::GError,
+) {
+    unsafe {
+        let element_name = GStr::from_ptr(element_name);
+        with_parser(context, user_data, error, |parser, context| {
+            parser.end_element(context, element_name)
+        });
+    }
+}
+
+unsafe fn text_from_raw<'a>(text: *const c_char, text_len: usize) -> &'a str {
+    unsafe {
+        if text_len == 0 {
+            return "";
+        }
+        // GMarkupParseContext validates that all text is UTF-8.
+        std::str::from_utf8_unchecked(slice::from_raw_parts(text as *const u8, text_len))
+    }
+}
+
+unsafe extern "C" fn text_trampoline(
+    context: *mut ffi::GMarkupParseContext,
+    text: *const c_char,
+    text_len: usize,
+    user_data: ffi::gpointer,
+    error: *mut *mut ffi::GError,
+) {
+    unsafe {
+        // Older GLib versions also report the empty text between two adjacent tags.
+        if text_len == 0 {
+            return;
+        }
+
+        let text = text_from_raw(text, text_len);
+        with_parser(context, user_data, error, |parser, context| {
+            parser.text(context, text)
+        });
+    }
+}
+
+unsafe extern "C" fn passthrough_trampoline(
+    context: *mut ffi::GMarkupParseContext,
+    passthrough_text: *const c_char,
+    text_len: usize,
+    user_data: ffi::gpointer,
+    error: *mut *mut ffi::GError,
+) {
+    unsafe {
+        let passthrough_text = text_from_raw(passthrough_text, text_len);
+        with_parser(context, user_data, error, |parser, context| {
+            parser.passthrough(context, passthrough_text)
+        });
+    }
+}
+
+unsafe extern "C" fn error_trampoline(
+    context: *mut ffi::GMarkupParseContext,
+    error: *mut ffi::GError,
+    user_data: ffi::gpointer,
+) {
+    unsafe {
+        let data = &*(user_data as *const ParserData);
+        let context: Borrowed<MarkupParseContext> = from_glib_borrow(context);
+        let error: Borrowed<Error> = from_glib_borrow(error);
+        data.parser.borrow_mut().error(&context, &error);
+    }
+}
+
+unsafe extern "C" fn destroy_parser_data(user_data: ffi::gpointer) {
+    unsafe {
+        let _ = Box::from_raw(user_data as *mut ParserData);
+    }
+}
+
+impl MarkupParseContext {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new parse context that passes all events to `parser`.
+    #[doc(alias = "g_markup_parse_context_new")]
+    pub fn new<P: MarkupParser>(parser: P, flags: MarkupParseFlags) -> MarkupParseContext {
+        unsafe {
+            let data = Box::into_raw(ParserData::new(parser));
+            from_glib_full(ffi::g_markup_parse_context_new(
+                &PARSER,
+                flags.into_glib(),
+                data as ffi::gpointer,
+                Some(destroy_parser_data),
+            ))
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Temporarily passes all events to `parser`, until the element that is currently being
+    /// started is closed again.
+    ///
+    /// This must be called from [`MarkupParser::start_element`]. The parser is handed back by
+    /// [`pop`](Self::pop), which must be called from [`MarkupParser::end_element`] for the same
+    /// element. Parsers that are not popped are only freed together with the context.
+    #[doc(alias = "g_markup_parse_context_push")]
+    pub fn push<P: MarkupParser>(&self, parser: P) {
+        unsafe {
+            let current = ffi::g_markup_parse_context_get_user_data(self.to_glib_none().0)
+                as *const ParserData;
+            assert!(
+                !current.is_null(),
+                "Markup parse context was not created from Rust"
+            );
+
+            let data = ParserData::new(parser);
+            let ptr = &*data as *const ParserData;
+            (*current).pushed.borrow_mut().push(data);
+
+            ffi::g_markup_parse_context_push(self.to_glib_none().0, &PARSER, ptr as ffi::gpointer);
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the parser that was passed to [`push`](Self::push) for the element that is
+    /// currently being closed.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the pushed parser is not a `P`.
+    #[doc(alias = "g_markup_parse_context_pop")]
+    pub fn pop<P: MarkupParser>(&self) -> P {
+        unsafe {
+            let popped =
+                ffi::g_markup_parse_context_pop(self.to_glib_none().0) as *const ParserData;
+            let current = ffi::g_markup_parse_context_get_user_data(self.to_glib_none().0)
+                as *const ParserData;
+            assert!(
+                !current.is_null(),
+                "Markup parse context was not created from Rust"
+            );
+
+            let data = {
+                let mut pushed = (*current).pushed.borrow_mut();
+                let index = pushed
+                    .iter()
+                    .position(|data| ptr::eq(&**data, popped))
+                    .expect("Popped parser was not pushed from Rust");
+                pushed.swap_remove(index)
+            };
+
+            let parser: Box<dyn Any> = data.parser.into_inner();
+            *parser.downcast::<P>().unwrap_or_else(|_| {
+                panic!("Popped parser is not a `{}`", std::any::type_name::<P>())
+            })
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Attributes of an element passed to [`MarkupParser::start_element`].
+#[derive(Clone, Copy)]
+pub struct MarkupAttributes<'a> {
+    names: &'a [*const c_char],
+    values: &'a [*const c_char],
+}
+
+impl<'a> MarkupAttributes<'a> {
+    unsafe fn from_raw(names: *mut *const c_char, values: *mut *const c_char) -> Self {
+        unsafe {
+            let mut len = 0;
+            while !(*names.add(len)).is_null() {
+                len += 1;
+            }
+
+            Self {
+                names: slice::from_raw_parts(names, len),
+                values: slice::from_raw_parts(values, len),
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of attributes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if the element has no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the value of the attribute `name`, if present.
+    pub fn get(&self, name: &str) -> Option<&'a GStr> {
+        self.iter()
+            .find_map(|(n, value)| (n == name).then_some(value))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns an iterator over the names and values of the attributes, in document order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&'a GStr, &'a GStr)> + 'a {
+        self.names
+            .iter()
+            .zip(self.values)
+            .map(|(&name, &value)| unsafe { (GStr::from_ptr(name), GStr::from_ptr(value)) })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a collector for the attributes of the element `element_name`.
+    pub fn collector<'b>(&self, element_name: &'b str) -> MarkupCollector<'a, 'b> {
+        MarkupCollector::new(element_name, self.iter())
+    }
+}
+
+impl std::fmt::Debug for MarkupAttributes<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+enum Target<'a, 'b> {
+    String(&'b mut Option<&'a GStr>),
+    OptionalString(&'b mut Option<&'a GStr>),
+    Boolean(&'b mut bool),
+    OptionalBoolean(&'b mut bool),
+    Tristate(&'b mut Option<bool>),
+}
+
+impl Target<'_, '_> {
+    fn is_required(&self) -> bool {
+        matches!(self, Self::String(_) | Self::Boolean(_))
+    }
+
+    fn is_boolean(&self) -> bool {
+        matches!(
+            self,
+            Self::Boolean(_) | Self::OptionalBoolean(_) | Self::Tristate(_)
+        )
+    }
+}
+
+fn parse_boolean(value: &str) -> Option<bool> {
+    const TRUE: &[&str] = &["true", "t", "yes", "y", "1"];
+    const FALSE: &[&str] = &["false", "f", "no", "n", "0"];
+
+    if TRUE.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+        Some(true)
+    } else if FALSE.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Collects the attributes of an element into variables, validating them like
+/// `g_markup_collect_attributes()`.
+///
+/// Every attribute of the element must be collected, must not appear more than once, and
+/// required attributes must be present. Booleans accept `true`, `t`, `yes`, `y` and `1`, and
+/// `false`, `f`, `no`, `n` and `0`, ignoring case.
+///
+/// ```
+/// # use glib::{MarkupCollector, gstr};
+/// let attributes = [(gstr!("name"), gstr!("label")), (gstr!("translatable"), gstr!("yes"))];
+///
+/// let mut name = None;
+/// let mut translatable = false;
+/// let mut context = None;
+/// MarkupCollector::new("property", attributes)
+///     .string("name", &mut name)
+///     .optional_boolean("translatable", &mut translatable)
+///     .optional_string("context", &mut context)
+///     .collect()
+///     .unwrap();
+///
+/// assert_eq!(name.unwrap(), "label");
+/// assert!(translatable);
+/// assert_eq!(context, None);
+/// ```
+#[must_use = "attributes are only collected by calling `collect()`"]
+pub struct MarkupCollector<'a, 'b> {
+    element_name: &'b str,
+    attributes: Vec<(&'a GStr, &'a GStr)>,
+    targets: Vec<(&'b str, Target<'a, 'b>)>,
+}
+
+impl<'a, 'b> MarkupCollector<'a, 'b> {
+    // rustdoc-stripper-ignore-next
+    /// Creates a collector for the given attribute names and values of the element
+    /// `element_name`.
+    pub fn new(
+        element_name: &'b str,
+        attributes: impl IntoIterator<Item = (&'a GStr, &'a GStr)>,
+    ) -> Self {
+        Self {
+            element_name,
+            attributes: attributes.into_iter().collect(),
+            targets: Vec::new(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Collects the required string attribute `name`.
+    pub fn string(mut self, name: &'b str, target: &'b mut Option<&'a GStr>) -> Self {
+        self.targets.push((name, Target::String(target)));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Collects the string attribute `name`, which is `None` if it is not present.
+    pub fn optional_string(mut self, name: &'b str, target: &'b mut Option<&'a GStr>) -> Self {
+        self.targets.push((name, Target::OptionalString(target)));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Collects the required boolean attribute `name`.
+    pub fn boolean(mut self, name: &'b str, target: &'b mut bool) -> Self {
+        self.targets.push((name, Target::Boolean(target)));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Collects the boolean attribute `name`, which is `false` if it is not present.
+    pub fn optional_boolean(mut self, name: &'b str, target: &'b mut bool) -> Self {
+        self.targets.push((name, Target::OptionalBoolean(target)));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Collects the boolean attribute `name`, which is `None` if it is not present.
+    pub fn tristate(mut self, name: &'b str, target: &'b mut Option<bool>) -> Self {
+        self.targets.push((name, Target::Tristate(target)));
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Validates the attributes and stores them in the targets.
+    ///
+    /// The targets are only modified if all attributes are valid.
+    #[doc(alias = "g_markup_collect_attributes")]
+    pub fn collect(self) -> Result<(), Error> {
+        let element_name = self.element_name;
+        let mut values = vec![None; self.targets.len()];
+
+        for &(name, value) in &self.attributes {
+            let Some(index) = self.targets.iter().position(|(n, _)| name == *n) else {
+                return Err(Error::new(
+                    MarkupError::UnknownAttribute,
+                    &format!("attribute '{name}' invalid for element '{element_name}'"),
+                ));
+            };
+            if values[index].replace(value).is_some() {
+                return Err(Error::new(
+                    MarkupError::InvalidContent,
+                    &format!(
+                        "attribute '{name}' given multiple times for element '{element_name}'"
+                    ),
+                ));
+            }
+        }
+
+        for ((name, target), value) in self.targets.iter().zip(&values) {
+            match value {
+                None if target.is_required() => {
+                    return Err(Error::new(
+                        MarkupError::MissingAttribute,
+                        &format!("element '{element_name}' requires attribute '{name}'"),
+                    ));
+                }
+                Some(value) if target.is_boolean() && parse_boolean(value).is_none() => {
+                    return Err(Error::new(
+                        MarkupError::InvalidContent,
+                        &format!(
+                            "element '{element_name}', attribute '{name}', value '{value}' cannot be parsed as a boolean value"
+                        ),
+                    ));
+                }
+                _ => (),
+            }
+        }
+
+        for ((_, target), value) in self.targets.into_iter().zip(values) {
+            let boolean = value.and_then(|value| parse_boolean(value));
+            match target {
+                Target::String(target) | Target::OptionalString(target) => *target = value,
+                Target::Boolean(target) | Target::OptionalBoolean(target) => {
+                    *target = boolean.unwrap_or(false)
+                }
+                Target::Tristate(target) => *target = boolean,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// An event returned by [`MarkupEvents`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarkupEvent {
+    StartElement {
+        name: GString,
+        attributes: Vec<(GString, GString)>,
+    },
+    EndElement {
+        name: GString,
+    },
+    Text(GString),
+    Passthrough(GString),
+}
+
+impl MarkupEvent {
+    // rustdoc-stripper-ignore-next
+    /// Creates a collector for the attributes of a [`StartElement`](Self::StartElement) event.
+    ///
+    /// For all other events, the collector has no attributes.
+    pub fn collector(&self) -> MarkupCollector<'_, '_> {
+        match self {
+            Self::StartElement { name, attributes } => MarkupCollector::new(
+                name.as_str(),
+                attributes
+                    .iter()
+                    .map(|(name, value)| (name.as_gstr(), value.as_gstr())),
+            ),
+            _ => MarkupCollector::new("", []),
+        }
+    }
+}
+
+struct EventCollector(Rc<RefCell<VecDeque<MarkupEvent>>>);
+
+impl MarkupParser for EventCollector {
+    fn start_element(
+        &mut self,
+        _context: &MarkupParseContext,
+        element_name: &GStr,
+        attributes: &MarkupAttributes,
+    ) -> Result<(), Error> {
+        self.0.borrow_mut().push_back(MarkupEvent::StartElement {
+            name: element_name.into(),
+            attributes: attributes
+                .iter()
+                .map(|(name, value)| (name.into(), value.into()))
+                .collect(),
+        });
+        Ok(())
+    }
+
+    fn end_element(
+        &mut self,
+        _context: &MarkupParseContext,
+        element_name: &GStr,
+    ) -> Result<(), Error> {
+        self.0.borrow_mut().push_back(MarkupEvent::EndElement {
+            name: element_name.into(),
+        });
+        Ok(())
+    }
+
+    fn text(&mut self, _context: &MarkupParseContext, text: &str) -> Result<(), Error> {
+        self.0
+            .borrow_mut()
+            .push_back(MarkupEvent::Text(text.into()));
+        Ok(())
+    }
+
+    fn passthrough(
+        &mut self,
+        _context: &MarkupParseContext,
+        passthrough_text: &str,
+    ) -> Result<(), Error> {
+        self.0
+            .borrow_mut()
+            .push_back(MarkupEvent::Passthrough(passthrough_text.into()));
+        Ok(())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Pull-style iterator over the [`MarkupEvent`]s of a document.
+///
+/// The document is passed to a [`MarkupParseContext`] in chunks as events are consumed. After an
+/// error, the iterator returns the events that were emitted before the error, then the error
+/// and then `None`.
+///
+/// ```
+/// # use glib::{MarkupEvent, MarkupEvents, MarkupParseFlags};
+/// let events = MarkupEvents::new("<a x=\"1\">b</a>", MarkupParseFlags::empty())
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(
+///     events,
+///     [
+///         MarkupEvent::StartElement {
+///             name: "a".into(),
+///             attributes: vec![("x".into(), "1".into())],
+///         },
+///         MarkupEvent::Text("b".into()),
+///         MarkupEvent::EndElement { name: "a".into() },
+///     ]
+/// );
+/// ```
+pub struct MarkupEvents<'a> {
+    context: MarkupParseContext,
+    events: Rc<RefCell<VecDeque<MarkupEvent>>>,
+    remaining: &'a str,
+    error: Option<Error>,
+    finished: bool,
+}
+
+impl<'a> MarkupEvents<'a> {
+    const CHUNK_SIZE: usize = 4096;
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an iterator over the events of `text`.
+    pub fn new(text: &'a str, flags: MarkupParseFlags) -> Self {
+        let events = Rc::<RefCell<VecDeque<MarkupEvent>>>::default();
+        Self {
+            context: MarkupParseContext::new(EventCollector(events.clone()), flags),
+            events,
+            remaining: text,
+            error: None,
+            finished: false,
+        }
+    }
+
+    fn parse_next_chunk(&mut self) {
+        let res = if self.remaining.is_empty() {
+            self.finished = true;
+            self.context.end_parse()
+        } else {
+            let mut len = self.remaining.len().min(Self::CHUNK_SIZE);
+            while !self.remaining.is_char_boundary(len) {
+                len += 1;
+            }
+            let (chunk, remaining) = self.remaining.split_at(len);
+            self.remaining = remaining;
+            self.context.parse(chunk)
+        };
+
+        if let Err(err) = res {
+            self.error = Some(err);
+            self.finished = true;
+        }
+    }
+}
+
+impl Iterator for MarkupEvents<'_> {
+    type Item = Result<MarkupEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.events.borrow_mut().pop_front() {
+                return Some(Ok(event));
+            }
+            if let Some(err) = self.error.take() {
+                return Some(Err(err));
+            }
+            if self.finished {
+                return None;
+            }
+
+            self.parse_next_chunk();
+        }
+    }
+}
+
+impl std::iter::FusedIterator for MarkupEvents<'_> {}
+
+impl std::fmt::Debug for MarkupEvents<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("MarkupEvents")
+            .field("context", &self.context)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gstr;
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+    }
+
+    impl MarkupParser for Recorder {
+        fn start_element(
+            &mut self,
+            context: &MarkupParseContext,
+            element_name: &GStr,
+            attributes: &MarkupAttributes,
+        ) -> Result<(), Error> {
+            if element_name == "child" {
+                context.push(Recorder::default());
+            }
+            if element_name == "bad" {
+                return Err(Error::new(MarkupError::UnknownElement, "bad element"));
+            }
+            let attributes = attributes
+                .iter()
+                .map(|(name, value)| format!(" {name}={value}"))
+                .collect::<String>();
+            self.events.push(format!("<{element_name}{attributes}>"));
+            Ok(())
+        }
+
+        fn end_element(
+            &mut self,
+            context: &MarkupParseContext,
+            element_name: &GStr,
+        ) -> Result<(), Error> {
+            if element_name == "child" {
+                let child = context.pop::<Recorder>();
+                self.events.push(format!("child{:?}", child.events));
+            }
+            self.events.push(format!("</{element_name}>"));
+            Ok(())
+        }
+
+        fn text(&mut self, _context: &MarkupParseContext, text: &str) -> Result<(), Error> {
+            self.events.push(text.to_owned());
+            Ok(())
+        }
+    }
+
+    struct Shared(Rc<RefCell<Recorder>>);
+
+    impl MarkupParser for Shared {
+        fn start_element(
+            &mut self,
+            context: &MarkupParseContext,
+            element_name: &GStr,
+            attributes: &MarkupAttributes,
+        ) -> Result<(), Error> {
+            self.0
+                .borrow_mut()
+                .start_element(context, element_name, attributes)
+        }
+
+        fn end_element(
+            &mut self,
+            context: &MarkupParseContext,
+            element_name: &GStr,
+        ) -> Result<(), Error> {
+            self.0.borrow_mut().end_element(context, element_name)
+        }
+
+        fn text(&mut self, context: &MarkupParseContext, text: &str) -> Result<(), Error> {
+            self.0.borrow_mut().text(context, text)
+        }
+    }
+
+    #[test]
+    fn parser() {
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        let context = MarkupParseContext::new(Shared(recorder.clone()), MarkupParseFlags::empty());
+        context
+            .parse("<root a=\"1\">x<child b=\"2\">y</child></root>")
+            .unwrap();
+        context.end_parse().unwrap();
+
+        assert_eq!(
+            recorder.borrow().events,
+            [
+                "<root a=1>",
+                "x",
+                "<child b=2>",
+                "child[\"y\"]",
+                "</child>",
+                "</root>"
+            ]
+        );
+    }
+
+    #[test]
+    fn parser_error() {
+        let context = MarkupParseContext::new(Recorder::default(), MarkupParseFlags::empty());
+        let err = context
+            .parse("<root><child><bad/></child></root>")
+            .unwrap_err();
+        assert!(err.matches(MarkupError::UnknownElement));
+    }
+
+    #[test]
+    fn collector() {
+        let attributes = [(gstr!("name"), gstr!("foo")), (gstr!("flag"), gstr!("No"))];
+
+        let mut name = None;
+        let mut flag = true;
+        let mut tristate = Some(true);
+        MarkupCollector::new("element", attributes)
+            .string("name", &mut name)
+            .boolean("flag", &mut flag)
+            .tristate("tristate", &mut tristate)
+            .collect()
+            .unwrap();
+        assert_eq!(name.unwrap(), "foo");
+        assert!(!flag);
+        assert_eq!(tristate, None);
+
+        let err = MarkupCollector::new("element", attributes)
+            .string("name", &mut name)
+            .collect()
+            .unwrap_err();
+        assert!(err.matches(MarkupError::UnknownAttribute));
+
+        let (mut flag_string, mut missing) = (None, None);
+        let err = MarkupCollector::new("element", attributes)
+            .string("name", &mut name)
+            .string("flag", &mut flag_string)
+            .string("missing", &mut missing)
+            .collect()
+            .unwrap_err();
+        assert!(err.matches(MarkupError::MissingAttribute));
+
+        let err = MarkupCollector::new("element", [(gstr!("flag"), gstr!("maybe"))])
+            .boolean("flag", &mut flag)
+            .collect()
+            .unwrap_err();
+        assert!(err.matches(MarkupError::InvalidContent));
+    }
+
+    #[test]
+    fn events() {
+        let text = format!("<r>{}<!-- c --></r>", "é".repeat(MarkupEvents::CHUNK_SIZE));
+        let events = MarkupEvents::new(&text, MarkupParseFlags::empty())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert_eq!(
+            events.first().unwrap(),
+            &MarkupEvent::StartElement {
+                name: "r".into(),
+                attributes: vec![],
+            }
+        );
+        let text = events
+            .iter()
+            .filter_map(|event| match event {
+                MarkupEvent::Text(text) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect::<String>();
+        assert_eq!(text, "é".repeat(MarkupEvents::CHUNK_SIZE));
+        assert!(events.contains(&MarkupEvent::Passthrough("<!-- c -->".into())));
+        assert_eq!(
+            events.last().unwrap(),
+            &MarkupEvent::EndElement { name: "r".into() }
+        );
+
+        let mut events = MarkupEvents::new("<a><b></a>", MarkupParseFlags::empty());
+        assert!(matches!(
+            events.next(),
+            Some(Ok(MarkupEvent::StartElement { .. }))
+        ));
+        assert!(matches!(
+            events.next(),
+            Some(Ok(MarkupEvent::StartElement { .. }))
+        ));
+        assert!(
+            events
+                .next()
+                .unwrap()
+                .unwrap_err()
+                .matches(MarkupError::Parse)
+        );
+        assert!(events.next().is_none());
+    }
+}
@@ -1,7 +1,7 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
 use crate::{GStr, Regex, ffi, prelude::*, translate::*};
-use std::{marker::PhantomData, mem, ptr};
+use std::{iter::FusedIterator, marker::PhantomData, mem, ops, ptr};
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[repr(transparent)]
@@ -279,6 +279,30 @@ impl<'input> MatchInfo<'input> {
         unsafe { from_glib_full(ffi::g_match_info_fetch(self.to_glib_none().0, match_num)) }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Returns the substrings of the current match.
+    ///
+    /// Unlike [`fetch()`](Self::fetch), the returned [`Captures`] borrow from the input string and
+    /// stay valid when advancing to the next match.
+    pub fn captures(&self) -> Captures<'input> {
+        let regex = self.regex();
+        // The match count doesn't include trailing groups that did not participate in the match.
+        let positions = (0..=regex.capture_count())
+            .map(|match_num| match self.fetch_pos(match_num) {
+                Some((start, end)) if start >= 0 && end >= 0 => {
+                    Some((start as usize, end as usize))
+                }
+                _ => None,
+            })
+            .collect();
+
+        Captures {
+            string: self.string(),
+            regex,
+            positions,
+        }
+    }
+
     #[doc(alias = "g_match_info_fetch_all")]
     pub fn fetch_all(&self) -> Vec<crate::GString> {
         unsafe {
@@ -395,3 +419,221 @@ impl<'input> MatchInfo<'input> {
         })
     }
 }
+
+// rustdoc-stripper-ignore-next
+/// Iterates over the matches of a [`MatchInfo`], starting with its current match.
+///
+/// Each call to [`next()`](Iterator::next) advances the underlying `MatchInfo` to the next match,
+/// so matches are only searched for on demand.
+///
+/// ```
+/// use glib::{Regex, RegexCompileFlags, RegexMatchFlags, gstr};
+///
+/// let regex = Regex::new(
+///     r"(?<key>\w+)=(?<value>\w*)",
+///     RegexCompileFlags::DEFAULT,
+///     RegexMatchFlags::DEFAULT,
+/// )
+/// .unwrap()
+/// .unwrap();
+/// let match_info = regex
+///     .match_(gstr!("a=1 b= c=3"), RegexMatchFlags::DEFAULT)
+///     .unwrap();
+///
+/// let pairs = match_info
+///     .into_iter()
+///     .map(|captures| {
+///         let captures = captures.unwrap();
+///         (captures.name("key").unwrap(), captures.name("value").unwrap())
+///     })
+///     .collect::<Vec<_>>();
+/// assert_eq!(pairs, [("a", "1"), ("b", ""), ("c", "3")]);
+/// ```
+#[derive(Debug)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Matches<'input> {
+    match_info: MatchInfo<'input>,
+    started: bool,
+    done: bool,
+}
+
+impl<'input> Iterator for Matches<'input> {
+    type Item = Result<Captures<'input>, crate::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        if self.started {
+            match self.match_info.next() {
+                Ok(true) => (),
+                Ok(false) => {
+                    self.done = true;
+                    return None;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err));
+                }
+            }
+        } else {
+            self.started = true;
+            if !self.match_info.matches() {
+                self.done = true;
+                return None;
+            }
+        }
+
+        Some(Ok(self.match_info.captures()))
+    }
+}
+
+impl FusedIterator for Matches<'_> {}
+
+impl<'input> IntoIterator for MatchInfo<'input> {
+    type Item = Result<Captures<'input>, crate::Error>;
+    type IntoIter = Matches<'input>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Matches {
+            match_info: self,
+            started: false,
+            done: false,
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// The substrings of a single match, i.e. the whole match and its capture groups.
+///
+/// Groups can be accessed by number or, for named groups, by name. Indexing panics if the group
+/// did not participate in the match, use [`get()`](Self::get) or [`name()`](Self::name) to handle
+/// that case.
+#[derive(Debug, Clone)]
+pub struct Captures<'input> {
+    string: &'input GStr,
+    regex: Regex,
+    positions: Vec<Option<(usize, usize)>>,
+}
+
+impl<'input> Captures<'input> {
+    // rustdoc-stripper-ignore-next
+    /// Returns the substring matched by group `match_num`, `0` being the whole match.
+    pub fn get(&self, match_num: usize) -> Option<&'input str> {
+        self.pos(match_num)
+            .map(|(start, end)| &self.string.as_str()[start..end])
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the byte range of group `match_num` in the input string.
+    pub fn pos(&self, match_num: usize) -> Option<(usize, usize)> {
+        self.positions.get(match_num).copied().flatten()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the substring matched by the group called `name`.
+    pub fn name(&self, name: &str) -> Option<&'input str> {
+        let match_num = self.regex.string_number(name);
+        usize::try_from(match_num)
+            .ok()
+            .and_then(|match_num| self.get(match_num))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of groups of the pattern, including the whole match and groups that
+    /// did not participate in the match.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Iterates over all groups, `None` for groups that did not participate in the match.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = Option<&'input str>> + '_ {
+        (0..self.len()).map(|match_num| self.get(match_num))
+    }
+
+    pub fn input(&self) -> &'input GStr {
+        self.string
+    }
+}
+
+impl ops::Index<usize> for Captures<'_> {
+    type Output = str;
+
+    fn index(&self, match_num: usize) -> &str {
+        self.get(match_num)
+            .unwrap_or_else(|| panic!("no group at index {match_num}"))
+    }
+}
+
+impl ops::Index<&str> for Captures<'_> {
+    type Output = str;
+
+    fn index(&self, name: &str) -> &str {
+        self.name(name)
+            .unwrap_or_else(|| panic!("no group named `{name}`"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Regex, RegexCompileFlags, RegexMatchFlags, gstr};
+
+    fn regex(pattern: &str) -> Regex {
+        Regex::new(
+            pattern,
+            RegexCompileFlags::DEFAULT,
+            RegexMatchFlags::DEFAULT,
+        )
+        .unwrap()
+        .unwrap()
+    }
+
+    #[test]
+    fn captures() {
+        let regex = regex(r"(?<word>[a-z]+)(\d)?");
+        let match_info = regex
+            .match_(gstr!("abc1 de"), RegexMatchFlags::DEFAULT)
+            .unwrap();
+
+        let mut matches = match_info.into_iter();
+        let first = matches.next().unwrap().unwrap();
+        let second = matches.next().unwrap().unwrap();
+        assert!(matches.next().is_none());
+        assert!(matches.next().is_none());
+
+        // The first captures stay valid after advancing.
+        assert_eq!(&first[0], "abc1");
+        assert_eq!(&first["word"], "abc");
+        assert_eq!(first.get(2), Some("1"));
+        assert_eq!(first.pos(2), Some((3, 4)));
+        assert_eq!(
+            first.iter().collect::<Vec<_>>(),
+            [Some("abc1"), Some("abc"), Some("1")]
+        );
+
+        assert_eq!(&second[0], "de");
+        assert_eq!(second.name("word"), Some("de"));
+        assert_eq!(second.get(2), None);
+        assert_eq!(second.len(), 3);
+        assert_eq!(
+            second.iter().collect::<Vec<_>>(),
+            [Some("de"), Some("de"), None]
+        );
+        assert_eq!(second.name("missing"), None);
+    }
+
+    #[test]
+    fn no_match() {
+        let regex = regex(r"\d");
+        let match_info = regex
+            .match_(gstr!("abc"), RegexMatchFlags::DEFAULT)
+            .unwrap();
+        assert_eq!(match_info.into_iter().count(), 0);
+    }
+}
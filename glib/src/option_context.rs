@@ -0,0 +1,1153 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    cell::RefCell,
+    ffi::OsString,
+    fmt, mem,
+    path::{Path, PathBuf},
+    ptr,
+    rc::Rc,
+    sync::atomic::{self, AtomicUsize},
+};
+
+use libc::c_char;
+
+use crate::{Error, GStr, GString, OptionArg, OptionError, OptionFlags, ffi, translate::*};
+
+enum Callback {
+    Flag(Box<dyn FnMut()>),
+    String(Box<dyn FnMut(&str)>),
+    Int(Box<dyn FnMut(i32)>),
+    Int64(Box<dyn FnMut(i64)>),
+    Double(Box<dyn FnMut(f64)>),
+    Filename(Box<dyn FnMut(&Path)>),
+    StringArray(Box<dyn FnMut(Vec<String>)>, Vec<String>),
+    FilenameArray(Box<dyn FnMut(Vec<PathBuf>)>, Vec<PathBuf>),
+}
+
+// rustdoc-stripper-ignore-next
+/// A command line option, created with [`OptionEntry::builder`].
+///
+/// The callback of the entry is called with the value of the option every time the option is
+/// parsed. For array options, the callback is called once after parsing with all values.
+#[doc(alias = "GOptionEntry")]
+pub struct OptionEntry {
+    long_name: GString,
+    short_name: Option<char>,
+    flags: OptionFlags,
+    description: Option<GString>,
+    arg_description: Option<GString>,
+    callback: Callback,
+}
+
+impl OptionEntry {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for an option that is passed as `--long-name`.
+    pub fn builder(long_name: &str) -> OptionEntryBuilder {
+        assert!(
+            !long_name.is_empty() && !long_name.contains(['=', ' ']) && !long_name.starts_with('-'),
+            "Invalid long option name '{long_name}'"
+        );
+
+        OptionEntryBuilder {
+            long_name: long_name.into(),
+            short_name: None,
+            flags: OptionFlags::NONE,
+            description: None,
+            arg_description: None,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the long name of the option, without the leading `--`.
+    pub fn long_name(&self) -> &GStr {
+        self.long_name.as_gstr()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the short name of the option, without the leading `-`.
+    pub fn short_name(&self) -> Option<char> {
+        self.short_name
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the flags set with [`OptionEntryBuilder::flags`]. Flags that are implied by the
+    /// type of the value, like [`OptionFlags::NO_ARG`], are not included.
+    pub fn flags(&self) -> OptionFlags {
+        self.flags
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the description of the option in `--help` output.
+    pub fn description(&self) -> Option<&GStr> {
+        self.description.as_ref().map(GString::as_gstr)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the placeholder for the value of the option in `--help` output.
+    pub fn arg_description(&self) -> Option<&GStr> {
+        self.arg_description.as_ref().map(GString::as_gstr)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the type of the value of the option.
+    pub fn arg(&self) -> OptionArg {
+        match self.callback {
+            Callback::Flag(_) => OptionArg::None,
+            Callback::String(_) => OptionArg::String,
+            Callback::Int(_) => OptionArg::Int,
+            Callback::Int64(_) => OptionArg::Int64,
+            Callback::Double(_) => OptionArg::Double,
+            Callback::Filename(_) => OptionArg::Filename,
+            Callback::StringArray(..) => OptionArg::StringArray,
+            Callback::FilenameArray(..) => OptionArg::FilenameArray,
+        }
+    }
+
+    fn matches(&self, option_name: &str, group_name: Option<&str>) -> bool {
+        if let Some(long_name) = option_name.strip_prefix("--") {
+            long_name == self.long_name.as_str()
+                || group_name
+                    .and_then(|group_name| long_name.strip_prefix(group_name))
+                    .and_then(|long_name| long_name.strip_prefix('-'))
+                    .is_some_and(|long_name| long_name == self.long_name.as_str())
+        } else if let Some(short_name) = option_name.strip_prefix('-') {
+            let mut chars = short_name.chars();
+            matches!((chars.next(), chars.next()), (Some(c), None) if Some(c) == self.short_name)
+        } else {
+            false
+        }
+    }
+
+    unsafe fn activate(&mut self, option_name: &str, value: *const c_char) -> Result<(), Error> {
+        unsafe {
+            let string = if value.is_null() {
+                ""
+            } else {
+                GStr::from_ptr(value).as_str()
+            };
+
+            match self.callback {
+                Callback::Flag(ref mut f) => f(),
+                Callback::String(ref mut f) => f(string),
+                Callback::Int(ref mut f) => f(parse_int(option_name, string)?),
+                Callback::Int64(ref mut f) => f(parse_int(option_name, string)?),
+                Callback::Double(ref mut f) => f(parse_double(option_name, string)?),
+                Callback::Filename(ref mut f) => f(&PathBuf::from_glib_none(value)),
+                Callback::StringArray(_, ref mut values) => values.push(string.to_owned()),
+                Callback::FilenameArray(_, ref mut values) => {
+                    values.push(PathBuf::from_glib_none(value))
+                }
+            }
+
+            Ok(())
+        }
+    }
+
+    fn finish(&mut self) {
+        match self.callback {
+            Callback::StringArray(ref mut f, ref mut values) if !values.is_empty() => {
+                f(mem::take(values))
+            }
+            Callback::FilenameArray(ref mut f, ref mut values) if !values.is_empty() => {
+                f(mem::take(values))
+            }
+            _ => (),
+        }
+    }
+
+    fn reset(&mut self) {
+        match self.callback {
+            Callback::StringArray(_, ref mut values) => values.clear(),
+            Callback::FilenameArray(_, ref mut values) => values.clear(),
+            _ => (),
+        }
+    }
+
+    fn to_ffi(&self) -> ffi::GOptionEntry {
+        let mut flags = self.flags;
+        match self.callback {
+            Callback::Flag(_) => flags |= OptionFlags::NO_ARG,
+            Callback::Filename(_) | Callback::FilenameArray(..) => flags |= OptionFlags::FILENAME,
+            _ => (),
+        }
+
+        ffi::GOptionEntry {
+            long_name: self.long_name.as_ptr(),
+            short_name: self.short_name.map_or(0, |c| c as c_char),
+            flags: flags.bits() as i32,
+            arg: ffi::G_OPTION_ARG_CALLBACK,
+            arg_data: parse_arg_trampoline as *const () as ffi::gpointer,
+            description: self
+                .description
+                .as_ref()
+                .map_or(ptr::null(), |description| description.as_ptr()),
+            arg_description: self
+                .arg_description
+                .as_ref()
+                .map_or(ptr::null(), |arg_description| arg_description.as_ptr()),
+        }
+    }
+}
+
+impl fmt::Debug for OptionEntry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OptionEntry")
+            .field("long_name", &self.long_name)
+            .field("short_name", &self.short_name)
+            .field("flags", &self.flags)
+            .field("arg", &self.arg())
+            .field("description", &self.description)
+            .field("arg_description", &self.arg_description)
+            .finish()
+    }
+}
+
+// Parses integers like `G_OPTION_ARG_INT` and `G_OPTION_ARG_INT64` do with `strtol()` in base 0:
+// an optional sign followed by a hexadecimal number with a `0x` prefix, an octal number with a `0`
+// prefix or a decimal number.
+fn parse_int<T: TryFrom<i128>>(option_name: &str, value: &str) -> Result<T, Error> {
+    let s = value.trim_start();
+    let (negative, s) = match s.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, s.strip_prefix('+').unwrap_or(s)),
+    };
+    let (radix, digits) = if let Some(digits) = s.strip_prefix("0x").or(s.strip_prefix("0X")) {
+        (16, digits)
+    } else if let Some(digits) = s.strip_prefix('0').filter(|digits| !digits.is_empty()) {
+        (8, digits)
+    } else {
+        (10, s)
+    };
+
+    if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+        return Err(Error::new(
+            OptionError::BadValue,
+            &format!("Cannot parse integer value “{value}” for {option_name}"),
+        ));
+    }
+
+    u64::from_str_radix(digits, radix)
+        .ok()
+        .map(|n| {
+            if negative {
+                -i128::from(n)
+            } else {
+                i128::from(n)
+            }
+        })
+        .and_then(|n| T::try_from(n).ok())
+        .ok_or_else(|| {
+            Error::new(
+                OptionError::BadValue,
+                &format!("Integer value “{value}” for {option_name} out of range"),
+            )
+        })
+}
+
+fn parse_double(option_name: &str, value: &str) -> Result<f64, Error> {
+    value.trim().parse().map_err(|_| {
+        Error::new(
+            OptionError::BadValue,
+            &format!("Cannot parse double value “{value}” for {option_name}"),
+        )
+    })
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for [`OptionEntry`].
+///
+/// The entry is created by one of the methods that set the callback, which also determines the
+/// type of the value of the option.
+#[must_use = "The builder must be built to be used"]
+pub struct OptionEntryBuilder {
+    long_name: GString,
+    short_name: Option<char>,
+    flags: OptionFlags,
+    description: Option<GString>,
+    arg_description: Option<GString>,
+}
+
+impl OptionEntryBuilder {
+    // rustdoc-stripper-ignore-next
+    /// Sets the short name of the option, which is passed as `-c`.
+    pub fn short_name(mut self, short_name: char) -> Self {
+        assert!(
+            short_name.is_ascii_graphic() && short_name != '-',
+            "Invalid short option name '{short_name}'"
+        );
+        self.short_name = Some(short_name);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets flags like [`OptionFlags::HIDDEN`] or [`OptionFlags::OPTIONAL_ARG`].
+    pub fn flags(mut self, flags: OptionFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the description of the option in `--help` output.
+    pub fn description(mut self, description: &str) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the placeholder for the value of the option in `--help` output, e.g. `FILE`.
+    pub fn arg_description(mut self, arg_description: &str) -> Self {
+        self.arg_description = Some(arg_description.into());
+        self
+    }
+
+    fn build(self, callback: Callback) -> OptionEntry {
+        OptionEntry {
+            long_name: self.long_name,
+            short_name: self.short_name,
+            flags: self.flags,
+            description: self.description,
+            arg_description: self.arg_description,
+            callback,
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an option without value.
+    pub fn flag<F: FnMut() + 'static>(self, f: F) -> OptionEntry {
+        self.build(Callback::Flag(Box::new(f)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an option with a UTF-8 string value.
+    pub fn string<F: FnMut(&str) + 'static>(self, f: F) -> OptionEntry {
+        self.build(Callback::String(Box::new(f)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an option with an integer value. Like in C, the value can be given in
+    /// hexadecimal with a `0x` prefix or in octal with a `0` prefix.
+    pub fn int<F: FnMut(i32) + 'static>(self, f: F) -> OptionEntry {
+        self.build(Callback::Int(Box::new(f)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an option with a 64-bit integer value, which is parsed like for
+    /// [`int`](Self::int).
+    pub fn int64<F: FnMut(i64) + 'static>(self, f: F) -> OptionEntry {
+        self.build(Callback::Int64(Box::new(f)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an option with a floating point value.
+    pub fn double<F: FnMut(f64) + 'static>(self, f: F) -> OptionEntry {
+        self.build(Callback::Double(Box::new(f)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an option with a file name value, which is not required to be UTF-8.
+    pub fn filename<F: FnMut(&Path) + 'static>(self, f: F) -> OptionEntry {
+        self.build(Callback::Filename(Box::new(f)))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an option that can be passed multiple times. The callback is called once with
+    /// all values after parsing.
+    pub fn string_array<F: FnMut(Vec<String>) + 'static>(self, f: F) -> OptionEntry {
+        self.build(Callback::StringArray(Box::new(f), Vec::new()))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates an option that can be passed multiple times. The callback is called once with
+    /// all values after parsing.
+    pub fn filename_array<F: FnMut(Vec<PathBuf>) + 'static>(self, f: F) -> OptionEntry {
+        self.build(Callback::FilenameArray(Box::new(f), Vec::new()))
+    }
+}
+
+thread_local! {
+    // The entries of option groups that call the callbacks without user data while a context
+    // that contains their hook group is parsed, see `OptionEntries`.
+    static ACTIVE_ENTRIES: RefCell<Vec<Rc<GroupData>>> = const { RefCell::new(Vec::new()) };
+}
+
+// The user data of option groups created from Rust.
+struct GroupData {
+    name: Option<GString>,
+    // Boxed because GLib keeps pointers to the strings of the entries.
+    #[allow(clippy::vec_box)]
+    entries: RefCell<Vec<Box<OptionEntry>>>,
+}
+
+impl GroupData {
+    // Stores the entries and returns their FFI representation, terminated by an entry with
+    // `NULL` long name.
+    fn push_entries(
+        &self,
+        entries: impl IntoIterator<Item = OptionEntry>,
+    ) -> Vec<ffi::GOptionEntry> {
+        let mut ffi_entries = Vec::new();
+        let mut self_entries = self.entries.borrow_mut();
+        for entry in entries {
+            let entry = Box::new(entry);
+            ffi_entries.push(entry.to_ffi());
+            self_entries.push(entry);
+        }
+
+        ffi_entries.push(unsafe { mem::zeroed() });
+        ffi_entries
+    }
+
+    unsafe fn add_entries(
+        &self,
+        group: *mut ffi::GOptionGroup,
+        entries: impl IntoIterator<Item = OptionEntry>,
+    ) {
+        unsafe {
+            let ffi_entries = self.push_entries(entries);
+            ffi::g_option_group_add_entries(group, ffi_entries.as_ptr());
+        }
+    }
+
+    fn reset(&self) {
+        self.entries
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|entry| entry.reset());
+    }
+
+    fn finish(&self) {
+        self.entries
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|entry| entry.finish());
+    }
+
+    unsafe fn activate(
+        &self,
+        option_name: &str,
+        value: *const c_char,
+    ) -> Option<Result<(), Error>> {
+        unsafe {
+            let mut entries = self.entries.borrow_mut();
+            let entry = entries
+                .iter_mut()
+                .find(|entry| entry.matches(option_name, self.name.as_deref()))?;
+            Some(entry.activate(option_name, value))
+        }
+    }
+}
+
+unsafe extern "C" fn parse_arg_trampoline(
+    option_name: *const c_char,
+    value: *const c_char,
+    data: ffi::gpointer,
+    error: *mut *mut ffi::GError,
+) -> ffi::gboolean {
+    unsafe {
+        let option_name = GStr::from_ptr(option_name).as_str();
+
+        let res = if data.is_null() {
+            // Only the entries of the context that is parsed right now are active. Most recently
+            // activated entries belong to the innermost context if parsing is nested.
+            let groups = ACTIVE_ENTRIES.with(|groups| groups.borrow().clone());
+            groups
+                .iter()
+                .rev()
+                .find_map(|data| data.activate(option_name, value))
+        } else {
+            (*(data as *const GroupData)).activate(option_name, value)
+        };
+        let res = res.unwrap_or_else(|| {
+            Err(Error::new(
+                OptionError::UnknownOption,
+                &format!("Unknown option {option_name}"),
+            ))
+        });
+
+        match res {
+            Ok(()) => ffi::GTRUE,
+            Err(err) => {
+                *error = err.into_glib_ptr();
+                ffi::GFALSE
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn pre_parse_trampoline(
+    _context: *mut ffi::GOptionContext,
+    _group: *mut ffi::GOptionGroup,
+    data: ffi::gpointer,
+    _error: *mut *mut ffi::GError,
+) -> ffi::gboolean {
+    unsafe {
+        (*(data as *const GroupData)).reset();
+        ffi::GTRUE
+    }
+}
+
+unsafe extern "C" fn post_parse_trampoline(
+    _context: *mut ffi::GOptionContext,
+    _group: *mut ffi::GOptionGroup,
+    data: ffi::gpointer,
+    _error: *mut *mut ffi::GError,
+) -> ffi::gboolean {
+    unsafe {
+        (*(data as *const GroupData)).finish();
+        ffi::GTRUE
+    }
+}
+
+unsafe extern "C" fn destroy_group_data(data: ffi::gpointer) {
+    unsafe {
+        let _ = Box::from_raw(data as *mut GroupData);
+    }
+}
+
+unsafe extern "C" fn entries_pre_parse_trampoline(
+    _context: *mut ffi::GOptionContext,
+    _group: *mut ffi::GOptionGroup,
+    data: ffi::gpointer,
+    _error: *mut *mut ffi::GError,
+) -> ffi::gboolean {
+    unsafe {
+        let entries = &*(data as *const OptionEntries);
+        entries.data.reset();
+        ACTIVE_ENTRIES.with(|groups| groups.borrow_mut().push(entries.data.clone()));
+        ffi::GTRUE
+    }
+}
+
+unsafe extern "C" fn entries_post_parse_trampoline(
+    _context: *mut ffi::GOptionContext,
+    _group: *mut ffi::GOptionGroup,
+    data: ffi::gpointer,
+    _error: *mut *mut ffi::GError,
+) -> ffi::gboolean {
+    unsafe {
+        let entries = &*(data as *const OptionEntries);
+        entries.deactivate();
+        entries.data.finish();
+        ffi::GTRUE
+    }
+}
+
+unsafe extern "C" fn entries_error_trampoline(
+    _context: *mut ffi::GOptionContext,
+    _group: *mut ffi::GOptionGroup,
+    data: ffi::gpointer,
+    _error: *mut *mut ffi::GError,
+) {
+    unsafe {
+        (*(data as *const OptionEntries)).deactivate();
+    }
+}
+
+unsafe extern "C" fn destroy_entries(data: ffi::gpointer) {
+    unsafe {
+        let _ = Box::from_raw(data as *mut OptionEntries);
+    }
+}
+
+crate::wrapper! {
+    // rustdoc-stripper-ignore-next
+    /// A group of command line options.
+    ///
+    /// Groups are added to an [`OptionContext`] or to a `gio::Application`. Their options are
+    /// shown in a separate section of the `--help` output.
+    #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    #[doc(alias = "GOptionGroup")]
+    pub struct OptionGroup(Shared<ffi::GOptionGroup>);
+
+    match fn {
+        ref => |ptr| ffi::g_option_group_ref(ptr),
+        unref => |ptr| ffi::g_option_group_unref(ptr),
+        type_ => || ffi::g_option_group_get_type(),
+    }
+}
+
+impl OptionGroup {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new option group with the given entries.
+    ///
+    /// The group's options can also be passed as `--name-long-name`, and its help is shown with
+    /// `--help-name`.
+    #[doc(alias = "g_option_group_new")]
+    pub fn new(
+        name: &str,
+        description: &str,
+        help_description: &str,
+        entries: impl IntoIterator<Item = OptionEntry>,
+    ) -> OptionGroup {
+        unsafe {
+            Self::with_entries(
+                Some(name),
+                Some(description),
+                Some(help_description),
+                entries,
+            )
+            .0
+        }
+    }
+
+    unsafe fn with_entries(
+        name: Option<&str>,
+        description: Option<&str>,
+        help_description: Option<&str>,
+        entries: impl IntoIterator<Item = OptionEntry>,
+    ) -> (OptionGroup, ptr::NonNull<GroupData>) {
+        unsafe {
+            let data = Box::new(GroupData {
+                name: name.map(GString::from),
+                entries: RefCell::new(Vec::new()),
+            });
+            let data_ptr = ptr::NonNull::from(&*data);
+
+            let group = ffi::g_option_group_new(
+                name.to_glib_none().0,
+                description.to_glib_none().0,
+                help_description.to_glib_none().0,
+                Box::into_raw(data) as ffi::gpointer,
+                Some(destroy_group_data),
+            );
+            ffi::g_option_group_set_parse_hooks(
+                group,
+                Some(pre_parse_trampoline),
+                Some(post_parse_trampoline),
+            );
+            data_ptr.as_ref().add_entries(group, entries);
+
+            (from_glib_full(group), data_ptr)
+        }
+    }
+
+    #[doc(alias = "g_option_group_set_translation_domain")]
+    pub fn set_translation_domain(&self, domain: &str) {
+        unsafe {
+            ffi::g_option_group_set_translation_domain(
+                self.to_glib_none().0,
+                domain.to_glib_none().0,
+            );
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Command line options for option groups that are not created from Rust, like the main group of
+/// `GApplication`.
+///
+/// GLib calls the callbacks of such groups without user data. To find the entries the callbacks
+/// belong to, the group returned by [`into_group`](Self::into_group) has to be added to the
+/// same [`OptionContext`]. It has no options of its own, but while the context is parsed its
+/// entries are looked up by option name, and the callbacks of array options are called after
+/// parsing. The group also keeps the entries alive.
+#[doc(alias = "GOptionEntry")]
+pub struct OptionEntries {
+    data: Rc<GroupData>,
+    ffi_entries: Vec<ffi::GOptionEntry>,
+}
+
+impl OptionEntries {
+    pub fn new(entries: impl IntoIterator<Item = OptionEntry>) -> OptionEntries {
+        let data = Rc::new(GroupData {
+            name: None,
+            entries: RefCell::new(Vec::new()),
+        });
+        let ffi_entries = data.push_entries(entries);
+
+        OptionEntries { data, ffi_entries }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the entries as an array terminated by an entry with `NULL` long name, e.g. for
+    /// `g_option_group_add_entries()`.
+    pub fn as_ptr(&self) -> *const ffi::GOptionEntry {
+        self.ffi_entries.as_ptr()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Converts the entries into a group without options that activates them while the
+    /// [`OptionContext`] it is added to is parsed.
+    ///
+    /// The entries must have been added to another group of the same context with
+    /// [`as_ptr`](Self::as_ptr).
+    #[doc(alias = "g_option_group_set_parse_hooks")]
+    pub fn into_group(self) -> OptionGroup {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        // Groups of a context need unique names. Without entries, the group is not shown in
+        // `--help` output.
+        let name = format!(
+            "rust-option-entries-{}",
+            COUNTER.fetch_add(1, atomic::Ordering::Relaxed)
+        );
+        unsafe {
+            let group = ffi::g_option_group_new(
+                name.to_glib_none().0,
+                ptr::null(),
+                ptr::null(),
+                Box::into_raw(Box::new(self)) as ffi::gpointer,
+                Some(destroy_entries),
+            );
+            ffi::g_option_group_set_parse_hooks(
+                group,
+                Some(entries_pre_parse_trampoline),
+                Some(entries_post_parse_trampoline),
+            );
+            ffi::g_option_group_set_error_hook(group, Some(entries_error_trampoline));
+            from_glib_full(group)
+        }
+    }
+
+    fn deactivate(&self) {
+        let _ = ACTIVE_ENTRIES.try_with(|groups| {
+            let mut groups = groups.borrow_mut();
+            if let Some(i) = groups.iter().rposition(|data| Rc::ptr_eq(data, &self.data)) {
+                groups.remove(i);
+            }
+        });
+    }
+}
+
+impl fmt::Debug for OptionEntries {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list()
+            .entries(self.data.entries.borrow().iter())
+            .finish()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A parser for command line options.
+///
+/// ```
+/// use std::{cell::Cell, rc::Rc};
+///
+/// let verbose = Rc::new(Cell::new(false));
+/// let count = Rc::new(Cell::new(0));
+///
+/// let mut context = glib::OptionContext::new(Some("FILE"));
+/// context.add_main_entries([
+///     glib::OptionEntry::builder("verbose")
+///         .short_name('v')
+///         .description("Be verbose")
+///         .flag(glib::clone!(#[strong] verbose, move || verbose.set(true))),
+///     glib::OptionEntry::builder("count")
+///         .arg_description("N")
+///         .int(glib::clone!(#[strong] count, move |n| count.set(n))),
+/// ]);
+///
+/// let mut args = vec!["prog".into(), "-v".into(), "--count=3".into(), "file".into()];
+/// context.parse(&mut args).unwrap();
+///
+/// assert!(verbose.get());
+/// assert_eq!(count.get(), 3);
+/// assert_eq!(args, ["prog", "file"]);
+/// ```
+#[doc(alias = "GOptionContext")]
+pub struct OptionContext {
+    ptr: ptr::NonNull<ffi::GOptionContext>,
+    // The main group if it was created by `add_main_entries()`.
+    main_group: Option<(OptionGroup, ptr::NonNull<GroupData>)>,
+}
+
+impl OptionContext {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new option context. `parameter_string` describes the remaining arguments in
+    /// the first line of `--help` output, e.g. `FILE…`.
+    #[doc(alias = "g_option_context_new")]
+    pub fn new(parameter_string: Option<&str>) -> OptionContext {
+        unsafe {
+            OptionContext {
+                ptr: ptr::NonNull::new_unchecked(ffi::g_option_context_new(
+                    parameter_string.to_glib_none().0,
+                )),
+                main_group: None,
+            }
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds options to the main group of the context.
+    ///
+    /// # Panics
+    ///
+    /// This panics if the main group was set with [`set_main_group`](Self::set_main_group).
+    #[doc(alias = "g_option_context_add_main_entries")]
+    pub fn add_main_entries(&mut self, entries: impl IntoIterator<Item = OptionEntry>) {
+        unsafe {
+            if let Some((ref group, data)) = self.main_group {
+                data.as_ref().add_entries(group.to_glib_none().0, entries);
+                return;
+            }
+
+            assert!(
+                ffi::g_option_context_get_main_group(self.ptr.as_ptr()).is_null(),
+                "Can't add entries to a main group set with `set_main_group()`"
+            );
+
+            let (group, data) = OptionGroup::with_entries(None, None, None, entries);
+            self.set_main_group(group.clone());
+            self.main_group = Some((group, data));
+        }
+    }
+
+    #[doc(alias = "g_option_context_add_group")]
+    pub fn add_group(&mut self, group: OptionGroup) {
+        unsafe {
+            ffi::g_option_context_add_group(self.ptr.as_ptr(), group.into_glib_ptr());
+        }
+    }
+
+    #[doc(alias = "g_option_context_set_main_group")]
+    pub fn set_main_group(&mut self, group: OptionGroup) {
+        self.main_group = None;
+        unsafe {
+            ffi::g_option_context_set_main_group(self.ptr.as_ptr(), group.into_glib_ptr());
+        }
+    }
+
+    #[doc(alias = "g_option_context_get_main_group")]
+    #[doc(alias = "get_main_group")]
+    pub fn main_group(&self) -> Option<OptionGroup> {
+        unsafe { from_glib_none(ffi::g_option_context_get_main_group(self.ptr.as_ptr())) }
+    }
+
+    #[doc(alias = "g_option_context_get_help")]
+    #[doc(alias = "get_help")]
+    pub fn help(&self, main_help: bool, group: Option<&OptionGroup>) -> GString {
+        unsafe {
+            from_glib_full(ffi::g_option_context_get_help(
+                self.ptr.as_ptr(),
+                main_help.into_glib(),
+                group.to_glib_none().0,
+            ))
+        }
+    }
+
+    #[doc(alias = "g_option_context_get_summary")]
+    #[doc(alias = "get_summary")]
+    pub fn summary(&self) -> Option<GString> {
+        unsafe { from_glib_none(ffi::g_option_context_get_summary(self.ptr.as_ptr())) }
+    }
+
+    #[doc(alias = "g_option_context_set_summary")]
+    pub fn set_summary(&mut self, summary: Option<&str>) {
+        unsafe {
+            ffi::g_option_context_set_summary(self.ptr.as_ptr(), summary.to_glib_none().0);
+        }
+    }
+
+    #[doc(alias = "g_option_context_get_description")]
+    #[doc(alias = "get_description")]
+    pub fn description(&self) -> Option<GString> {
+        unsafe { from_glib_none(ffi::g_option_context_get_description(self.ptr.as_ptr())) }
+    }
+
+    #[doc(alias = "g_option_context_set_description")]
+    pub fn set_description(&mut self, description: Option<&str>) {
+        unsafe {
+            ffi::g_option_context_set_description(self.ptr.as_ptr(), description.to_glib_none().0);
+        }
+    }
+
+    #[doc(alias = "g_option_context_get_help_enabled")]
+    #[doc(alias = "get_help_enabled")]
+    pub fn is_help_enabled(&self) -> bool {
+        unsafe { from_glib(ffi::g_option_context_get_help_enabled(self.ptr.as_ptr())) }
+    }
+
+    #[doc(alias = "g_option_context_set_help_enabled")]
+    pub fn set_help_enabled(&mut self, help_enabled: bool) {
+        unsafe {
+            ffi::g_option_context_set_help_enabled(self.ptr.as_ptr(), help_enabled.into_glib());
+        }
+    }
+
+    #[doc(alias = "g_option_context_get_ignore_unknown_options")]
+    #[doc(alias = "get_ignore_unknown_options")]
+    pub fn ignores_unknown_options(&self) -> bool {
+        unsafe {
+            from_glib(ffi::g_option_context_get_ignore_unknown_options(
+                self.ptr.as_ptr(),
+            ))
+        }
+    }
+
+    #[doc(alias = "g_option_context_set_ignore_unknown_options")]
+    pub fn set_ignore_unknown_options(&mut self, ignore_unknown: bool) {
+        unsafe {
+            ffi::g_option_context_set_ignore_unknown_options(
+                self.ptr.as_ptr(),
+                ignore_unknown.into_glib(),
+            );
+        }
+    }
+
+    #[doc(alias = "g_option_context_get_strict_posix")]
+    #[doc(alias = "get_strict_posix")]
+    pub fn is_strict_posix(&self) -> bool {
+        unsafe { from_glib(ffi::g_option_context_get_strict_posix(self.ptr.as_ptr())) }
+    }
+
+    #[doc(alias = "g_option_context_set_strict_posix")]
+    pub fn set_strict_posix(&mut self, strict_posix: bool) {
+        unsafe {
+            ffi::g_option_context_set_strict_posix(self.ptr.as_ptr(), strict_posix.into_glib());
+        }
+    }
+
+    #[doc(alias = "g_option_context_set_translation_domain")]
+    pub fn set_translation_domain(&mut self, domain: &str) {
+        unsafe {
+            ffi::g_option_context_set_translation_domain(
+                self.ptr.as_ptr(),
+                domain.to_glib_none().0,
+            );
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Parses the command line arguments `args`, including the program name, and calls the
+    /// callbacks of all options that are found.
+    ///
+    /// Parsed options are removed from `args`, also if parsing fails. Unless disabled, `--help`
+    /// prints the help and exits the process.
+    #[doc(alias = "g_option_context_parse_strv")]
+    #[doc(alias = "g_option_context_parse")]
+    pub fn parse(&mut self, args: &mut Vec<OsString>) -> Result<(), Error> {
+        unsafe {
+            let mut argv: *mut *mut c_char = args.as_slice().to_glib_full();
+            let mut error = ptr::null_mut();
+            let is_ok = ffi::g_option_context_parse_strv(self.ptr.as_ptr(), &mut argv, &mut error);
+            *args = FromGlibPtrContainer::from_glib_full(argv);
+            debug_assert_eq!(is_ok == ffi::GFALSE, !error.is_null());
+            if error.is_null() {
+                Ok(())
+            } else {
+                Err(from_glib_full(error))
+            }
+        }
+    }
+}
+
+impl Drop for OptionContext {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::g_option_context_free(self.ptr.as_ptr());
+        }
+    }
+}
+
+impl fmt::Debug for OptionContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OptionContext")
+            .field("summary", &self.summary())
+            .field("description", &self.description())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc};
+
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<OsString> {
+        args.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn parse() {
+        let flag = Rc::new(Cell::new(false));
+        let string = Rc::new(RefCell::new(String::new()));
+        let int64 = Rc::new(Cell::new(0));
+        let double = Rc::new(Cell::new(0.0));
+        let filename = Rc::new(RefCell::new(PathBuf::new()));
+        let strings = Rc::new(RefCell::new(Vec::new()));
+        let filenames = Rc::new(RefCell::new(Vec::new()));
+
+        let mut context = OptionContext::new(None);
+        context.add_main_entries([
+            OptionEntry::builder("flag").short_name('f').flag({
+                let flag = flag.clone();
+                move || flag.set(true)
+            }),
+            OptionEntry::builder("string").string({
+                let string = string.clone();
+                move |s| *string.borrow_mut() = s.to_owned()
+            }),
+        ]);
+        context.add_main_entries([OptionEntry::builder("int64").int64({
+            let int64 = int64.clone();
+            move |i| int64.set(i)
+        })]);
+        context.add_group(OptionGroup::new(
+            "group",
+            "Group options",
+            "Show group options",
+            [
+                OptionEntry::builder("double").double({
+                    let double = double.clone();
+                    move |d| double.set(d)
+                }),
+                OptionEntry::builder("file").filename({
+                    let filename = filename.clone();
+                    move |f| *filename.borrow_mut() = f.to_owned()
+                }),
+                OptionEntry::builder("strings").string_array({
+                    let strings = strings.clone();
+                    move |s| *strings.borrow_mut() = s
+                }),
+                OptionEntry::builder("files").filename_array({
+                    let filenames = filenames.clone();
+                    move |f| *filenames.borrow_mut() = f
+                }),
+            ],
+        ));
+
+        let mut argv = args(&[
+            "prog",
+            "-f",
+            "--string=foo",
+            "--int64",
+            "-5000000000",
+            "--group-double=1.5",
+            "--file",
+            "/tmp/a",
+            "--strings=a",
+            "rest",
+            "--strings=b",
+            "--files=x",
+        ]);
+        context.parse(&mut argv).unwrap();
+
+        assert_eq!(argv, args(&["prog", "rest"]));
+        assert!(flag.get());
+        assert_eq!(*string.borrow(), "foo");
+        assert_eq!(int64.get(), -5000000000);
+        assert_eq!(double.get(), 1.5);
+        assert_eq!(*filename.borrow(), Path::new("/tmp/a"));
+        assert_eq!(*strings.borrow(), ["a", "b"]);
+        assert_eq!(*filenames.borrow(), [PathBuf::from("x")]);
+
+        let help = context.help(true, None);
+        assert!(help.contains("--flag"));
+        assert!(help.contains("--help-group"));
+    }
+
+    #[test]
+    fn unowned_entries() {
+        // Creates a group without user data like `GApplication` does for its main group.
+        unsafe fn unowned_group(entries: &OptionEntries) -> OptionGroup {
+            unsafe {
+                let group: OptionGroup = from_glib_full(ffi::g_option_group_new(
+                    ptr::null(),
+                    ptr::null(),
+                    ptr::null(),
+                    ptr::null_mut(),
+                    None,
+                ));
+                ffi::g_option_group_add_entries(group.to_glib_none().0, entries.as_ptr());
+                group
+            }
+        }
+
+        let count = Rc::new(Cell::new(0));
+        let strings = Rc::new(RefCell::new(Vec::new()));
+        let entries = OptionEntries::new([
+            OptionEntry::builder("count").int({
+                let count = count.clone();
+                move |n| count.set(n)
+            }),
+            OptionEntry::builder("strings").string_array({
+                let strings = strings.clone();
+                move |s| *strings.borrow_mut() = s
+            }),
+        ]);
+        // Entries of another context with the same option name.
+        let other_count = Rc::new(Cell::new(0));
+        let other_entries = OptionEntries::new([OptionEntry::builder("count").int({
+            let other_count = other_count.clone();
+            move |n| other_count.set(n)
+        })]);
+
+        let mut context = OptionContext::new(None);
+        context.set_main_group(unsafe { unowned_group(&entries) });
+        context.add_group(entries.into_group());
+
+        let mut other_context = OptionContext::new(None);
+        other_context.set_main_group(unsafe { unowned_group(&other_entries) });
+        other_context.add_group(other_entries.into_group());
+
+        let mut argv = args(&["prog", "--count=3", "--strings=a", "--strings=b"]);
+        context.parse(&mut argv).unwrap();
+        assert_eq!(count.get(), 3);
+        assert_eq!(*strings.borrow(), ["a", "b"]);
+        assert_eq!(other_count.get(), 0);
+
+        let mut argv = args(&["prog", "--count=4"]);
+        other_context.parse(&mut argv).unwrap();
+        assert_eq!(count.get(), 3);
+        assert_eq!(other_count.get(), 4);
+
+        let mut argv = args(&["prog", "--count=foo"]);
+        let err = context.parse(&mut argv).unwrap_err();
+        assert!(err.matches(OptionError::BadValue));
+        assert!(ACTIVE_ENTRIES.with(|groups| groups.borrow().is_empty()));
+    }
+
+    #[test]
+    fn parse_int_prefixes() {
+        let values = Rc::new(RefCell::new(Vec::new()));
+        let mut context = OptionContext::new(None);
+        context.add_main_entries([
+            OptionEntry::builder("int").int({
+                let values = values.clone();
+                move |n| values.borrow_mut().push(i64::from(n))
+            }),
+            OptionEntry::builder("int64").int64({
+                let values = values.clone();
+                move |n| values.borrow_mut().push(n)
+            }),
+        ]);
+
+        let mut argv = args(&[
+            "prog",
+            "--int=0x10",
+            "--int=017",
+            "--int=-0X1f",
+            "--int=0",
+            "--int64=+010",
+            "--int64=-0x8000000000000000",
+        ]);
+        context.parse(&mut argv).unwrap();
+        assert_eq!(*values.borrow(), [16, 15, -31, 0, 8, i64::MIN]);
+
+        for value in ["--int=08", "--int=0x", "--int=1 "] {
+            let mut argv = args(&["prog", value]);
+            let err = context.parse(&mut argv).unwrap_err();
+            assert!(err.matches(OptionError::BadValue));
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let mut context = OptionContext::new(None);
+        context.add_main_entries([OptionEntry::builder("int").int(|_| ())]);
+
+        let mut argv = args(&["prog", "--int=foo"]);
+        let err = context.parse(&mut argv).unwrap_err();
+        assert!(err.matches(OptionError::BadValue));
+
+        let mut argv = args(&["prog", "--int=0x100000000"]);
+        let err = context.parse(&mut argv).unwrap_err();
+        assert!(err.matches(OptionError::BadValue));
+
+        let mut argv = args(&["prog", "--unknown"]);
+        let err = context.parse(&mut argv).unwrap_err();
+        assert!(err.matches(OptionError::UnknownOption));
+    }
+}
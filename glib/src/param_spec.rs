@@ -766,7 +766,7 @@ macro_rules! define_builder_numeric {
 
 #[track_caller]
 // the default panic formatter will use its caller as the location in its error message
-fn assert_param_name(name: &str) {
+pub(crate) fn assert_param_name(name: &str) {
     assert!(
         is_canonical_pspec_name(name),
         "{name} is not a valid canonical parameter name",
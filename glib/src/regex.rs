@@ -5,8 +5,8 @@
 //! compatibility with GLib.Regex based APIs.
 
 use crate::{
-    GStr, GStringPtr, MatchInfo, PtrSlice, Regex, RegexCompileFlags, RegexMatchFlags, ffi,
-    translate::*,
+    ControlFlow, GStr, GStringBuilder, GStringPtr, MatchInfo, PtrSlice, Regex, RegexCompileFlags,
+    RegexMatchFlags, ffi, translate::*,
 };
 use std::{mem, ptr};
 
@@ -223,6 +223,75 @@ impl Regex {
         }
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Replaces all matches with the text that `eval` appends to the passed builder.
+    ///
+    /// Returning [`ControlFlow::Break`] from `eval` stops the replacement, the rest of the string
+    /// is copied unchanged.
+    ///
+    /// ```
+    /// use glib::{Regex, RegexCompileFlags, RegexMatchFlags};
+    ///
+    /// let regex = Regex::new(r"\d+", RegexCompileFlags::DEFAULT, RegexMatchFlags::DEFAULT)
+    ///     .unwrap()
+    ///     .unwrap();
+    /// let doubled = regex
+    ///     .replace_eval("1 apple, 21 pears", 0, RegexMatchFlags::DEFAULT, |info, result| {
+    ///         let n = info.fetch(0).unwrap().parse::<u32>().unwrap();
+    ///         result.append(&(n * 2).to_string());
+    ///         glib::ControlFlow::Continue
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(doubled, "2 apple, 42 pears");
+    /// ```
+    #[doc(alias = "g_regex_replace_eval")]
+    pub fn replace_eval<F: FnMut(&MatchInfo<'_>, &mut GStringBuilder) -> ControlFlow>(
+        &self,
+        string: impl IntoGStr,
+        start_position: i32,
+        match_options: RegexMatchFlags,
+        eval: F,
+    ) -> Result<crate::GString, crate::Error> {
+        unsafe extern "C" fn eval_trampoline<
+            F: FnMut(&MatchInfo<'_>, &mut GStringBuilder) -> ControlFlow,
+        >(
+            match_info: *const ffi::GMatchInfo,
+            result: *mut ffi::GString,
+            user_data: ffi::gpointer,
+        ) -> ffi::gboolean {
+            unsafe {
+                let eval = &mut *(user_data as *mut F);
+                let match_info = mut_override(match_info);
+                let match_info = MatchInfo::from_glib_ptr_borrow(&match_info);
+                let result = GStringBuilder::from_glib_ptr_borrow_mut(result);
+                eval(match_info, result).is_break().into_glib()
+            }
+        }
+
+        let mut eval = eval;
+        unsafe {
+            string.run_with_gstr(|string| {
+                let mut error = ptr::null_mut();
+                let ret = ffi::g_regex_replace_eval(
+                    self.to_glib_none().0,
+                    string.as_ptr() as *const _,
+                    string.len() as _,
+                    start_position,
+                    match_options.into_glib(),
+                    Some(eval_trampoline::<F>),
+                    &mut eval as *mut F as ffi::gpointer,
+                    &mut error,
+                );
+                debug_assert_eq!(ret.is_null(), !error.is_null());
+                if error.is_null() {
+                    Ok(from_glib_full(ret))
+                } else {
+                    Err(from_glib_full(error))
+                }
+            })
+        }
+    }
+
     #[doc(alias = "g_regex_split")]
     pub fn split(
         &self,
@@ -0,0 +1,405 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+use std::{
+    collections::BTreeMap,
+    ffi::{OsStr, OsString},
+    os::unix::io::{AsFd, BorrowedFd, OwnedFd, RawFd},
+    path::{Path, PathBuf},
+};
+
+use crate::{Error, Pid, SpawnFlags};
+
+// rustdoc-stripper-ignore-next
+/// How a standard stream of a child spawned with [`SpawnCommand`] is set up.
+#[derive(Debug, Default)]
+pub enum SpawnStdio {
+    // rustdoc-stripper-ignore-next
+    /// The child uses the stream of the parent.
+    #[default]
+    Inherit,
+    // rustdoc-stripper-ignore-next
+    /// The stream is redirected to `/dev/null`.
+    Null,
+    // rustdoc-stripper-ignore-next
+    /// A pipe to the parent is created and returned in [`SpawnedChild`].
+    Piped,
+    // rustdoc-stripper-ignore-next
+    /// The stream is redirected to the file descriptor, which is closed in the parent after
+    /// spawning.
+    Fd(OwnedFd),
+}
+
+impl From<OwnedFd> for SpawnStdio {
+    fn from(fd: OwnedFd) -> Self {
+        Self::Fd(fd)
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A child process spawned with [`SpawnCommand::spawn()`].
+///
+/// The pipes can be wrapped with `gio_unix::InputStream::take_fd()` and
+/// `gio_unix::OutputStream::take_fd()` for asynchronous I/O.
+#[derive(Debug)]
+pub struct SpawnedChild {
+    pub pid: Pid,
+    // rustdoc-stripper-ignore-next
+    /// The write end of the pipe to the standard input of the child, if [`SpawnStdio::Piped`].
+    pub stdin: Option<OwnedFd>,
+    // rustdoc-stripper-ignore-next
+    /// The read end of the pipe from the standard output of the child, if [`SpawnStdio::Piped`].
+    pub stdout: Option<OwnedFd>,
+    // rustdoc-stripper-ignore-next
+    /// The read end of the pipe from the standard error of the child, if [`SpawnStdio::Piped`].
+    pub stderr: Option<OwnedFd>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for spawning child processes with [`spawn_async_with_pipes_and_fds()`], similar to
+/// [`std::process::Command`].
+///
+/// The program is searched for in `PATH` and the child inherits the environment and the standard
+/// streams of the parent by default. Unless [`SpawnFlags::DO_NOT_REAP_CHILD`] is passed to
+/// [`flags()`](Self::flags), the child is reaped automatically.
+///
+/// ```no_run
+/// use std::io::Read;
+///
+/// let memfd = std::fs::File::open("/etc/hostname").unwrap();
+/// let child = glib::SpawnCommand::new("cat")
+///     .arg("/proc/self/fd/3")
+///     .fd(memfd, 3)
+///     .stdout(glib::SpawnStdio::Piped)
+///     .spawn()
+///     .unwrap();
+///
+/// let mut output = String::new();
+/// std::fs::File::from(child.stdout.unwrap())
+///     .read_to_string(&mut output)
+///     .unwrap();
+/// ```
+///
+/// [`spawn_async_with_pipes_and_fds()`]: crate::spawn_async_with_pipes_and_fds
+#[must_use = "The command must be spawned to be used"]
+pub struct SpawnCommand {
+    args: Vec<OsString>,
+    env_clear: bool,
+    env: BTreeMap<OsString, Option<OsString>>,
+    working_directory: Option<PathBuf>,
+    flags: SpawnFlags,
+    child_setup: Option<Box<dyn FnOnce() + 'static>>,
+    stdin: SpawnStdio,
+    stdout: SpawnStdio,
+    stderr: SpawnStdio,
+    fds: Vec<(OwnedFd, RawFd)>,
+}
+
+impl SpawnCommand {
+    pub fn new(program: impl AsRef<OsStr>) -> Self {
+        Self {
+            args: vec![program.as_ref().to_owned()],
+            env_clear: false,
+            env: BTreeMap::new(),
+            working_directory: None,
+            flags: SpawnFlags::SEARCH_PATH,
+            child_setup: None,
+            stdin: SpawnStdio::Inherit,
+            stdout: SpawnStdio::Inherit,
+            stderr: SpawnStdio::Inherit,
+            fds: Vec::new(),
+        }
+    }
+
+    pub fn arg(mut self, arg: impl AsRef<OsStr>) -> Self {
+        self.args.push(arg.as_ref().to_owned());
+        self
+    }
+
+    pub fn args<I: IntoIterator<Item = S>, S: AsRef<OsStr>>(mut self, args: I) -> Self {
+        self.args
+            .extend(args.into_iter().map(|arg| arg.as_ref().to_owned()));
+        self
+    }
+
+    pub fn env(mut self, key: impl AsRef<OsStr>, value: impl AsRef<OsStr>) -> Self {
+        self.env
+            .insert(key.as_ref().to_owned(), Some(value.as_ref().to_owned()));
+        self
+    }
+
+    pub fn env_remove(mut self, key: impl AsRef<OsStr>) -> Self {
+        self.env.insert(key.as_ref().to_owned(), None);
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Clears the environment, so that the child only gets the variables set with
+    /// [`env()`](Self::env) afterwards.
+    pub fn env_clear(mut self) -> Self {
+        self.env_clear = true;
+        self.env.clear();
+        self
+    }
+
+    pub fn current_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.working_directory = Some(dir.as_ref().to_owned());
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets the flags, in addition to the flags for the standard streams.
+    ///
+    /// This replaces the default [`SpawnFlags::SEARCH_PATH`]. Flags that redirect standard
+    /// streams, like [`SpawnFlags::STDOUT_TO_DEV_NULL`], are ignored in favor of
+    /// [`stdin()`](Self::stdin), [`stdout()`](Self::stdout) and [`stderr()`](Self::stderr).
+    pub fn flags(mut self, flags: SpawnFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Sets a function that is called in the child after forking, before the program is
+    /// executed. Only async-signal-safe functions may be called from it.
+    pub fn child_setup(mut self, child_setup: impl FnOnce() + 'static) -> Self {
+        self.child_setup = Some(Box::new(child_setup));
+        self
+    }
+
+    pub fn stdin(mut self, stdin: impl Into<SpawnStdio>) -> Self {
+        self.stdin = stdin.into();
+        self
+    }
+
+    pub fn stdout(mut self, stdout: impl Into<SpawnStdio>) -> Self {
+        self.stdout = stdout.into();
+        self
+    }
+
+    pub fn stderr(mut self, stderr: impl Into<SpawnStdio>) -> Self {
+        self.stderr = stderr.into();
+        self
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Passes `fd` to the child as file descriptor `target_fd`, e.g. for socket activation.
+    ///
+    /// `fd` is closed in the parent after spawning.
+    pub fn fd(mut self, fd: impl Into<OwnedFd>, target_fd: RawFd) -> Self {
+        self.fds.push((fd.into(), target_fd));
+        self
+    }
+
+    fn environment(&self) -> Option<Vec<OsString>> {
+        if !self.env_clear && self.env.is_empty() {
+            return None;
+        }
+
+        let mut env = if self.env_clear {
+            BTreeMap::new()
+        } else {
+            std::env::vars_os().collect::<BTreeMap<_, _>>()
+        };
+        for (key, value) in &self.env {
+            match value {
+                Some(value) => env.insert(key.clone(), value.clone()),
+                None => env.remove(key),
+            };
+        }
+
+        Some(
+            env.into_iter()
+                .map(|(key, value)| {
+                    let mut var = key;
+                    var.push("=");
+                    var.push(value);
+                    var
+                })
+                .collect(),
+        )
+    }
+
+    #[doc(alias = "g_spawn_async_with_pipes_and_fds")]
+    pub fn spawn(self) -> Result<SpawnedChild, Error> {
+        // The configured standard streams replace any flags for them, which GLib rejects in
+        // combination with pipes and file descriptors.
+        let [stdin_flags, stdout_flags, stderr_flags] = crate::functions::spawn_stdio_flags();
+        let mut flags = self.flags - (stdin_flags | stdout_flags | stderr_flags);
+        match self.stdin {
+            SpawnStdio::Inherit => flags |= SpawnFlags::CHILD_INHERITS_STDIN,
+            #[cfg(feature = "v2_74")]
+            SpawnStdio::Null => flags |= SpawnFlags::STDIN_FROM_DEV_NULL,
+            _ => (),
+        }
+        if matches!(self.stdout, SpawnStdio::Null) {
+            flags |= SpawnFlags::STDOUT_TO_DEV_NULL;
+        }
+        if matches!(self.stderr, SpawnStdio::Null) {
+            flags |= SpawnFlags::STDERR_TO_DEV_NULL;
+        }
+
+        fn stdio_fd(stdio: &SpawnStdio) -> Option<BorrowedFd<'_>> {
+            match stdio {
+                SpawnStdio::Fd(fd) => Some(fd.as_fd()),
+                _ => None,
+            }
+        }
+        let stdin_fd = stdio_fd(&self.stdin);
+        let stdout_fd = stdio_fd(&self.stdout);
+        let stderr_fd = stdio_fd(&self.stderr);
+
+        let env = self.environment();
+        let envp = env
+            .as_ref()
+            .map(|env| env.iter().map(OsString::as_os_str).collect::<Vec<_>>());
+        let argv = self
+            .args
+            .iter()
+            .map(OsString::as_os_str)
+            .collect::<Vec<_>>();
+        let source_fds = self
+            .fds
+            .iter()
+            .map(|(fd, _)| fd.as_fd())
+            .collect::<Vec<BorrowedFd<'_>>>();
+        let target_fds = self
+            .fds
+            .iter()
+            .map(|(_, target_fd)| *target_fd)
+            .collect::<Vec<_>>();
+
+        // Without a pipe or fd, standard input is redirected to `/dev/null` by GLib.
+        let piped = |stdio: &SpawnStdio| matches!(stdio, SpawnStdio::Piped);
+        let (pid, stdin, stdout, stderr) = crate::functions::spawn_with_pipes_and_fds(
+            self.working_directory.as_deref(),
+            &argv,
+            envp.as_deref(),
+            flags,
+            self.child_setup,
+            [stdin_fd, stdout_fd, stderr_fd],
+            [piped(&self.stdin), piped(&self.stdout), piped(&self.stderr)],
+            &source_fds,
+            &target_fds,
+        )?;
+
+        Ok(SpawnedChild {
+            pid,
+            stdin,
+            stdout,
+            stderr,
+        })
+    }
+}
+
+impl std::fmt::Debug for SpawnCommand {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("SpawnCommand")
+            .field("args", &self.args)
+            .field("env_clear", &self.env_clear)
+            .field("env", &self.env)
+            .field("working_directory", &self.working_directory)
+            .field("flags", &self.flags)
+            .field("stdin", &self.stdin)
+            .field("stdout", &self.stdout)
+            .field("stderr", &self.stderr)
+            .field("fds", &self.fds)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{Read, Seek, Write},
+    };
+
+    use super::*;
+
+    fn read_all(fd: OwnedFd) -> String {
+        let mut output = String::new();
+        File::from(fd).read_to_string(&mut output).unwrap();
+        output
+    }
+
+    #[test]
+    fn pipes_and_fds() {
+        let mut file = tempfile::tempfile().unwrap();
+        file.write_all(b"passed").unwrap();
+        file.rewind().unwrap();
+
+        let child = SpawnCommand::new("sh")
+            .arg("-c")
+            .arg("read line; echo \"$line $GLIB_SPAWN_TEST\"; cat <&5; echo error >&2")
+            .env("GLIB_SPAWN_TEST", "env")
+            .stdin(SpawnStdio::Piped)
+            .stdout(SpawnStdio::Piped)
+            .stderr(SpawnStdio::Null)
+            .fd(file, 5)
+            .spawn()
+            .unwrap();
+        assert!(child.stderr.is_none());
+
+        File::from(child.stdin.unwrap())
+            .write_all(b"input\n")
+            .unwrap();
+        assert_eq!(read_all(child.stdout.unwrap()), "input env\npassed");
+    }
+
+    #[test]
+    fn stdio_fd() {
+        let (reader, writer) = std::io::pipe().unwrap();
+
+        let child = SpawnCommand::new("echo")
+            .arg("redirected")
+            .stdout(OwnedFd::from(writer))
+            .spawn()
+            .unwrap();
+        assert!(child.stdin.is_none());
+        assert!(child.stdout.is_none());
+
+        assert_eq!(read_all(reader.into()), "redirected\n");
+    }
+
+    #[test]
+    fn env_clear() {
+        let child = SpawnCommand::new("/usr/bin/env")
+            .env_clear()
+            .env("A", "1")
+            .stdout(SpawnStdio::Piped)
+            .flags(SpawnFlags::DEFAULT)
+            .spawn()
+            .unwrap();
+        assert_eq!(read_all(child.stdout.unwrap()), "A=1\n");
+    }
+
+    #[test]
+    fn conflicting_flags() {
+        let child = SpawnCommand::new("cat")
+            .flags(SpawnFlags::SEARCH_PATH | SpawnFlags::CHILD_INHERITS_STDIN)
+            .stdin(SpawnStdio::Piped)
+            .stdout(SpawnStdio::Piped)
+            .spawn()
+            .unwrap();
+
+        File::from(child.stdin.unwrap())
+            .write_all(b"piped")
+            .unwrap();
+        assert_eq!(read_all(child.stdout.unwrap()), "piped");
+
+        let child = SpawnCommand::new("echo")
+            .flags(SpawnFlags::SEARCH_PATH | SpawnFlags::STDOUT_TO_DEV_NULL)
+            .stdout(SpawnStdio::Piped)
+            .spawn()
+            .unwrap();
+        assert_eq!(read_all(child.stdout.unwrap()), "\n");
+    }
+
+    #[test]
+    fn not_found() {
+        assert!(
+            SpawnCommand::new("glib-spawn-command-does-not-exist")
+                .spawn()
+                .is_err()
+        );
+    }
+}
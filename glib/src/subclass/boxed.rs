@@ -3,6 +3,14 @@
 // rustdoc-stripper-ignore-next
 //! Module for registering boxed types for Rust types.
 
+use std::{
+    any::TypeId,
+    cell::RefCell,
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    sync::{Mutex, OnceLock},
+};
+
 use crate::{ffi, gobject_ffi, prelude::*, translate::*};
 
 // rustdoc-stripper-ignore-next
@@ -47,7 +55,58 @@ pub trait BoxedType: StaticType + Clone + Sized + 'static {
 ///
 /// [`Boxed!`]: ../../derive.Boxed.html
 pub fn register_boxed_type<T: BoxedType>() -> crate::Type {
-    unsafe extern "C" fn boxed_copy<T: BoxedType>(v: ffi::gpointer) -> ffi::gpointer {
+    unsafe { register_boxed_type_with_name::<T>(T::NAME, T::ALLOW_NAME_CONFLICT) }
+}
+
+// rustdoc-stripper-ignore-next
+/// Returns the boxed `glib::Type` ID for `T`, registering it on first use.
+///
+/// Unlike [`register_boxed_type`] this does not require a [`BoxedType`] implementation and can
+/// be called any number of times, including for generic types such as tuples or
+/// `Result<T, E>`. The type name is derived from the [`TypeId`] of `T` and must not be relied
+/// upon.
+///
+/// This is used for the [`Value`](crate::Value) implementations of tuples, arrays, `Result` and
+/// `HashMap`, and by [`Boxed!`] when no type name is given.
+///
+/// [`Boxed!`]: ../../derive.Boxed.html
+pub fn register_boxed_type_on_demand<T: Clone + 'static>() -> crate::Type {
+    static TYPES: OnceLock<Mutex<HashMap<TypeId, crate::Type>>> = OnceLock::new();
+    thread_local! {
+        // Registered types never change, so every thread keeps its own copy of the ones it
+        // looked up before to avoid locking `TYPES` on each call.
+        static CACHE: RefCell<HashMap<TypeId, crate::Type>> = RefCell::default();
+    }
+
+    let id = TypeId::of::<T>();
+    if let Some(type_) = CACHE.with_borrow(|cache| cache.get(&id).copied()) {
+        return type_;
+    }
+
+    let type_ = *TYPES
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .entry(id)
+        .or_insert_with(|| {
+            // `std::any::type_name()` is neither stable nor unique, e.g. for types declared in
+            // different blocks of the same function, so name the type after its `TypeId`.
+            let mut hasher = DefaultHasher::new();
+            id.hash(&mut hasher);
+            let hash = hasher.finish();
+
+            unsafe { register_boxed_type_with_name::<T>(&format!("GRsBoxed-{hash:016x}"), true) }
+        });
+    CACHE.with_borrow_mut(|cache| cache.insert(id, type_));
+
+    type_
+}
+
+unsafe fn register_boxed_type_with_name<T: Clone + 'static>(
+    name: &str,
+    allow_name_conflict: bool,
+) -> crate::Type {
+    unsafe extern "C" fn boxed_copy<T: Clone>(v: ffi::gpointer) -> ffi::gpointer {
         unsafe {
             let v = &*(v as *mut T);
             let copy = Box::new(v.clone());
@@ -55,7 +114,7 @@ pub fn register_boxed_type<T: BoxedType>() -> crate::Type {
             Box::into_raw(copy) as ffi::gpointer
         }
     }
-    unsafe extern "C" fn boxed_free<T: BoxedType>(v: ffi::gpointer) {
+    unsafe extern "C" fn boxed_free<T>(v: ffi::gpointer) {
         unsafe {
             let v = v as *mut T;
             let _ = Box::from_raw(v);
@@ -64,13 +123,13 @@ pub fn register_boxed_type<T: BoxedType>() -> crate::Type {
     unsafe {
         use std::ffi::CString;
 
-        let type_name = if T::ALLOW_NAME_CONFLICT {
+        let type_name = if allow_name_conflict {
             let mut i = 0;
             loop {
                 let type_name = CString::new(if i == 0 {
-                    T::NAME.to_string()
+                    name.to_string()
                 } else {
-                    format!("{}-{}", T::NAME, i)
+                    format!("{}-{}", name, i)
                 })
                 .unwrap();
                 if gobject_ffi::g_type_from_name(type_name.as_ptr()) == gobject_ffi::G_TYPE_INVALID
@@ -80,7 +139,7 @@ pub fn register_boxed_type<T: BoxedType>() -> crate::Type {
                 i += 1;
             }
         } else {
-            let type_name = CString::new(T::NAME).unwrap();
+            let type_name = CString::new(name).unwrap();
             assert_eq!(
                 gobject_ffi::g_type_from_name(type_name.as_ptr()),
                 gobject_ffi::G_TYPE_INVALID,
@@ -144,4 +203,61 @@ mod test {
 
         assert_eq!(new_b.0, "abc".to_string());
     }
+
+    #[derive(Clone, Debug, PartialEq, Eq, glib::Boxed)]
+    enum MyUnnamedBoxed {
+        Text(String),
+        Pair(i32, i32),
+    }
+
+    #[test]
+    fn test_register_on_demand_same_type_name() {
+        let (a, a_name) = {
+            #[derive(Clone)]
+            struct Local;
+            (
+                glib::subclass::register_boxed_type_on_demand::<Local>(),
+                std::any::type_name::<Local>(),
+            )
+        };
+        let (b, b_name) = {
+            #[derive(Clone)]
+            struct Local;
+            (
+                glib::subclass::register_boxed_type_on_demand::<Local>(),
+                std::any::type_name::<Local>(),
+            )
+        };
+
+        assert_eq!(a_name, b_name);
+        assert_ne!(a, b);
+        assert_ne!(a.name(), b.name());
+    }
+
+    #[test]
+    fn test_register_on_demand() {
+        let t = MyUnnamedBoxed::static_type();
+        assert!(t.is_valid());
+        assert!(t.is_a(glib::Type::BOXED));
+        assert_eq!(t, MyUnnamedBoxed::static_type());
+        assert_eq!(
+            t,
+            glib::subclass::register_boxed_type_on_demand::<MyUnnamedBoxed>()
+        );
+        assert_ne!(
+            t,
+            glib::subclass::register_boxed_type_on_demand::<(i32, String)>()
+        );
+
+        let b = MyUnnamedBoxed::Pair(1, 2);
+        let v = b.to_value();
+        assert_eq!(v.get::<MyUnnamedBoxed>().unwrap(), b);
+        assert_eq!(v.get::<&MyUnnamedBoxed>().unwrap(), &b);
+        assert!(
+            MyUnnamedBoxed::Text(String::from("abc"))
+                .to_value()
+                .get::<MyUnnamedBoxed>()
+                .is_ok()
+        );
+    }
 }
@@ -0,0 +1,836 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Module for registering fundamental types for Rust types.
+//!
+//! Fundamental types are root types of the type system, like `GObject`, `GParamSpec` or
+//! `GVariant`. The types registered here are classed and instantiatable, similar to
+//! `GstMiniObject` or `GtkExpression`: every instance is a reference counted `GTypeInstance`
+//! that stores the Rust data of the type, without any of the `GObject` overhead. They come with
+//! their own `GTypeValueTable` and their own `GParamSpec` type and, if they are derivable, other
+//! types can be derived from them with [`register_derived_fundamental_type`].
+
+use std::{
+    ffi::CString,
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+    ptr,
+    sync::atomic::{self, AtomicUsize},
+};
+
+use libc::{c_char, c_uint};
+
+use crate::{
+    ParamFlags, ParamSpec, Quark, Type, Value, ffi, gobject_ffi, prelude::*, translate::*,
+};
+
+// rustdoc-stripper-ignore-next
+/// Trait for defining fundamental types.
+///
+/// Links together the type name with the type itself.
+///
+/// See [`register_fundamental_type`] for registering an implementation of this trait
+/// with the type system.
+///
+/// [`register_fundamental_type`]: fn.register_fundamental_type.html
+pub trait FundamentalType: StaticType + Clone + Sized + 'static {
+    // rustdoc-stripper-ignore-next
+    /// Fundamental type name.
+    ///
+    /// This must be unique in the whole process.
+    const NAME: &'static str;
+
+    // rustdoc-stripper-ignore-next
+    /// Allow name conflicts for this fundamental type.
+    ///
+    /// By default, trying to register a type with a name that was registered before will panic. If
+    /// this is set to `true` then a new name will be selected by appending a counter.
+    ///
+    /// This is useful for defining new types in Rust library crates that might be linked multiple
+    /// times in the same process.
+    ///
+    /// A consequence of setting this to `true` is that it's not guaranteed that
+    /// `glib::Type::from_name(Self::NAME).unwrap() == Self::static_type()`.
+    ///
+    /// Optional.
+    const ALLOW_NAME_CONFLICT: bool = false;
+
+    // rustdoc-stripper-ignore-next
+    /// Whether other types can be derived from this fundamental type.
+    ///
+    /// Derived types share the instance data and the value table of the fundamental type.
+    ///
+    /// Optional.
+    const DERIVABLE: bool = false;
+
+    // rustdoc-stripper-ignore-next
+    /// The Rust data stored in every instance.
+    type Data: Send + Sync + 'static;
+
+    // rustdoc-stripper-ignore-next
+    /// Constructs a FundamentalType from a reference to an instance.
+    fn from_instance(this: FundamentalRef<Self::Data>) -> Self;
+
+    // rustdoc-stripper-ignore-next
+    /// Converts the FundamentalType into a reference to its instance.
+    fn into_instance(self) -> FundamentalRef<Self::Data>;
+
+    // rustdoc-stripper-ignore-next
+    /// Called when the class of this type or of a type derived from it is initialized.
+    ///
+    /// Optional.
+    fn class_init(_class: &mut FundamentalClass) {}
+
+    // rustdoc-stripper-ignore-next
+    /// Called for every new instance of this type or of a type derived from it, once its data
+    /// is set.
+    ///
+    /// Optional.
+    fn instance_init(_instance: &FundamentalRef<Self::Data>) {}
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new instance of this type holding `data`.
+    #[doc(alias = "g_type_create_instance")]
+    fn with_data(data: Self::Data) -> Self {
+        Self::with_type(Self::static_type(), data)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a new instance of `type_` holding `data`.
+    ///
+    /// `type_` must be this type or a type derived from it with
+    /// [`register_derived_fundamental_type`].
+    #[doc(alias = "g_type_create_instance")]
+    fn with_type(type_: Type, data: Self::Data) -> Self {
+        assert!(
+            type_.is_a(Self::static_type()),
+            "Type {type_} is not derived from {}",
+            Self::static_type()
+        );
+
+        let instance = unsafe { FundamentalRef::create(type_, data) };
+        Self::instance_init(&instance);
+        Self::from_instance(instance)
+    }
+}
+
+// The instance struct of all fundamental types registered from Rust. The header does not depend
+// on the data, so it can be initialized without knowing the Rust type.
+#[repr(C)]
+struct InstanceHeader {
+    instance: gobject_ffi::GTypeInstance,
+    ref_count: AtomicUsize,
+}
+
+#[repr(C)]
+struct Instance<D> {
+    header: InstanceHeader,
+    data: D,
+}
+
+// rustdoc-stripper-ignore-next
+/// A strong reference to an instance of a fundamental type registered from Rust.
+///
+/// Like an `Arc<D>` whose allocation is a `GTypeInstance`, which allows storing it in `Value`s
+/// and passing it to C code.
+pub struct FundamentalRef<D: 'static>(ptr::NonNull<Instance<D>>);
+
+unsafe impl<D: Send + Sync> Send for FundamentalRef<D> {}
+unsafe impl<D: Send + Sync> Sync for FundamentalRef<D> {}
+
+impl<D> FundamentalRef<D> {
+    // Creates a new instance of `type_`, which must have been registered for `D`.
+    unsafe fn create(type_: Type, data: D) -> Self {
+        unsafe {
+            let ptr = gobject_ffi::g_type_create_instance(type_.into_glib()) as *mut Instance<D>;
+            ptr::write(ptr::addr_of_mut!((*ptr).data), data);
+            Self(ptr::NonNull::new_unchecked(ptr))
+        }
+    }
+
+    unsafe fn from_raw(ptr: ffi::gpointer) -> Self {
+        debug_assert!(!ptr.is_null());
+        unsafe { Self(ptr::NonNull::new_unchecked(ptr as *mut Instance<D>)) }
+    }
+
+    unsafe fn from_raw_none(ptr: ffi::gpointer) -> Self {
+        unsafe {
+            let this = mem::ManuallyDrop::new(Self::from_raw(ptr));
+            (*this).clone()
+        }
+    }
+
+    fn into_raw(this: Self) -> ffi::gpointer {
+        mem::ManuallyDrop::new(this).0.as_ptr() as ffi::gpointer
+    }
+
+    fn header(this: &Self) -> &InstanceHeader {
+        unsafe { &(*this.0.as_ptr()).header }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the `GTypeInstance` pointer of the instance.
+    #[inline]
+    pub fn as_ptr(this: &Self) -> *mut gobject_ffi::GTypeInstance {
+        this.0.as_ptr() as *mut _
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the type of the instance, which may be derived from the fundamental type.
+    pub fn type_(this: &Self) -> Type {
+        unsafe { from_glib((*Self::header(this).instance.g_class).g_type) }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of strong references to the instance.
+    pub fn ref_count(this: &Self) -> usize {
+        Self::header(this).ref_count.load(atomic::Ordering::Relaxed)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if both references point to the same instance.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.0 == other.0
+    }
+}
+
+impl<D> Clone for FundamentalRef<D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::header(self)
+            .ref_count
+            .fetch_add(1, atomic::Ordering::Relaxed);
+        Self(self.0)
+    }
+}
+
+impl<D> Drop for FundamentalRef<D> {
+    fn drop(&mut self) {
+        if Self::header(self)
+            .ref_count
+            .fetch_sub(1, atomic::Ordering::Release)
+            != 1
+        {
+            return;
+        }
+
+        atomic::fence(atomic::Ordering::Acquire);
+        unsafe {
+            let ptr = self.0.as_ptr();
+            ptr::drop_in_place(ptr::addr_of_mut!((*ptr).data));
+            gobject_ffi::g_type_free_instance(ptr as *mut gobject_ffi::GTypeInstance);
+        }
+    }
+}
+
+impl<D> Deref for FundamentalRef<D> {
+    type Target = D;
+
+    #[inline]
+    fn deref(&self) -> &D {
+        unsafe { &(*self.0.as_ptr()).data }
+    }
+}
+
+impl<D: fmt::Debug> fmt::Debug for FundamentalRef<D> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FundamentalRef")
+            .field("type", &Self::type_(self))
+            .field("data", &**self)
+            .finish()
+    }
+}
+
+impl<D: PartialEq> PartialEq for FundamentalRef<D> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        **self == **other
+    }
+}
+
+impl<D: Eq> Eq for FundamentalRef<D> {}
+
+// rustdoc-stripper-ignore-next
+/// The class struct of fundamental types registered from Rust and of types derived from them.
+#[repr(transparent)]
+pub struct FundamentalClass(gobject_ffi::GTypeClass);
+
+impl FundamentalClass {
+    // rustdoc-stripper-ignore-next
+    /// Returns the type of the class.
+    #[inline]
+    pub fn type_(&self) -> Type {
+        unsafe { from_glib(self.0.g_type) }
+    }
+}
+
+impl fmt::Debug for FundamentalClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FundamentalClass")
+            .field("type", &self.type_())
+            .finish()
+    }
+}
+
+fn param_spec_type_quark() -> Quark {
+    Quark::from_static_str(crate::gstr!("glib-rs-fundamental-param-spec-type"))
+}
+
+fn type_info<T: FundamentalType>() -> crate::TypeInfo {
+    unsafe extern "C" fn class_init<T: FundamentalType>(
+        klass: ffi::gpointer,
+        _class_data: ffi::gpointer,
+    ) {
+        unsafe {
+            T::class_init(&mut *(klass as *mut FundamentalClass));
+        }
+    }
+    unsafe extern "C" fn instance_init(
+        instance: *mut gobject_ffi::GTypeInstance,
+        _klass: ffi::gpointer,
+    ) {
+        unsafe {
+            let header = instance as *mut InstanceHeader;
+            ptr::write(ptr::addr_of_mut!((*header).ref_count), AtomicUsize::new(1));
+        }
+    }
+
+    let instance_size = mem::size_of::<Instance<T::Data>>();
+    assert!(
+        instance_size <= u16::MAX as usize,
+        "Instance data of {} is too big",
+        T::NAME
+    );
+    // GLib only guarantees the alignment of `malloc()` for instances.
+    assert!(
+        mem::align_of::<Instance<T::Data>>() <= 2 * mem::size_of::<usize>(),
+        "Instance data of {} is overaligned",
+        T::NAME
+    );
+
+    let mut type_info = crate::TypeInfo::default();
+    type_info.0.class_size = mem::size_of::<FundamentalClass>() as u16;
+    type_info.0.class_init = Some(class_init::<T>);
+    type_info.0.instance_size = instance_size as u16;
+    type_info.0.instance_init = Some(instance_init);
+    type_info
+}
+
+// rustdoc-stripper-ignore-next
+/// Register a fundamental `glib::Type` ID for `T`.
+///
+/// The type is classed and instantiatable, and deep derivable if [`FundamentalType::DERIVABLE`]
+/// is set. Together with the fundamental type, a `GParamSpec` type for properties holding values
+/// of `T` is registered. See [`ParamSpecFundamentalBuilder`].
+///
+/// This must be called only once and will panic on a second call.
+///
+/// See [`Fundamental!`] for defining a function that ensures that
+/// this is only called once and returns the type id.
+///
+/// [`Fundamental!`]: ../../derive.Fundamental.html
+pub fn register_fundamental_type<T: FundamentalType>() -> Type {
+    unsafe extern "C" fn value_init(value: *mut gobject_ffi::GValue) {
+        unsafe {
+            (*value).data[0].v_pointer = ptr::null_mut();
+        }
+    }
+    unsafe extern "C" fn value_free<T: FundamentalType>(value: *mut gobject_ffi::GValue) {
+        unsafe {
+            let ptr = (*value).data[0].v_pointer;
+            if !ptr.is_null() {
+                let _ = FundamentalRef::<T::Data>::from_raw(ptr);
+            }
+        }
+    }
+    unsafe extern "C" fn value_copy<T: FundamentalType>(
+        src_value: *const gobject_ffi::GValue,
+        dest_value: *mut gobject_ffi::GValue,
+    ) {
+        unsafe {
+            let ptr = (*src_value).data[0].v_pointer;
+            (*dest_value).data[0].v_pointer = if ptr.is_null() {
+                ptr
+            } else {
+                FundamentalRef::into_raw(FundamentalRef::<T::Data>::from_raw_none(ptr))
+            };
+        }
+    }
+    unsafe extern "C" fn value_peek_pointer(value: *const gobject_ffi::GValue) -> ffi::gpointer {
+        unsafe { (*value).data[0].v_pointer }
+    }
+    unsafe extern "C" fn value_collect<T: FundamentalType>(
+        value: *mut gobject_ffi::GValue,
+        _n_collect_values: c_uint,
+        collect_values: *mut gobject_ffi::GTypeCValue,
+        _collect_flags: c_uint,
+    ) -> *mut c_char {
+        unsafe {
+            let ptr = (*collect_values).v_pointer;
+            (*value).data[0].v_pointer = if ptr.is_null() {
+                ptr
+            } else if gobject_ffi::g_type_check_instance_is_a(ptr as *mut _, (*value).g_type)
+                == ffi::GFALSE
+            {
+                return ffi::g_strdup(c"invalid instance type passed".as_ptr());
+            } else {
+                FundamentalRef::into_raw(FundamentalRef::<T::Data>::from_raw_none(ptr))
+            };
+
+            ptr::null_mut()
+        }
+    }
+    unsafe extern "C" fn value_lcopy<T: FundamentalType>(
+        value: *const gobject_ffi::GValue,
+        _n_collect_values: c_uint,
+        collect_values: *mut gobject_ffi::GTypeCValue,
+        collect_flags: c_uint,
+    ) -> *mut c_char {
+        unsafe {
+            let dest = (*collect_values).v_pointer as *mut ffi::gpointer;
+            if dest.is_null() {
+                return ffi::g_strdup(c"value location passed as NULL".as_ptr());
+            }
+
+            let ptr = (*value).data[0].v_pointer;
+            *dest = if ptr.is_null()
+                || collect_flags & gobject_ffi::G_VALUE_NOCOPY_CONTENTS as c_uint != 0
+            {
+                ptr
+            } else {
+                FundamentalRef::into_raw(FundamentalRef::<T::Data>::from_raw_none(ptr))
+            };
+
+            ptr::null_mut()
+        }
+    }
+
+    unsafe {
+        let type_name = if T::ALLOW_NAME_CONFLICT {
+            let mut i = 0;
+            loop {
+                let type_name = CString::new(if i == 0 {
+                    T::NAME.to_string()
+                } else {
+                    format!("{}-{}", T::NAME, i)
+                })
+                .unwrap();
+                if gobject_ffi::g_type_from_name(type_name.as_ptr()) == gobject_ffi::G_TYPE_INVALID
+                {
+                    break type_name;
+                }
+                i += 1;
+            }
+        } else {
+            let type_name = CString::new(T::NAME).unwrap();
+            assert_eq!(
+                gobject_ffi::g_type_from_name(type_name.as_ptr()),
+                gobject_ffi::G_TYPE_INVALID,
+                "Type {} has already been registered",
+                type_name.to_str().unwrap()
+            );
+
+            type_name
+        };
+
+        // The value table is copied by GLib, only the functions have to stay alive.
+        let value_table = gobject_ffi::GTypeValueTable {
+            value_init: Some(value_init),
+            value_free: Some(value_free::<T>),
+            value_copy: Some(value_copy::<T>),
+            value_peek_pointer: Some(value_peek_pointer),
+            collect_format: c"p".as_ptr(),
+            collect_value: Some(value_collect::<T>),
+            lcopy_format: c"p".as_ptr(),
+            lcopy_value: Some(value_lcopy::<T>),
+        };
+        let mut type_info = type_info::<T>();
+        type_info.0.value_table = &value_table;
+        let mut type_flags =
+            gobject_ffi::G_TYPE_FLAG_CLASSED | gobject_ffi::G_TYPE_FLAG_INSTANTIATABLE;
+        if T::DERIVABLE {
+            type_flags |=
+                gobject_ffi::G_TYPE_FLAG_DERIVABLE | gobject_ffi::G_TYPE_FLAG_DEEP_DERIVABLE;
+        }
+        let fundamental_info = gobject_ffi::GTypeFundamentalInfo { type_flags };
+
+        let type_ = Type::from_glib(gobject_ffi::g_type_register_fundamental(
+            gobject_ffi::g_type_fundamental_next(),
+            type_name.as_ptr(),
+            type_info.as_ptr(),
+            &fundamental_info,
+            0,
+        ));
+        assert!(type_.is_valid());
+
+        let param_spec_type_name =
+            CString::new(format!("{}ParamSpec", type_name.to_str().unwrap())).unwrap();
+        let param_spec_type_info = gobject_ffi::GParamSpecTypeInfo {
+            instance_size: std::mem::size_of::<gobject_ffi::GParamSpec>() as u16,
+            n_preallocs: 0,
+            instance_init: None,
+            value_type: type_.into_glib(),
+            finalize: None,
+            value_set_default: None,
+            value_validate: None,
+            values_cmp: None,
+        };
+        let param_spec_type = Type::from_glib(gobject_ffi::g_param_type_register_static(
+            param_spec_type_name.as_ptr(),
+            &param_spec_type_info,
+        ));
+        assert!(param_spec_type.is_valid());
+        gobject_ffi::g_type_set_qdata(
+            type_.into_glib(),
+            param_spec_type_quark().into_glib(),
+            param_spec_type.into_glib() as ffi::gpointer,
+        );
+
+        type_
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Register a `glib::Type` ID named `name` that is derived from `parent`.
+///
+/// `parent` must be the fundamental type of `T`, which must be [`FundamentalType::DERIVABLE`], or
+/// a type derived from it. Instances of the new type are created with
+/// [`FundamentalType::with_type`] and hold the same data as instances of `T`.
+///
+/// This must be called only once per name and will panic on a second call.
+///
+/// ```
+/// use std::sync::OnceLock;
+///
+/// use glib::{prelude::*, subclass::fundamental::FundamentalRef, subclass::prelude::*};
+///
+/// #[derive(Clone, Debug, glib::Fundamental)]
+/// #[fundamental_type(name = "MyExpression", derivable)]
+/// struct MyExpression(FundamentalRef<i32>);
+///
+/// fn constant_expression_type() -> glib::Type {
+///     static TYPE: OnceLock<glib::Type> = OnceLock::new();
+///     *TYPE.get_or_init(|| {
+///         glib::subclass::register_derived_fundamental_type::<MyExpression>(
+///             MyExpression::static_type(),
+///             "MyConstantExpression",
+///         )
+///     })
+/// }
+///
+/// let expression = MyExpression::with_type(constant_expression_type(), 42);
+/// assert_eq!(FundamentalRef::type_(&expression.0), constant_expression_type());
+/// assert_eq!(*expression.0, 42);
+/// ```
+pub fn register_derived_fundamental_type<T: FundamentalType>(parent: Type, name: &str) -> Type {
+    assert!(T::DERIVABLE, "Type {} is not derivable", T::static_type());
+    assert!(
+        parent.is_a(T::static_type()),
+        "Type {parent} is not derived from {}",
+        T::static_type()
+    );
+
+    unsafe {
+        let type_name = CString::new(name).unwrap();
+        assert_eq!(
+            gobject_ffi::g_type_from_name(type_name.as_ptr()),
+            gobject_ffi::G_TYPE_INVALID,
+            "Type {name} has already been registered",
+        );
+
+        let type_info = type_info::<T>();
+        let type_ = Type::from_glib(gobject_ffi::g_type_register_static(
+            parent.into_glib(),
+            type_name.as_ptr(),
+            type_info.as_ptr(),
+            0,
+        ));
+        assert!(type_.is_valid());
+
+        type_
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Returns the `GParamSpec` type registered together with the fundamental type `type_`.
+///
+/// Returns `None` if `type_` was not registered with [`register_fundamental_type`] and is not
+/// derived from such a type.
+pub fn param_spec_type(type_: Type) -> Option<Type> {
+    let mut type_ = type_;
+    loop {
+        let param_spec_type = unsafe {
+            gobject_ffi::g_type_get_qdata(type_.into_glib(), param_spec_type_quark().into_glib())
+        };
+        if !param_spec_type.is_null() {
+            return Some(unsafe { Type::from_glib(param_spec_type as ffi::GType) });
+        }
+        type_ = type_.parent()?;
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Stores `v` in `value`, replacing the previous content.
+///
+/// # Safety
+///
+/// `value` must hold a value of `T`'s fundamental type or of a type derived from it.
+pub unsafe fn value_take<T: FundamentalType>(value: &mut Value, v: Option<T>) {
+    unsafe {
+        let value = value.to_glib_none_mut().0;
+        let old = (*value).data[0].v_pointer;
+        (*value).data[0].v_pointer = match v {
+            Some(v) => FundamentalRef::into_raw(v.into_instance()),
+            None => ptr::null_mut(),
+        };
+        if !old.is_null() {
+            let _ = FundamentalRef::<T::Data>::from_raw(old);
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Returns a new reference to the content of `value`.
+///
+/// # Safety
+///
+/// `value` must hold a value of `T`'s fundamental type or of a type derived from it.
+pub unsafe fn value_dup<T: FundamentalType>(value: &Value) -> Option<T> {
+    unsafe {
+        let ptr = (*value.to_glib_none().0).data[0].v_pointer;
+        if ptr.is_null() {
+            None
+        } else {
+            Some(T::from_instance(FundamentalRef::from_raw_none(ptr)))
+        }
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Builder for a [`ParamSpec`] holding values of the fundamental type `T`.
+///
+/// The nick, blurb and flags are set through
+/// [`ParamSpecBuilderExt`](crate::prelude::ParamSpecBuilderExt).
+#[must_use]
+pub struct ParamSpecFundamentalBuilder<'a, T: FundamentalType> {
+    name: &'a str,
+    nick: Option<&'a str>,
+    blurb: Option<&'a str>,
+    flags: ParamFlags,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, T: FundamentalType> ParamSpecFundamentalBuilder<'a, T> {
+    // rustdoc-stripper-ignore-next
+    /// Creates a builder for a `GParamSpec` holding values of `T`.
+    pub fn new(name: &'a str) -> Self {
+        crate::param_spec::assert_param_name(name);
+        Self {
+            name,
+            nick: None,
+            blurb: None,
+            flags: ParamFlags::default(),
+            phantom: Default::default(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Builds the `GParamSpec`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` was not registered as fundamental type.
+    #[must_use]
+    pub fn build(self) -> ParamSpec {
+        let param_spec_type =
+            param_spec_type(T::static_type()).expect("Type was not registered as fundamental type");
+        unsafe {
+            from_glib_none(gobject_ffi::g_param_spec_internal(
+                param_spec_type.into_glib(),
+                self.name.to_glib_none().0,
+                self.nick.to_glib_none().0,
+                self.blurb.to_glib_none().0,
+                self.flags.into_glib(),
+            ))
+        }
+    }
+}
+
+impl<'a, T: FundamentalType> crate::prelude::ParamSpecBuilderExt<'a>
+    for ParamSpecFundamentalBuilder<'a, T>
+{
+    fn set_nick(&mut self, nick: Option<&'a str>) {
+        self.nick = nick;
+    }
+    fn set_blurb(&mut self, blurb: Option<&'a str>) {
+        self.blurb = blurb;
+    }
+    fn set_flags(&mut self, flags: ParamFlags) {
+        self.flags = flags;
+    }
+    fn current_flags(&self) -> ParamFlags {
+        self.flags
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+    // We rename the current crate as glib, since the macros in glib-macros
+    // generate the glib namespace through the crate_ident_new utility,
+    // and that returns `glib` (and not `crate`) when called inside the glib crate
+    use crate as glib;
+
+    #[derive(Debug, PartialEq, Eq)]
+    struct MyFundamentalInner {
+        foo: String,
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, glib::Fundamental)]
+    #[fundamental_type(name = "MyFundamental", derivable)]
+    struct MyFundamental(FundamentalRef<MyFundamentalInner>);
+
+    fn my_derived_type() -> Type {
+        static TYPE: std::sync::OnceLock<Type> = std::sync::OnceLock::new();
+        *TYPE.get_or_init(|| {
+            register_derived_fundamental_type::<MyFundamental>(
+                MyFundamental::static_type(),
+                "MyDerivedFundamental",
+            )
+        })
+    }
+
+    fn new(foo: &str) -> MyFundamental {
+        MyFundamental::with_data(MyFundamentalInner {
+            foo: String::from(foo),
+        })
+    }
+
+    #[test]
+    fn test_register() {
+        let type_ = MyFundamental::static_type();
+        assert!(type_.is_valid());
+        assert_eq!(type_.parent(), None);
+        assert_eq!(type_.name(), "MyFundamental");
+        assert!(param_spec_type(type_).unwrap().is_a(Type::PARAM_SPEC));
+        assert!(unsafe {
+            gobject_ffi::g_type_test_flags(
+                type_.into_glib(),
+                gobject_ffi::G_TYPE_FLAG_CLASSED
+                    | gobject_ffi::G_TYPE_FLAG_INSTANTIATABLE
+                    | gobject_ffi::G_TYPE_FLAG_DEEP_DERIVABLE,
+            ) != ffi::GFALSE
+        });
+
+        let derived = my_derived_type();
+        assert_eq!(derived.parent(), Some(type_));
+        assert_eq!(param_spec_type(derived), param_spec_type(type_));
+    }
+
+    #[test]
+    fn test_instance() {
+        struct DropCheck(Arc<AtomicBool>);
+
+        impl Drop for DropCheck {
+            fn drop(&mut self) {
+                self.0.store(true, Ordering::SeqCst);
+            }
+        }
+
+        #[derive(Clone, glib::Fundamental)]
+        #[fundamental_type(name = "MyDropCheckFundamental")]
+        struct MyDropCheck(FundamentalRef<DropCheck>);
+
+        let dropped = Arc::new(AtomicBool::new(false));
+        let f = MyDropCheck::with_data(DropCheck(dropped.clone()));
+        assert_eq!(FundamentalRef::type_(&f.0), MyDropCheck::static_type());
+        assert!(unsafe {
+            gobject_ffi::g_type_check_instance_is_a(
+                FundamentalRef::as_ptr(&f.0),
+                MyDropCheck::static_type().into_glib(),
+            ) != ffi::GFALSE
+        });
+        assert_eq!(FundamentalRef::ref_count(&f.0), 1);
+
+        let f2 = f.clone();
+        assert_eq!(FundamentalRef::ref_count(&f.0), 2);
+        drop(f);
+        assert!(!dropped.load(Ordering::SeqCst));
+        drop(f2);
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_derived() {
+        let f = MyFundamental::with_type(
+            my_derived_type(),
+            MyFundamentalInner {
+                foo: String::from("derived"),
+            },
+        );
+        assert_eq!(FundamentalRef::type_(&f.0), my_derived_type());
+        assert_eq!(f.0.foo, "derived");
+
+        let v = f.to_value();
+        assert_eq!(v.type_(), MyFundamental::static_type());
+        let f2 = v.get::<MyFundamental>().unwrap();
+        assert!(FundamentalRef::ptr_eq(&f.0, &f2.0));
+
+        let mut v = Value::from_type(my_derived_type());
+        unsafe { value_take(&mut v, Some(f)) };
+        assert_eq!(v.get::<MyFundamental>().unwrap(), f2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_with_unrelated_type() {
+        MyFundamental::with_type(
+            Type::OBJECT,
+            MyFundamentalInner {
+                foo: String::from("abc"),
+            },
+        );
+    }
+
+    #[test]
+    fn test_value() {
+        let f = new("abc");
+        let v = f.to_value();
+        assert_eq!(v.type_(), MyFundamental::static_type());
+        assert_eq!(FundamentalRef::ref_count(&f.0), 2);
+
+        let f2 = v.get::<MyFundamental>().unwrap();
+        assert!(FundamentalRef::ptr_eq(&f.0, &f2.0));
+        assert_eq!(FundamentalRef::ref_count(&f.0), 3);
+
+        let v2 = v.clone();
+        assert_eq!(FundamentalRef::ref_count(&f.0), 4);
+        drop(v);
+        drop(v2);
+        assert_eq!(FundamentalRef::ref_count(&f.0), 2);
+
+        let v = Value::from_type(MyFundamental::static_type());
+        assert!(v.get::<MyFundamental>().is_err());
+        assert_eq!(v.get::<Option<MyFundamental>>().unwrap(), None);
+    }
+
+    #[test]
+    fn test_param_spec() {
+        use crate::prelude::ParamSpecBuilderExt;
+
+        let pspec = MyFundamental::param_spec_builder()("my-fundamental")
+            .read_only()
+            .build();
+        assert_eq!(pspec.value_type(), MyFundamental::static_type());
+        assert_eq!(pspec.name(), "my-fundamental");
+        assert_eq!(pspec.flags(), ParamFlags::READABLE);
+    }
+}
@@ -487,6 +487,8 @@ pub mod boxed;
 
 pub mod shared;
 
+pub mod fundamental;
+
 pub mod signal;
 
 mod object_impl_ref;
@@ -501,6 +503,7 @@ pub mod prelude {
     //! Prelude that re-exports all important traits from this crate.
     pub use super::{
         boxed::BoxedType,
+        fundamental::FundamentalType,
         interface::{ObjectInterface, ObjectInterfaceExt, ObjectInterfaceType},
         object::{DerivedObjectProperties, ObjectClassSubclassExt, ObjectImpl, ObjectImplExt},
         shared::{RefCounted, SharedType},
@@ -515,7 +518,8 @@ pub mod prelude {
 }
 
 pub use self::{
-    boxed::register_boxed_type,
+    boxed::{register_boxed_type, register_boxed_type_on_demand},
+    fundamental::{register_derived_fundamental_type, register_fundamental_type},
     interface::{register_dynamic_interface, register_interface},
     signal::{
         Signal, SignalClassHandlerToken, SignalId, SignalInvocationHint, SignalQuery, SignalType,
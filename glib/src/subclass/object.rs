@@ -134,6 +134,15 @@ unsafe extern "C" fn constructed<T: ObjectImpl>(obj: *mut gobject_ffi::GObject)
         let instance = &*(obj as *mut T::Instance);
         let imp = instance.imp();
 
+        // Parent types' implementations are chained up to from here, only report the actual type.
+        #[cfg(feature = "leak-tracker")]
+        {
+            let obj = from_glib_borrow::<_, Object>(obj);
+            if obj.type_() == T::type_() {
+                crate::test::object_constructed(&obj);
+            }
+        }
+
         imp.constructed();
     }
 }
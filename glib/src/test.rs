@@ -0,0 +1,370 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Helpers for writing tests.
+//!
+//! [`LeakTracker`] keeps track of objects created during a test and reports the ones that are
+//! still alive at the end of it, e.g. because of a reference cycle through a closure.
+//!
+//! Tracking instances of Rust subclasses automatically when they are constructed requires the
+//! `leak-tracker` feature, which adds a check to the construction of every such instance.
+//!
+//! ```
+//! use glib::prelude::*;
+//!
+//! let tracker = glib::test::LeakTracker::new();
+//!
+//! let obj = glib::Object::new::<glib::Object>();
+//! tracker.track(&obj);
+//! drop(obj);
+//!
+//! // Panics if `obj` is still alive.
+//! tracker.assert_no_leaks();
+//! ```
+
+#[cfg(feature = "leak-tracker")]
+use std::{backtrace::Backtrace, rc::Weak};
+use std::{cell::RefCell, fmt, panic::Location, rc::Rc};
+
+use crate::{Object, Type, WeakRef, prelude::*};
+
+#[cfg(feature = "leak-tracker")]
+thread_local! {
+    static TRACKERS: RefCell<Vec<Weak<TrackerState>>> = const { RefCell::new(Vec::new()) };
+}
+
+enum Filter {
+    None,
+    #[cfg(feature = "leak-tracker")]
+    Types(Vec<Type>),
+    #[cfg(feature = "leak-tracker")]
+    AllSubclasses,
+}
+
+enum Origin {
+    Caller(&'static Location<'static>),
+    #[cfg(feature = "leak-tracker")]
+    Backtrace(Backtrace),
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Caller(location) => write!(f, "{location}"),
+            #[cfg(feature = "leak-tracker")]
+            Self::Backtrace(backtrace) => write!(f, "\n{backtrace}"),
+        }
+    }
+}
+
+struct Tracked {
+    object: WeakRef<Object>,
+    origin: Rc<Origin>,
+}
+
+struct TrackerState {
+    // Only read when instances of Rust subclasses are constructed.
+    #[cfg_attr(not(feature = "leak-tracker"), allow(dead_code))]
+    filter: Filter,
+    objects: RefCell<Vec<Tracked>>,
+}
+
+// rustdoc-stripper-ignore-next
+/// Tracks the lifetime of objects and reports the ones that are still alive.
+///
+/// Objects can be tracked explicitly with [`track`](Self::track). Additionally, instances of
+/// Rust subclasses can be tracked automatically when they are constructed, either for chosen
+/// types with [`for_types`](Self::for_types) or for all of them with
+/// [`for_all_subclasses`](Self::for_all_subclasses). For those, the backtrace of their
+/// construction is recorded. Automatic tracking requires the `leak-tracker` feature.
+///
+/// Only objects created on the thread the tracker was created on are tracked automatically.
+///
+/// When the tracker is dropped, it panics if any of the tracked objects is still alive, unless
+/// the thread is already panicking.
+#[must_use = "the tracker stops tracking objects once it is dropped"]
+pub struct LeakTracker {
+    state: Rc<TrackerState>,
+}
+
+impl LeakTracker {
+    // rustdoc-stripper-ignore-next
+    /// Creates a tracker that only tracks objects passed to [`track`](Self::track).
+    pub fn new() -> Self {
+        Self::with_filter(Filter::None)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a tracker that additionally tracks all instances of Rust subclasses of the given
+    /// types that are constructed while the tracker is alive.
+    #[cfg(feature = "leak-tracker")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "leak-tracker")))]
+    pub fn for_types(types: &[Type]) -> Self {
+        Self::with_filter(Filter::Types(types.to_vec()))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a tracker that additionally tracks all instances of Rust subclasses that are
+    /// constructed while the tracker is alive.
+    #[cfg(feature = "leak-tracker")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "leak-tracker")))]
+    pub fn for_all_subclasses() -> Self {
+        Self::with_filter(Filter::AllSubclasses)
+    }
+
+    fn with_filter(filter: Filter) -> Self {
+        let state = Rc::new(TrackerState {
+            filter,
+            objects: RefCell::new(Vec::new()),
+        });
+        #[cfg(feature = "leak-tracker")]
+        if !matches!(state.filter, Filter::None) {
+            TRACKERS.with(|trackers| trackers.borrow_mut().push(Rc::downgrade(&state)));
+        }
+
+        Self { state }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Tracks `obj`, recording the caller as its origin.
+    #[track_caller]
+    pub fn track(&self, obj: &impl IsA<Object>) {
+        self.state.objects.borrow_mut().push(Tracked {
+            object: obj.upcast_ref::<Object>().downgrade(),
+            origin: Rc::new(Origin::Caller(Location::caller())),
+        });
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the tracked objects that are still alive.
+    pub fn leaked(&self) -> Vec<LeakedObject> {
+        // Collect strong references first so that no object is disposed while the tracked
+        // objects are borrowed.
+        let alive = self
+            .state
+            .objects
+            .borrow()
+            .iter()
+            .filter_map(|tracked| Some((tracked.object.upgrade()?, tracked.origin.clone())))
+            .collect::<Vec<_>>();
+
+        alive
+            .into_iter()
+            .map(|(obj, origin)| LeakedObject {
+                type_: obj.type_(),
+                // Don't count the reference that was just taken.
+                ref_count: obj.ref_count() - 1,
+                origin,
+            })
+            .collect()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Panics with a report of all tracked objects that are still alive.
+    #[track_caller]
+    pub fn assert_no_leaks(&self) {
+        let leaked = self.leaked();
+        if leaked.is_empty() {
+            return;
+        }
+
+        let mut report = format!("{} tracked object(s) still alive:", leaked.len());
+        for leaked in &leaked {
+            report.push_str(&format!("\n  {leaked}"));
+        }
+        panic!("{report}");
+    }
+}
+
+impl Default for LeakTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for LeakTracker {
+    fn drop(&mut self) {
+        #[cfg(feature = "leak-tracker")]
+        {
+            let state = Rc::downgrade(&self.state);
+            let _ = TRACKERS.try_with(|trackers| {
+                trackers
+                    .borrow_mut()
+                    .retain(|tracker| !Weak::ptr_eq(tracker, &state))
+            });
+        }
+
+        if !std::thread::panicking() {
+            self.assert_no_leaks();
+        }
+    }
+}
+
+impl fmt::Debug for LeakTracker {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LeakTracker")
+            .field("tracked", &self.state.objects.borrow().len())
+            .finish()
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// An object reported by [`LeakTracker::leaked`].
+pub struct LeakedObject {
+    type_: Type,
+    ref_count: u32,
+    origin: Rc<Origin>,
+}
+
+impl LeakedObject {
+    // rustdoc-stripper-ignore-next
+    /// The type of the object.
+    pub fn type_(&self) -> Type {
+        self.type_
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// The reference count of the object at the time it was reported.
+    pub fn ref_count(&self) -> u32 {
+        self.ref_count
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Where the object was created or passed to [`LeakTracker::track`].
+    pub fn origin(&self) -> String {
+        self.origin.to_string()
+    }
+}
+
+impl fmt::Debug for LeakedObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LeakedObject")
+            .field("type_", &self.type_)
+            .field("ref_count", &self.ref_count)
+            .field("origin", &self.origin.to_string())
+            .finish()
+    }
+}
+
+impl fmt::Display for LeakedObject {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} (ref count {}) created at {}",
+            self.type_, self.ref_count, self.origin
+        )
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Called when an instance of a Rust subclass was constructed.
+#[cfg(feature = "leak-tracker")]
+pub(crate) fn object_constructed(obj: &Object) {
+    let trackers = TRACKERS.with(|trackers| {
+        let mut trackers = trackers.borrow_mut();
+        trackers.retain(|tracker| tracker.strong_count() > 0);
+        trackers
+            .iter()
+            .filter_map(Weak::upgrade)
+            .filter(|tracker| match &tracker.filter {
+                Filter::None => false,
+                Filter::Types(types) => types.iter().any(|type_| obj.type_().is_a(*type_)),
+                Filter::AllSubclasses => true,
+            })
+            .collect::<Vec<_>>()
+    });
+    if trackers.is_empty() {
+        return;
+    }
+
+    let origin = Rc::new(Origin::Backtrace(Backtrace::force_capture()));
+    for tracker in trackers {
+        tracker.objects.borrow_mut().push(Tracked {
+            object: obj.downgrade(),
+            origin: origin.clone(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as glib, subclass::prelude::*};
+
+    mod imp {
+        use super::*;
+
+        #[derive(Default)]
+        pub struct Leaky {
+            pub other: RefCell<Option<Object>>,
+        }
+
+        #[glib::object_subclass]
+        impl ObjectSubclass for Leaky {
+            const NAME: &'static str = "LeakTrackerTestLeaky";
+            type Type = super::Leaky;
+        }
+
+        impl ObjectImpl for Leaky {}
+    }
+
+    glib::wrapper! {
+        pub struct Leaky(ObjectSubclass<imp::Leaky>);
+    }
+
+    #[test]
+    fn test_track() {
+        let tracker = LeakTracker::new();
+        let obj = Object::new::<Object>();
+        tracker.track(&obj);
+
+        let leaked = tracker.leaked();
+        assert_eq!(leaked.len(), 1);
+        assert_eq!(leaked[0].type_(), Object::static_type());
+        assert_eq!(leaked[0].ref_count(), 1);
+        assert!(leaked[0].origin().contains(file!()));
+
+        drop(obj);
+        assert!(tracker.leaked().is_empty());
+    }
+
+    #[cfg(feature = "leak-tracker")]
+    #[test]
+    fn test_subclasses() {
+        let tracker = LeakTracker::for_types(&[Leaky::static_type()]);
+        let untracked = Object::new::<Object>();
+        let leaky = Object::new::<Leaky>();
+        assert_eq!(tracker.leaked().len(), 1);
+
+        // Create a reference cycle and break it again.
+        leaky.imp().other.replace(Some(leaky.clone().upcast()));
+        drop(leaky);
+        let leaked = tracker.leaked();
+        assert_eq!(leaked.len(), 1);
+        assert_eq!(leaked[0].type_(), Leaky::static_type());
+        assert_eq!(leaked[0].ref_count(), 1);
+
+        drop(untracked);
+        drop(leaked);
+        let cycle = tracker.state.objects.borrow()[0].object.upgrade().unwrap();
+        cycle
+            .downcast_ref::<Leaky>()
+            .unwrap()
+            .imp()
+            .other
+            .replace(None);
+        drop(cycle);
+        tracker.assert_no_leaks();
+    }
+
+    #[test]
+    #[should_panic(expected = "1 tracked object(s) still alive")]
+    fn test_leak_panics_on_drop() {
+        let tracker = LeakTracker::new();
+        let leaky = Object::new::<Leaky>();
+        tracker.track(&leaky);
+        leaky.imp().other.replace(Some(leaky.clone().upcast()));
+        drop(leaky);
+        drop(tracker);
+    }
+}
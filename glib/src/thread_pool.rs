@@ -1,17 +1,27 @@
 // Take a look at the license at the top of the repository in the LICENSE file.
 
-use std::{panic, ptr};
+use std::{
+    cmp::Ordering,
+    marker::PhantomData,
+    mem, panic, ptr,
+    sync::{
+        Arc, Condvar, Mutex, Once,
+        atomic::{self, AtomicBool, AtomicU64},
+        mpsc,
+    },
+    thread,
+};
 
 #[cfg(feature = "futures")]
 use futures_channel::oneshot;
 #[cfg(feature = "futures")]
 use std::future::Future;
 
-use crate::{ffi, translate::*};
+use crate::{Priority, ffi, translate::*};
 
 #[derive(Debug)]
 #[doc(alias = "GThreadPool")]
-pub struct ThreadPool(ptr::NonNull<ffi::GThreadPool>);
+pub struct ThreadPool(ptr::NonNull<ffi::GThreadPool>, Once);
 
 unsafe impl Send for ThreadPool {}
 unsafe impl Sync for ThreadPool {}
@@ -24,7 +34,7 @@ unsafe impl Sync for ThreadPool {}
 /// allowing it to complete but discarding the return value.
 #[derive(Debug)]
 pub struct ThreadHandle<T> {
-    rx: std::sync::mpsc::Receiver<std::thread::Result<T>>,
+    rx: mpsc::Receiver<thread::Result<T>>,
 }
 
 impl<T> ThreadHandle<T> {
@@ -35,11 +45,133 @@ impl<T> ThreadHandle<T> {
     /// thread, or `Err` if the thread panicked. This function will return immediately if the
     /// associated thread has already finished.
     #[inline]
-    pub fn join(self) -> std::thread::Result<T> {
+    pub fn join(self) -> thread::Result<T> {
         self.rx.recv().unwrap()
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// A scope for pushing tasks that borrow non-`'static` data to a [`ThreadPool`].
+///
+/// Created by [`ThreadPool::scope`].
+#[derive(Debug)]
+pub struct ThreadPoolScope<'scope, 'env: 'scope> {
+    pool: ptr::NonNull<ffi::GThreadPool>,
+    data: Arc<ScopeData>,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+unsafe impl Send for ThreadPoolScope<'_, '_> {}
+unsafe impl Sync for ThreadPoolScope<'_, '_> {}
+
+#[derive(Debug)]
+struct ScopeData {
+    pending: Mutex<usize>,
+    finished: Condvar,
+    a_task_panicked: AtomicBool,
+}
+
+impl ScopeData {
+    fn increment_pending(&self) {
+        *self.pending.lock().unwrap() += 1;
+    }
+
+    fn decrement_pending(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        *pending -= 1;
+        if *pending == 0 {
+            self.finished.notify_all();
+        }
+    }
+
+    fn wait_pending(&self) {
+        let mut pending = self.pending.lock().unwrap();
+        while *pending > 0 {
+            pending = self.finished.wait(pending).unwrap();
+        }
+    }
+
+    fn set_task_panicked(&self) {
+        self.a_task_panicked.store(true, atomic::Ordering::Relaxed);
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A handle to a task pushed with [`ThreadPoolScope::push`].
+///
+/// Like [`ThreadHandle`], but the task is guaranteed to finish before the scope ends.
+#[derive(Debug)]
+pub struct ScopedThreadHandle<'scope, T> {
+    rx: Option<mpsc::Receiver<thread::Result<T>>>,
+    data: Arc<ScopeData>,
+    scope: PhantomData<&'scope ()>,
+}
+
+impl<T> ScopedThreadHandle<'_, T> {
+    // rustdoc-stripper-ignore-next
+    /// Waits for the associated task to finish.
+    ///
+    /// Returns `Ok` with the value returned from the task, or `Err` if the task panicked.
+    #[inline]
+    pub fn join(mut self) -> thread::Result<T> {
+        self.rx.take().unwrap().recv().unwrap()
+    }
+}
+
+impl<T> Drop for ScopedThreadHandle<'_, T> {
+    fn drop(&mut self) {
+        // A panic of an already finished task would go unnoticed otherwise. Tasks that are still
+        // running notice the closed channel themselves.
+        if let Some(rx) = self.rx.take()
+            && let Ok(Err(_)) = rx.try_recv()
+        {
+            self.data.set_task_panicked();
+        }
+    }
+}
+
+impl<'scope> ThreadPoolScope<'scope, '_> {
+    // rustdoc-stripper-ignore-next
+    /// Pushes a task that may borrow data from outside the scope.
+    ///
+    /// On error the task is still queued by GLib and runs once a thread of the pool is available.
+    #[doc(alias = "g_thread_pool_push")]
+    pub fn push<T: Send + 'scope, F: FnOnce() -> T + Send + 'scope>(
+        &'scope self,
+        func: F,
+    ) -> Result<ScopedThreadHandle<'scope, T>, crate::Error> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let data = self.data.clone();
+        let func: Box<dyn FnOnce() + Send + 'scope> = Box::new(move || {
+            // Everything borrowing from the scope has to be dropped before the scope can end.
+            {
+                let res = panic::catch_unwind(panic::AssertUnwindSafe(func));
+                if let Err(mpsc::SendError(Err(_))) = tx.send(res) {
+                    data.set_task_panicked();
+                }
+                drop(tx);
+            }
+            data.decrement_pending();
+        });
+        // SAFETY: `ThreadPool::scope` doesn't return before all its tasks finished.
+        let func = unsafe {
+            mem::transmute::<Box<dyn FnOnce() + Send + 'scope>, Box<dyn FnOnce() + Send + 'static>>(
+                func,
+            )
+        };
+
+        self.data.increment_pending();
+        unsafe { push_task(self.pool, Priority::DEFAULT, func)? };
+
+        Ok(ScopedThreadHandle {
+            rx: Some(rx),
+            data: self.data.clone(),
+            scope: PhantomData,
+        })
+    }
+}
+
 impl ThreadPool {
     #[doc(alias = "g_thread_pool_new")]
     pub fn shared(max_threads: Option<u32>) -> Result<Self, crate::Error> {
@@ -55,7 +187,7 @@ impl ThreadPool {
             if pool.is_null() {
                 Err(from_glib_full(err))
             } else {
-                Ok(ThreadPool(ptr::NonNull::new_unchecked(pool)))
+                Ok(ThreadPool(ptr::NonNull::new_unchecked(pool), Once::new()))
             }
         }
     }
@@ -74,7 +206,7 @@ impl ThreadPool {
             if pool.is_null() {
                 Err(from_glib_full(err))
             } else {
-                Ok(ThreadPool(ptr::NonNull::new_unchecked(pool)))
+                Ok(ThreadPool(ptr::NonNull::new_unchecked(pool), Once::new()))
             }
         }
     }
@@ -84,26 +216,93 @@ impl ThreadPool {
         &self,
         func: F,
     ) -> Result<ThreadHandle<T>, crate::Error> {
-        let (tx, rx) = std::sync::mpsc::sync_channel(1);
-        unsafe {
-            let func: Box<dyn FnOnce() + Send + 'static> = Box::new(move || {
-                let _ = tx.send(panic::catch_unwind(panic::AssertUnwindSafe(func)));
+        self.push_with_priority(Priority::DEFAULT, func)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Pushes a task with the given priority.
+    ///
+    /// Queued tasks with a higher priority, i.e. a lower value, are run first. Tasks with the same
+    /// priority are run in the order they were pushed.
+    ///
+    /// Tasks are only sorted by priority once the first task with a priority other than
+    /// [`Priority::DEFAULT`] has been pushed. From then on, pushing a task takes time proportional to
+    /// the number of queued tasks.
+    #[doc(alias = "g_thread_pool_push")]
+    #[doc(alias = "g_thread_pool_set_sort_function")]
+    pub fn push_with_priority<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(
+        &self,
+        priority: Priority,
+        func: F,
+    ) -> Result<ThreadHandle<T>, crate::Error> {
+        let (tx, rx) = mpsc::sync_channel(1);
+        let func: Box<dyn FnOnce() + Send + 'static> = Box::new(move || {
+            let _ = tx.send(panic::catch_unwind(panic::AssertUnwindSafe(func)));
+        });
+
+        // The sequence numbers of tasks queued before keep them in the order they were pushed.
+        if priority != Priority::DEFAULT {
+            self.1.call_once(|| unsafe {
+                ffi::g_thread_pool_set_sort_function(
+                    self.0.as_ptr(),
+                    Some(sort_func),
+                    ptr::null_mut(),
+                );
             });
-            let func = Box::new(func);
-            let mut err = ptr::null_mut();
+        }
 
-            let func = Box::into_raw(func);
-            let ret: bool = from_glib(ffi::g_thread_pool_push(
-                self.0.as_ptr(),
-                func as *mut _,
-                &mut err,
-            ));
-            if ret {
-                Ok(ThreadHandle { rx })
-            } else {
-                let _ = Box::from_raw(func);
-                Err(from_glib_full(err))
+        unsafe { push_task(self.0, priority, func)? };
+
+        Ok(ThreadHandle { rx })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Creates a scope for pushing tasks that borrow non-`'static` data, like
+    /// [`std::thread::scope`].
+    ///
+    /// All tasks pushed with [`ThreadPoolScope::push`] have finished when this returns. If any of
+    /// them panicked and its handle was not joined, this panics once all tasks finished.
+    ///
+    /// Calling this from a task of the same pool blocks forever if no other thread of the pool is
+    /// available to run the scoped tasks.
+    ///
+    /// ```
+    /// let pool = glib::ThreadPool::exclusive(4).unwrap();
+    /// let mut chunks = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+    ///
+    /// pool.scope(|s| {
+    ///     for chunk in &mut chunks {
+    ///         s.push(move || chunk.iter_mut().for_each(|v| *v *= 2))
+    ///             .unwrap();
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(chunks, [[2, 4], [6, 8], [10, 12]]);
+    /// ```
+    pub fn scope<'env, F, T>(&self, f: F) -> T
+    where
+        F: for<'scope> FnOnce(&'scope ThreadPoolScope<'scope, 'env>) -> T,
+    {
+        let scope = ThreadPoolScope {
+            pool: self.0,
+            data: Arc::new(ScopeData {
+                pending: Mutex::new(0),
+                finished: Condvar::new(),
+                a_task_panicked: AtomicBool::new(false),
+            }),
+            scope: PhantomData,
+            env: PhantomData,
+        };
+
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| f(&scope)));
+        scope.data.wait_pending();
+
+        match res {
+            Err(e) => panic::resume_unwind(e),
+            Ok(_) if scope.data.a_task_panicked.load(atomic::Ordering::Relaxed) => {
+                panic!("a scoped thread pool task panicked")
             }
+            Ok(res) => res,
         }
     }
 
@@ -112,7 +311,7 @@ impl ThreadPool {
         &self,
         func: F,
     ) -> Result<
-        impl Future<Output = std::thread::Result<T>> + Send + Sync + 'static + use<T, F>,
+        impl Future<Output = thread::Result<T>> + Send + Sync + 'static + use<T, F>,
         crate::Error,
     > {
         let (sender, receiver) = oneshot::channel();
@@ -124,6 +323,37 @@ impl ThreadPool {
         Ok(async move { receiver.await.expect("Dropped before executing") })
     }
 
+    // rustdoc-stripper-ignore-next
+    /// Runs the future returned by `func` to completion on a thread of the pool.
+    ///
+    /// Every thread of the pool has its own [`MainContext`](crate::MainContext), which is the
+    /// thread-default main context while the future runs. The future doesn't have to be `Send`,
+    /// and asynchronous operations started from it complete on the same thread.
+    #[cfg(feature = "futures")]
+    pub fn spawn_future<
+        T: Send + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = T> + 'static,
+    >(
+        &self,
+        func: F,
+    ) -> Result<
+        impl Future<Output = thread::Result<T>> + Send + Sync + 'static + use<T, F, Fut>,
+        crate::Error,
+    > {
+        thread_local! {
+            static WORKER_CONTEXT: crate::MainContext = crate::MainContext::new();
+        }
+
+        self.push_future(move || {
+            WORKER_CONTEXT.with(|context| {
+                context
+                    .with_thread_default(|| context.block_on(func()))
+                    .expect("Worker main context acquired by another thread")
+            })
+        })
+    }
+
     #[doc(alias = "g_thread_pool_set_max_threads")]
     pub fn set_max_threads(&self, max_threads: Option<u32>) -> Result<(), crate::Error> {
         unsafe {
@@ -220,10 +450,61 @@ impl Drop for ThreadPool {
     }
 }
 
-unsafe extern "C" fn spawn_func(func: ffi::gpointer, _data: ffi::gpointer) {
+struct Task {
+    priority: Priority,
+    sequence: u64,
+    func: Box<dyn FnOnce() + Send + 'static>,
+}
+
+unsafe fn push_task(
+    pool: ptr::NonNull<ffi::GThreadPool>,
+    priority: Priority,
+    func: Box<dyn FnOnce() + Send + 'static>,
+) -> Result<(), crate::Error> {
+    static SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
     unsafe {
-        let func: Box<Box<dyn FnOnce()>> = Box::from_raw(func as *mut _);
-        func()
+        let task = Box::new(Task {
+            priority,
+            sequence: SEQUENCE.fetch_add(1, atomic::Ordering::Relaxed),
+            func,
+        });
+        let mut err = ptr::null_mut();
+
+        // GLib queues the task even if starting a new thread failed, so it's not freed on errors.
+        let ret: bool = from_glib(ffi::g_thread_pool_push(
+            pool.as_ptr(),
+            Box::into_raw(task) as *mut _,
+            &mut err,
+        ));
+        if ret {
+            Ok(())
+        } else {
+            Err(from_glib_full(err))
+        }
+    }
+}
+
+unsafe extern "C" fn spawn_func(task: ffi::gpointer, _data: ffi::gpointer) {
+    unsafe {
+        let task: Box<Task> = Box::from_raw(task as *mut _);
+        (task.func)()
+    }
+}
+
+unsafe extern "C" fn sort_func(
+    a: ffi::gconstpointer,
+    b: ffi::gconstpointer,
+    _data: ffi::gpointer,
+) -> i32 {
+    unsafe {
+        let a = &*(a as *const Task);
+        let b = &*(b as *const Task);
+        match (a.priority, a.sequence).cmp(&(b.priority, b.sequence)) {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        }
     }
 }
 
@@ -249,6 +530,79 @@ mod tests {
         assert_eq!(receiver.recv(), Ok(true));
     }
 
+    #[test]
+    fn test_push_with_priority() {
+        let p = ThreadPool::exclusive(1).unwrap();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        // Block the only thread so that the following tasks are queued and sorted.
+        let (unblock, blocked) = mpsc::channel::<()>();
+        p.push(move || blocked.recv().unwrap()).unwrap();
+
+        let handles = [
+            (Priority::LOW, "low"),
+            (Priority::DEFAULT, "default 1"),
+            (Priority::HIGH, "high"),
+            (Priority::DEFAULT, "default 2"),
+        ]
+        .map(|(priority, name)| {
+            let order = order.clone();
+            p.push_with_priority(priority, move || order.lock().unwrap().push(name))
+                .unwrap()
+        });
+
+        unblock.send(()).unwrap();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(
+            *order.lock().unwrap(),
+            ["high", "default 1", "default 2", "low"]
+        );
+    }
+
+    #[test]
+    fn test_scope() {
+        let p = ThreadPool::exclusive(2).unwrap();
+        let mut values = [1, 2, 3, 4];
+        let offset = 10;
+
+        let sum = p.scope(|s| {
+            let handles = values
+                .iter_mut()
+                .map(|v| {
+                    s.push(move || {
+                        *v += offset;
+                        *v
+                    })
+                    .unwrap()
+                })
+                .collect::<Vec<_>>();
+            handles.into_iter().map(|h| h.join().unwrap()).sum::<i32>()
+        });
+
+        assert_eq!(sum, 50);
+        assert_eq!(values, [11, 12, 13, 14]);
+    }
+
+    #[test]
+    fn test_scope_panic() {
+        let p = ThreadPool::exclusive(1).unwrap();
+
+        // Joined panics are returned from the handle.
+        let res = p.scope(|s| s.push(|| panic!("joined")).unwrap().join());
+        assert!(res.is_err());
+
+        // Unjoined panics are propagated by the scope.
+        let res = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            p.scope(|s| {
+                s.push(|| panic!("unjoined")).unwrap();
+            })
+        }));
+        assert!(res.is_err());
+    }
+
     #[cfg(feature = "futures")]
     #[test]
     fn test_push_future() {
@@ -260,4 +614,26 @@ mod tests {
         let res = c.block_on(fut);
         assert!(res.unwrap());
     }
+
+    #[cfg(feature = "futures")]
+    #[test]
+    fn test_spawn_future() {
+        let c = crate::MainContext::new();
+        let p = ThreadPool::exclusive(1).unwrap();
+
+        let fut = p
+            .spawn_future(|| async {
+                // Not `Send`, and spawned on the thread-default main context of the worker.
+                let value = std::rc::Rc::new(21);
+                let context = crate::MainContext::ref_thread_default();
+                assert!(context.is_owner());
+                context
+                    .spawn_local(async move { *value * 2 })
+                    .await
+                    .unwrap()
+            })
+            .unwrap();
+
+        assert_eq!(c.block_on(fut).unwrap(), 42);
+    }
 }
@@ -15,4 +15,98 @@ impl TimeZone {
             (res, time)
         }
     }
+
+    // Creates a time zone with a fixed offset from UTC in seconds.
+    #[cfg(any(feature = "chrono", feature = "time", feature = "jiff"))]
+    pub(crate) fn from_offset_seconds(seconds: i32) -> Self {
+        #[cfg(feature = "v2_58")]
+        {
+            Self::from_offset(seconds)
+        }
+        #[cfg(not(feature = "v2_58"))]
+        {
+            let sign = if seconds < 0 { '-' } else { '+' };
+            let seconds = seconds.unsigned_abs();
+            #[allow(deprecated)]
+            Self::new(Some(&format!(
+                "{sign}{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            )))
+        }
+    }
+
+    // Creates a time zone for an IANA time zone identifier, returns `None` if it is unknown.
+    #[cfg(feature = "jiff")]
+    fn from_iana_name(name: &str) -> Option<Self> {
+        #[cfg(feature = "v2_68")]
+        {
+            Self::from_identifier(Some(name))
+        }
+        #[cfg(not(feature = "v2_68"))]
+        {
+            // Unknown identifiers can't be detected here, they result in UTC.
+            #[allow(deprecated)]
+            Some(Self::new(Some(name)))
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl From<chrono::FixedOffset> for TimeZone {
+    fn from(offset: chrono::FixedOffset) -> Self {
+        Self::from_offset_seconds(offset.local_minus_utc())
+    }
+}
+
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+impl From<chrono::Utc> for TimeZone {
+    fn from(_: chrono::Utc) -> Self {
+        Self::utc()
+    }
+}
+
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+impl From<time::UtcOffset> for TimeZone {
+    fn from(offset: time::UtcOffset) -> Self {
+        Self::from_offset_seconds(offset.whole_seconds())
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts the time zone by its IANA identifier if it has one, otherwise fails unless the time
+/// zone has a fixed offset.
+#[cfg(feature = "jiff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+impl TryFrom<&jiff::tz::TimeZone> for TimeZone {
+    type Error = crate::BoolError;
+
+    fn try_from(tz: &jiff::tz::TimeZone) -> Result<Self, Self::Error> {
+        if let Some(tz) = tz.iana_name().and_then(Self::from_iana_name) {
+            return Ok(tz);
+        }
+        let offset = tz
+            .to_fixed_offset()
+            .map_err(|_| crate::bool_error!("Time zone has no IANA identifier or fixed offset"))?;
+        Ok(Self::from_offset_seconds(offset.seconds()))
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// Converts the time zone by its identifier, which fails if it is not an IANA identifier known
+/// to jiff.
+#[cfg(all(feature = "jiff", feature = "v2_58"))]
+#[cfg_attr(docsrs, doc(cfg(all(feature = "jiff", feature = "v2_58"))))]
+impl TryFrom<&TimeZone> for jiff::tz::TimeZone {
+    type Error = crate::BoolError;
+
+    fn try_from(tz: &TimeZone) -> Result<Self, Self::Error> {
+        let identifier = tz.identifier();
+        jiff::tz::TimeZone::get(&identifier)
+            .map_err(|err| crate::bool_error!("Unknown time zone '{}': {}", identifier, err))
+    }
 }
@@ -14,6 +14,10 @@
 //! Supported types are `bool`, `i8`, `u8`, `i32`, `u32`, `i64`, `u64`, `f32`,
 //! `f64`, `String` and objects (`T: IsA<Object>`).
 //!
+//! Tuples, `Result` and `HashMap` of any `Clone` types, and arrays of `Clone` types
+//! that can be stored in a `Value` themselves, are stored as boxed types that are registered
+//! on first use. Arrays of `&str` are stored as a `StrV` instead.
+//!
 //! # Examples
 //!
 //! ```
@@ -41,6 +45,7 @@
 //! ```
 
 use std::{
+    collections::HashMap,
     convert::Infallible,
     error,
     ffi::CStr,
@@ -1541,6 +1546,108 @@ impl ToValueOptional for BoxedValue {
     }
 }
 
+// rustdoc-stripper-ignore-next
+/// Implements the `Value` traits for a generic Rust type by storing a clone of it in a boxed
+/// type that is registered on first use, see
+/// [`register_boxed_type_on_demand`](crate::subclass::register_boxed_type_on_demand).
+macro_rules! on_demand_boxed {
+    (<$($generic:ident),+> $name:ty) => {
+        on_demand_boxed!(@impl [$($generic: Clone + 'static),+] $name);
+    };
+    (@impl [$($generics:tt)+] $name:ty) => {
+        impl<$($generics)+> StaticType for $name {
+            #[inline]
+            fn static_type() -> Type {
+                crate::subclass::register_boxed_type_on_demand::<Self>()
+            }
+        }
+
+        impl<$($generics)+> ValueType for $name {
+            type Type = Self;
+        }
+
+        unsafe impl<'a, $($generics)+> FromValue<'a> for $name {
+            type Checker = GenericValueTypeChecker<Self>;
+
+            #[inline]
+            unsafe fn from_value(value: &'a Value) -> Self {
+                unsafe {
+                    let ptr = gobject_ffi::g_value_get_boxed(value.to_glib_none().0);
+                    debug_assert!(!ptr.is_null());
+                    (*(ptr as *const Self)).clone()
+                }
+            }
+        }
+
+        unsafe impl<'a, $($generics)+> FromValue<'a> for &'a $name {
+            type Checker = GenericValueTypeChecker<Self>;
+
+            #[inline]
+            unsafe fn from_value(value: &'a Value) -> Self {
+                unsafe {
+                    let ptr = gobject_ffi::g_value_get_boxed(value.to_glib_none().0);
+                    debug_assert!(!ptr.is_null());
+                    &*(ptr as *const $name)
+                }
+            }
+        }
+
+        impl<$($generics)+> ToValue for $name {
+            #[inline]
+            fn to_value(&self) -> Value {
+                self.clone().into()
+            }
+
+            #[inline]
+            fn value_type(&self) -> Type {
+                Self::static_type()
+            }
+        }
+
+        impl<$($generics)+> From<$name> for Value {
+            #[inline]
+            fn from(v: $name) -> Self {
+                unsafe {
+                    let mut value = Value::from_type_unchecked(<$name>::static_type());
+                    gobject_ffi::g_value_take_boxed(
+                        value.to_glib_none_mut().0,
+                        Box::into_raw(Box::new(v)) as ffi::gconstpointer,
+                    );
+                    value
+                }
+            }
+        }
+
+        impl<$($generics)+> crate::HasParamSpec for $name {
+            type ParamSpec = crate::ParamSpecBoxed;
+            type SetValue = Self;
+            type BuilderFn = fn(&str) -> crate::ParamSpecBoxedBuilder<'_, Self>;
+
+            fn param_spec_builder() -> Self::BuilderFn {
+                |name| Self::ParamSpec::builder(name)
+            }
+        }
+    };
+}
+
+on_demand_boxed!(<T0> (T0,));
+on_demand_boxed!(<T0, T1> (T0, T1));
+on_demand_boxed!(<T0, T1, T2> (T0, T1, T2));
+on_demand_boxed!(<T0, T1, T2, T3> (T0, T1, T2, T3));
+on_demand_boxed!(<T0, T1, T2, T3, T4> (T0, T1, T2, T3, T4));
+on_demand_boxed!(<T0, T1, T2, T3, T4, T5> (T0, T1, T2, T3, T4, T5));
+on_demand_boxed!(<T0, T1, T2, T3, T4, T5, T6> (T0, T1, T2, T3, T4, T5, T6));
+on_demand_boxed!(<T0, T1, T2, T3, T4, T5, T6, T7> (T0, T1, T2, T3, T4, T5, T6, T7));
+on_demand_boxed!(<T0, T1, T2, T3, T4, T5, T6, T7, T8> (T0, T1, T2, T3, T4, T5, T6, T7, T8));
+on_demand_boxed!(<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9> (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9));
+on_demand_boxed!(<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10> (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10));
+on_demand_boxed!(<T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11> (T0, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11));
+// Only arrays of value types are boxed so that `[&str; N]` keeps being stored as a `StrV`
+// through the `[&str]` implementation.
+on_demand_boxed!(@impl [T: ValueType + Clone, const N: usize] [T; N]);
+on_demand_boxed!(<T, E> Result<T, E>);
+on_demand_boxed!(<K, V, S> HashMap<K, V, S>);
+
 #[cfg(test)]
 mod tests {
     use std::num::NonZeroI32;
@@ -0,0 +1,558 @@
+// Take a look at the license at the top of the repository in the LICENSE file.
+
+// rustdoc-stripper-ignore-next
+//! Collections of weak references to objects that remove their entries once the referenced
+//! objects are disposed.
+//!
+//! Entries are removed from a weak notify callback of the object, see
+//! [`ObjectExt::add_weak_ref_notify_local`], so no dead entries accumulate over time.
+//!
+//! These collections are not thread-safe and the objects must be disposed on the same thread
+//! where they were inserted.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, hash_map},
+    fmt,
+    hash::Hash,
+    rc::{Rc, Weak},
+};
+
+use crate::{
+    object::{ObjectType, WeakRefNotify},
+    prelude::*,
+};
+
+// rustdoc-stripper-ignore-next
+/// Adds a weak ref notify to `obj` that calls `prune` with the collection's entries once `obj`
+/// is disposed, unless the collection was dropped before.
+fn watch<T: ObjectType, C: 'static>(
+    obj: &T,
+    entries: &Rc<RefCell<C>>,
+    prune: impl FnOnce(&mut C) + 'static,
+) -> WeakRefNotify<T> {
+    let entries: Weak<RefCell<C>> = Rc::downgrade(entries);
+    obj.add_weak_ref_notify_local(move || {
+        if let Some(entries) = entries.upgrade() {
+            prune(&mut entries.borrow_mut());
+        }
+    })
+}
+
+// rustdoc-stripper-ignore-next
+/// Disconnects the weak ref notifies of entries that were removed from a collection.
+///
+/// This must be called after releasing the borrow of the collection's entries as the objects
+/// might be disposed while disconnecting.
+fn disconnect_all<T: ObjectType>(notifies: impl IntoIterator<Item = WeakRefNotify<T>>) {
+    for notify in notifies {
+        notify.disconnect();
+    }
+}
+
+// rustdoc-stripper-ignore-next
+/// A set of weak references to objects.
+///
+/// Objects are removed from the set once they are disposed.
+pub struct WeakRefSet<T: ObjectType> {
+    entries: Rc<RefCell<HashMap<usize, WeakRefNotify<T>>>>,
+}
+
+impl<T: ObjectType> WeakRefSet<T> {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new, empty set.
+    pub fn new() -> Self {
+        Self {
+            entries: Default::default(),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Adds `obj` to the set.
+    ///
+    /// Returns `false` if `obj` was already part of the set.
+    pub fn insert(&mut self, obj: &T) -> bool {
+        let key = obj.as_ptr() as usize;
+        if self.entries.borrow().contains_key(&key) {
+            return false;
+        }
+
+        let notify = watch(obj, &self.entries, move |entries| {
+            entries.remove(&key);
+        });
+        self.entries.borrow_mut().insert(key, notify);
+
+        true
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes `obj` from the set.
+    ///
+    /// Returns `false` if `obj` was not part of the set.
+    pub fn remove(&mut self, obj: &T) -> bool {
+        let notify = self.entries.borrow_mut().remove(&(obj.as_ptr() as usize));
+        let removed = notify.is_some();
+        disconnect_all(notify);
+
+        removed
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if `obj` is part of the set.
+    pub fn contains(&self, obj: &T) -> bool {
+        self.entries.borrow().contains_key(&(obj.as_ptr() as usize))
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of objects in the set.
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if the set contains no objects.
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes all objects from the set.
+    pub fn clear(&mut self) {
+        let notifies = self
+            .entries
+            .borrow_mut()
+            .drain()
+            .map(|(_, notify)| notify)
+            .collect::<Vec<_>>();
+        disconnect_all(notifies);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns strong references to all objects in the set, in arbitrary order.
+    pub fn iter(&self) -> std::vec::IntoIter<T> {
+        self.entries
+            .borrow()
+            .values()
+            .filter_map(WeakRefNotify::upgrade)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<T: ObjectType> Default for WeakRefSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ObjectType> Drop for WeakRefSet<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: ObjectType> fmt::Debug for WeakRefSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ObjectType> Extend<T> for WeakRefSet<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for obj in iter {
+            self.insert(&obj);
+        }
+    }
+}
+
+impl<'a, T: ObjectType> Extend<&'a T> for WeakRefSet<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        for obj in iter {
+            self.insert(obj);
+        }
+    }
+}
+
+impl<T: ObjectType> FromIterator<T> for WeakRefSet<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+struct VecEntries<T: ObjectType> {
+    entries: Vec<(u64, WeakRefNotify<T>)>,
+    next_id: u64,
+}
+
+// rustdoc-stripper-ignore-next
+/// A list of weak references to objects in insertion order.
+///
+/// Objects are removed from the list once they are disposed. The same object can be part of
+/// the list multiple times.
+pub struct WeakRefVec<T: ObjectType> {
+    entries: Rc<RefCell<VecEntries<T>>>,
+}
+
+impl<T: ObjectType> WeakRefVec<T> {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new, empty list.
+    pub fn new() -> Self {
+        Self {
+            entries: Rc::new(RefCell::new(VecEntries {
+                entries: Vec::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Appends `obj` to the end of the list.
+    pub fn push(&mut self, obj: &T) {
+        let id = {
+            let mut entries = self.entries.borrow_mut();
+            entries.next_id += 1;
+            entries.next_id
+        };
+
+        let notify = watch(obj, &self.entries, move |entries| {
+            entries.entries.retain(|(entry_id, _)| *entry_id != id);
+        });
+        self.entries.borrow_mut().entries.push((id, notify));
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes all occurrences of `obj` from the list.
+    ///
+    /// Returns `false` if `obj` was not part of the list.
+    pub fn remove(&mut self, obj: &T) -> bool {
+        let removed = {
+            let mut entries = self.entries.borrow_mut();
+            let (removed, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut entries.entries)
+                .into_iter()
+                .partition(|(_, notify)| notify.upgrade().as_ref() == Some(obj));
+            entries.entries = kept;
+            removed
+        };
+        let found = !removed.is_empty();
+        disconnect_all(removed.into_iter().map(|(_, notify)| notify));
+
+        found
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if `obj` is part of the list.
+    pub fn contains(&self, obj: &T) -> bool {
+        self.iter().any(|o| &o == obj)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of objects in the list.
+    pub fn len(&self) -> usize {
+        self.entries.borrow().entries.len()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if the list contains no objects.
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().entries.is_empty()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes all objects from the list.
+    pub fn clear(&mut self) {
+        let notifies = self
+            .entries
+            .borrow_mut()
+            .entries
+            .drain(..)
+            .map(|(_, notify)| notify)
+            .collect::<Vec<_>>();
+        disconnect_all(notifies);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns strong references to all objects in the list, in insertion order.
+    pub fn iter(&self) -> std::vec::IntoIter<T> {
+        self.entries
+            .borrow()
+            .entries
+            .iter()
+            .filter_map(|(_, notify)| notify.upgrade())
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<T: ObjectType> Default for WeakRefVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: ObjectType> Drop for WeakRefVec<T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<T: ObjectType> fmt::Debug for WeakRefVec<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T: ObjectType> Extend<T> for WeakRefVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for obj in iter {
+            self.push(&obj);
+        }
+    }
+}
+
+impl<'a, T: ObjectType> Extend<&'a T> for WeakRefVec<T> {
+    fn extend<I: IntoIterator<Item = &'a T>>(&mut self, iter: I) {
+        for obj in iter {
+            self.push(obj);
+        }
+    }
+}
+
+impl<T: ObjectType> FromIterator<T> for WeakRefVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut vec = Self::new();
+        vec.extend(iter);
+        vec
+    }
+}
+
+struct MapEntries<K, T: ObjectType> {
+    entries: HashMap<K, (u64, WeakRefNotify<T>)>,
+    next_id: u64,
+}
+
+// rustdoc-stripper-ignore-next
+/// A map from keys to weak references to objects.
+///
+/// Entries are removed from the map once their object is disposed.
+pub struct WeakRefMap<K: Hash + Eq + Clone + 'static, T: ObjectType> {
+    entries: Rc<RefCell<MapEntries<K, T>>>,
+}
+
+impl<K: Hash + Eq + Clone + 'static, T: ObjectType> WeakRefMap<K, T> {
+    // rustdoc-stripper-ignore-next
+    /// Creates a new, empty map.
+    pub fn new() -> Self {
+        Self {
+            entries: Rc::new(RefCell::new(MapEntries {
+                entries: HashMap::new(),
+                next_id: 0,
+            })),
+        }
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Inserts `obj` for `key`.
+    ///
+    /// Returns the object that was previously stored for `key`, if any.
+    pub fn insert(&mut self, key: K, obj: &T) -> Option<T> {
+        let id = {
+            let mut entries = self.entries.borrow_mut();
+            entries.next_id += 1;
+            entries.next_id
+        };
+
+        let notify = {
+            let key = key.clone();
+            watch(obj, &self.entries, move |entries| {
+                // The entry might have been replaced in the meantime.
+                if let hash_map::Entry::Occupied(entry) = entries.entries.entry(key)
+                    && entry.get().0 == id
+                {
+                    entry.remove();
+                }
+            })
+        };
+        let old = self.entries.borrow_mut().entries.insert(key, (id, notify));
+
+        old.and_then(|(_, notify)| {
+            let old = notify.upgrade();
+            notify.disconnect();
+            old
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes the object stored for `key`.
+    ///
+    /// Returns the removed object, if any.
+    pub fn remove(&mut self, key: &K) -> Option<T> {
+        let old = self.entries.borrow_mut().entries.remove(key);
+
+        old.and_then(|(_, notify)| {
+            let old = notify.upgrade();
+            notify.disconnect();
+            old
+        })
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns a strong reference to the object stored for `key`, if any.
+    pub fn get(&self, key: &K) -> Option<T> {
+        self.entries
+            .borrow()
+            .entries
+            .get(key)
+            .and_then(|(_, notify)| notify.upgrade())
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if an object is stored for `key`.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.entries.borrow().entries.contains_key(key)
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> usize {
+        self.entries.borrow().entries.len()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns `true` if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().entries.is_empty()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Removes all entries from the map.
+    pub fn clear(&mut self) {
+        let notifies = self
+            .entries
+            .borrow_mut()
+            .entries
+            .drain()
+            .map(|(_, (_, notify))| notify)
+            .collect::<Vec<_>>();
+        disconnect_all(notifies);
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all keys of the map, in arbitrary order.
+    pub fn keys(&self) -> std::vec::IntoIter<K> {
+        self.entries
+            .borrow()
+            .entries
+            .keys()
+            .cloned()
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    // rustdoc-stripper-ignore-next
+    /// Returns all keys together with strong references to their objects, in arbitrary order.
+    pub fn iter(&self) -> std::vec::IntoIter<(K, T)> {
+        self.entries
+            .borrow()
+            .entries
+            .iter()
+            .filter_map(|(key, (_, notify))| Some((key.clone(), notify.upgrade()?)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, T: ObjectType> Default for WeakRefMap<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone + 'static, T: ObjectType> Drop for WeakRefMap<K, T> {
+    fn drop(&mut self) {
+        self.clear();
+    }
+}
+
+impl<K: Hash + Eq + Clone + fmt::Debug + 'static, T: ObjectType> fmt::Debug for WeakRefMap<K, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Object;
+
+    #[test]
+    fn test_weak_ref_set() {
+        let mut set = WeakRefSet::new();
+        let obj1 = Object::new::<Object>();
+        let obj2 = Object::new::<Object>();
+
+        assert!(set.insert(&obj1));
+        assert!(!set.insert(&obj1));
+        assert!(set.insert(&obj2));
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&obj1));
+
+        drop(obj1);
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![obj2.clone()]);
+
+        assert!(set.remove(&obj2));
+        assert!(!set.remove(&obj2));
+        assert!(set.is_empty());
+
+        // The object must not be pruned from a dropped set.
+        set.insert(&obj2);
+        drop(set);
+        drop(obj2);
+    }
+
+    #[test]
+    fn test_weak_ref_vec() {
+        let mut vec = WeakRefVec::new();
+        let obj1 = Object::new::<Object>();
+        let obj2 = Object::new::<Object>();
+
+        vec.push(&obj1);
+        vec.push(&obj2);
+        vec.push(&obj1);
+        assert_eq!(vec.len(), 3);
+        assert_eq!(
+            vec.iter().collect::<Vec<_>>(),
+            vec![obj1.clone(), obj2.clone(), obj1.clone()]
+        );
+
+        drop(obj1);
+        assert_eq!(vec.len(), 1);
+        assert!(vec.contains(&obj2));
+
+        assert!(vec.remove(&obj2));
+        assert!(vec.is_empty());
+    }
+
+    #[test]
+    fn test_weak_ref_map() {
+        let mut map = WeakRefMap::new();
+        let obj1 = Object::new::<Object>();
+        let obj2 = Object::new::<Object>();
+
+        assert_eq!(map.insert("a", &obj1), None);
+        assert_eq!(map.insert("b", &obj2), None);
+        assert_eq!(map.get(&"a"), Some(obj1.clone()));
+
+        // Replacing an entry must not prune the new one when the old object is disposed.
+        assert_eq!(map.insert("a", &obj2), Some(obj1.clone()));
+        drop(obj1);
+        assert_eq!(map.len(), 2);
+        assert_eq!(map.get(&"a"), Some(obj2.clone()));
+
+        drop(obj2);
+        assert!(map.is_empty());
+        assert_eq!(map.get(&"a"), None);
+    }
+}
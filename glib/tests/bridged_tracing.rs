@@ -0,0 +1,134 @@
+#![cfg(feature = "tracing")]
+
+use std::sync::{Arc, Mutex};
+
+use glib::{LogField, LogLevel, gstr};
+use tracing_subscriber::{Layer, layer::SubscriberExt};
+
+#[derive(Debug, PartialEq, Eq)]
+struct LoggedEvent {
+    level: LogLevel,
+    fields: Vec<(String, Option<String>)>,
+}
+
+/// Test the glib tracing layer.
+///
+/// We put everything into one test because we can only set the log writer func once.
+#[test]
+fn glib_tracing_layer() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let event_writer = events.clone();
+    glib::log_set_writer_func(move |level, fields| {
+        let fields = fields
+            .iter()
+            .map(|field| {
+                (
+                    field.key().to_string(),
+                    field.value_str().map(|s| s.to_owned()),
+                )
+            })
+            .collect();
+        event_writer
+            .lock()
+            .unwrap()
+            .push(LoggedEvent { level, fields });
+        glib::LogWriterOutput::Handled
+    });
+
+    let subscriber = tracing_subscriber::registry().with(glib::GlibTracingLayer::new());
+    let line = tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("request", id = 7);
+        let _enter = span.enter();
+        let inner = tracing::debug_span!("parse");
+        let _enter = inner.enter();
+        tracing::error!(user.name = "alice", priority = 1, "failed: {}", 42);
+        line!() - 1
+    });
+
+    let event = events.lock().unwrap().pop().unwrap();
+    assert_eq!(
+        event,
+        LoggedEvent {
+            level: LogLevel::Critical,
+            fields: vec![
+                ("PRIORITY".to_string(), Some("4".to_string())),
+                ("MESSAGE".to_string(), Some("failed: 42".to_string())),
+                (
+                    "GLIB_DOMAIN".to_string(),
+                    Some("bridged_tracing".to_string())
+                ),
+                ("CODE_FILE".to_string(), Some(file!().to_string())),
+                ("CODE_LINE".to_string(), Some(line.to_string())),
+                (
+                    "RUST_MODULE".to_string(),
+                    Some("bridged_tracing".to_string())
+                ),
+                (
+                    "TRACING_SPAN".to_string(),
+                    Some("request:parse".to_string())
+                ),
+                ("ID".to_string(), Some("7".to_string())),
+                ("USER_NAME".to_string(), Some("alice".to_string())),
+            ]
+        }
+    );
+}
+
+type CollectedEvent = (tracing::Level, String, Vec<(String, String)>);
+
+#[derive(Clone, Default)]
+struct Collector(Arc<Mutex<Vec<CollectedEvent>>>);
+
+struct Visitor(Vec<(String, String)>);
+
+impl tracing::field::Visit for Visitor {
+    fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+        self.0.push((field.name().to_owned(), format!("{value:?}")));
+    }
+}
+
+impl<S: tracing::Subscriber> Layer<S> for Collector {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        let mut visitor = Visitor(Vec::new());
+        event.record(&mut visitor);
+        self.0.lock().unwrap().push((
+            *event.metadata().level(),
+            event.metadata().target().to_owned(),
+            visitor.0,
+        ));
+    }
+}
+
+#[test]
+fn tracing_log_writer() {
+    let collector = Collector::default();
+    let subscriber = tracing_subscriber::registry().with(collector.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        glib::tracing_log_writer(
+            LogLevel::Warning,
+            &[
+                LogField::new(gstr!("GLIB_DOMAIN"), b"Gio"),
+                LogField::new(gstr!("MESSAGE"), b"Something happened"),
+                LogField::new(gstr!("CODE_LINE"), b"12"),
+            ],
+        );
+    });
+
+    let events = collector.0.lock().unwrap();
+    assert_eq!(
+        *events,
+        [(
+            tracing::Level::WARN,
+            "glib".to_owned(),
+            vec![
+                ("message".to_owned(), "Something happened".to_owned()),
+                ("domain".to_owned(), "\"Gio\"".to_owned()),
+                ("code.lineno".to_owned(), "12".to_owned()),
+            ]
+        )]
+    );
+}
@@ -65,3 +65,32 @@ pub fn to_value_boxed() {
             .unwrap()
     );
 }
+
+// Test that tuples, arrays, `Result` and `HashMap` are stored as boxed types registered on demand
+#[test]
+pub fn to_value_on_demand_boxed() {
+    let t = (1i32, String::from("abc"));
+    let v = t.to_value();
+    assert!(v.type_().is_a(glib::Type::BOXED));
+    assert_eq!(v.type_(), <(i32, String)>::static_type());
+    assert_ne!(v.type_(), <(String, i32)>::static_type());
+    assert_eq!(v.get::<(i32, String)>().unwrap(), t);
+    assert_eq!(v.get::<&(i32, String)>().unwrap(), &t);
+    assert!(v.get::<(String, i32)>().is_err());
+
+    let a = [1u8, 2, 3];
+    assert_eq!(Value::from(a).get::<[u8; 3]>().unwrap(), a);
+    assert!(a.to_value().get::<[u8; 4]>().is_err());
+    assert_eq!(["a", "b"].to_value().type_(), glib::StrV::static_type());
+
+    let r: Result<u32, String> = Err(String::from("failed"));
+    assert_eq!(r.to_value().get::<Result<u32, String>>().unwrap(), r);
+
+    let m = std::collections::HashMap::from([(String::from("a"), 1i32), (String::from("b"), 2)]);
+    assert_eq!(
+        m.to_value()
+            .get::<std::collections::HashMap<String, i32>>()
+            .unwrap(),
+        m
+    );
+}